@@ -0,0 +1,295 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use num::Round;
+use num_lib::{self, NumCast, ToPrimitive};
+
+pub use num::Rounding;
+
+use core::convert::TryFrom;
+use core::fmt as f;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Number of app units in one CSS pixel.
+pub const AU_PER_PX: i32 = 60;
+
+// .
+// . Fixed-point app unit, 1/60 of CSS pixel.
+// .
+
+/// Fixed-point scalar counting sub-pixel app units, like browser layout engines do.
+///
+/// Addition and subtraction are exact. Multiplication and division treat both
+/// operands as pixel values, e.g. `Au::from_px(2) * Au::from_px(3) == Au::from_px(6)`,
+/// and truncate the result towards zero. Arithmetic that leaves the `i32` range panics,
+/// use the `checked_*` methods of the underlying value to handle it.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Au(pub i32);
+
+impl Au {
+    /// Creates an instance from a raw number of app units.
+    #[inline]
+    pub fn new(value: i32) -> Au {
+        Au(value)
+    }
+
+    /// Creates an instance from whole pixels.
+    ///
+    /// # Panics
+    ///
+    /// When `px` is beyond `i32::MAX / AU_PER_PX` in either direction, see `checked_from_px`.
+    #[inline]
+    pub fn from_px(px: i32) -> Au {
+        Au::checked_from_px(px).expect("pixel value overflows app units")
+    }
+
+    /// Creates an instance from whole pixels, returns None when the value doesn't fit.
+    #[inline]
+    pub fn checked_from_px(px: i32) -> Option<Au> {
+        px.checked_mul(AU_PER_PX).map(Au)
+    }
+
+    /// Converts float pixels, returns None when the value doesn't fit or isn't finite.
+    pub fn from_f64_px(px: f64, rounding: Rounding) -> Option<Au> {
+        let value = rounding.apply(px * AU_PER_PX as f64);
+
+        if value.is_finite() && value >= i32::MIN as f64 && value <= i32::MAX as f64 {
+            Some(Au(value as i32))
+        } else {
+            None
+        }
+    }
+
+    /// Converts float pixels, returns None when the value doesn't fit or isn't finite.
+    #[inline]
+    pub fn from_f32_px(px: f32, rounding: Rounding) -> Option<Au> {
+        Au::from_f64_px(px as f64, rounding)
+    }
+
+    /// Gets the raw number of app units.
+    #[inline]
+    pub fn get(&self) -> i32 {
+        self.0
+    }
+
+    /// Converts to whole pixels with the given rounding.
+    #[inline]
+    pub fn to_px(&self, rounding: Rounding) -> i32 {
+        rounding.apply(self.to_f64_px()) as i32
+    }
+
+    #[inline]
+    pub fn to_f32_px(&self) -> f32 {
+        self.0 as f32 / AU_PER_PX as f32
+    }
+
+    #[inline]
+    pub fn to_f64_px(&self) -> f64 {
+        self.0 as f64 / AU_PER_PX as f64
+    }
+}
+
+/// Formats output for debugging purpose.
+impl f::Debug for Au {
+    fn fmt(&self, formatter: &mut f::Formatter) -> f::Result {
+        write!(formatter, "{}au", self.0)
+    }
+}
+
+/// Formats output as pixels.
+impl f::Display for Au {
+    fn fmt(&self, formatter: &mut f::Formatter) -> f::Result {
+        write!(formatter, "{}px", self.to_f64_px())
+    }
+}
+
+/// Adds exactly.
+///
+/// # Panics
+///
+/// When the sum doesn't fit in `i32`.
+impl Add for Au {
+    type Output = Au;
+
+    #[inline]
+    fn add(self, other: Au) -> Au {
+        Au(self.0.checked_add(other.0).expect("app unit addition overflowed"))
+    }
+}
+
+/// Subtracts exactly.
+///
+/// # Panics
+///
+/// When the difference doesn't fit in `i32`.
+impl Sub for Au {
+    type Output = Au;
+
+    #[inline]
+    fn sub(self, other: Au) -> Au {
+        Au(self.0.checked_sub(other.0).expect("app unit subtraction overflowed"))
+    }
+}
+
+/// Multiplies pixel values, the product is truncated to whole app units.
+///
+/// # Panics
+///
+/// When the product doesn't fit in `i32`.
+impl Mul for Au {
+    type Output = Au;
+
+    #[inline]
+    fn mul(self, other: Au) -> Au {
+        let product = self.0 as i64 * other.0 as i64 / AU_PER_PX as i64;
+        Au(i32::try_from(product).expect("app unit multiplication overflowed"))
+    }
+}
+
+/// Divides pixel values, the quotient is truncated to whole app units.
+///
+/// # Panics
+///
+/// When dividing by `Au(0)`, or when the quotient doesn't fit in `i32`.
+impl Div for Au {
+    type Output = Au;
+
+    #[inline]
+    fn div(self, other: Au) -> Au {
+        let quotient = self.0 as i64 * AU_PER_PX as i64 / other.0 as i64;
+        Au(i32::try_from(quotient).expect("app unit division overflowed"))
+    }
+}
+
+/// Remainder in app units, with the sign of `self`.
+///
+/// # Panics
+///
+/// When dividing by `Au(0)`, or for `Au(i32::MIN) % Au(-1)`.
+impl Rem for Au {
+    type Output = Au;
+
+    #[inline]
+    fn rem(self, other: Au) -> Au {
+        Au(self.0.checked_rem(other.0).expect("app unit remainder overflowed"))
+    }
+}
+
+/// Scales by an integer factor, exact.
+///
+/// # Panics
+///
+/// When the product doesn't fit in `i32`.
+impl Mul<i32> for Au {
+    type Output = Au;
+
+    #[inline]
+    fn mul(self, scale: i32) -> Au {
+        Au(self.0.checked_mul(scale).expect("app unit multiplication overflowed"))
+    }
+}
+
+/// Descales by an integer factor, truncated towards zero.
+///
+/// # Panics
+///
+/// When dividing by zero, or when the quotient doesn't fit in `i32`.
+impl Div<i32> for Au {
+    type Output = Au;
+
+    #[inline]
+    fn div(self, scale: i32) -> Au {
+        Au(self.0.checked_div(scale).expect("app unit division overflowed"))
+    }
+}
+
+/// Negates exactly.
+///
+/// # Panics
+///
+/// For `Au(i32::MIN)`, which has no positive counterpart.
+impl Neg for Au {
+    type Output = Au;
+
+    #[inline]
+    fn neg(self) -> Au {
+        Au(self.0.checked_neg().expect("app unit negation overflowed"))
+    }
+}
+
+impl num_lib::Zero for Au {
+    #[inline]
+    fn zero() -> Au {
+        Au(0)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// One pixel, the identity for `Mul`.
+impl num_lib::One for Au {
+    #[inline]
+    fn one() -> Au {
+        Au(AU_PER_PX)
+    }
+}
+
+/// Integer conversions give whole pixels truncated towards zero, float ones are exact.
+impl ToPrimitive for Au {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        Some((self.0 / AU_PER_PX) as i64)
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        (self.0 / AU_PER_PX).to_u64()
+    }
+
+    #[inline]
+    fn to_f32(&self) -> Option<f32> {
+        Some(self.to_f32_px())
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.to_f64_px())
+    }
+}
+
+/// Casts a pixel value, fractions are rounded to the nearest app unit.
+impl NumCast for Au {
+    #[inline]
+    fn from<T: ToPrimitive>(n: T) -> Option<Au> {
+        n.to_f64().and_then(|px| Au::from_f64_px(px, Rounding::Nearest))
+    }
+}
+
+/// Rounds to whole pixels.
+impl Round for Au {
+    #[inline]
+    fn round(&self) -> Au {
+        Au::from_px(self.to_px(Rounding::Nearest))
+    }
+
+    #[inline]
+    fn floor(&self) -> Au {
+        Au::from_px(self.0.div_euclid(AU_PER_PX))
+    }
+
+    #[inline]
+    fn ceil(&self) -> Au {
+        let floor = self.0.div_euclid(AU_PER_PX);
+        Au::from_px(if self.0.rem_euclid(AU_PER_PX) == 0 { floor } else { floor + 1 })
+    }
+
+    #[inline]
+    fn trunc(&self) -> Au {
+        Au::from_px(self.0 / AU_PER_PX)
+    }
+}
@@ -1,3 +1,5 @@
+use num_lib::Float;
+
 /// Trait for testing approximate equality
 pub trait ApproxEq<Eps> {
     fn approx_epsilon() -> Eps;
@@ -15,7 +17,7 @@ impl ApproxEq<f32> for f32 {
 
     #[inline]
     fn approx_eq_eps(&self, other: &f32, approx_epsilon: &f32) -> bool {
-        (*self - *other).abs() < *approx_epsilon
+        Float::abs(*self - *other) < *approx_epsilon
     }
 
     #[inline]
@@ -32,7 +34,7 @@ impl ApproxEq<f64> for f64 {
 
     #[inline]
     fn approx_eq_eps(&self, other: &f64, approx_epsilon: &f64) -> bool {
-        (*self - *other).abs() < *approx_epsilon
+        Float::abs(*self - *other) < *approx_epsilon
     }
 
     #[inline]
@@ -0,0 +1,94 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use point::Point3D;
+
+use num_lib::Float;
+use core::fmt::{Debug, Display, Formatter, Result};
+
+/// Axis-aligned box stored as its minimal and maximal corners.
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Box3D<T> {
+    pub min: Point3D<T>,
+    pub max: Point3D<T>,
+}
+
+/// Implements type-safe formatting for debug operations.
+impl<T: Debug> Debug for Box3D<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        write!(formatter, "Box3D({:?} to {:?})", self.min, self.max)
+    }
+}
+
+/// Implements console-friendly format printing.
+impl<T: Display> Display for Box3D<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        write!(formatter, "Box3D({} to {})", self.min, self.max)
+    }
+}
+
+/// Creates an instance of Box3D.
+impl<T> Box3D<T> {
+    pub fn new(min: Point3D<T>, max: Point3D<T>) -> Box3D<T> {
+        Box3D {
+            min: min,
+            max: max,
+        }
+    }
+}
+
+impl<T: Float> Box3D<T> {
+    /// Creates the smallest box containing all the points, None when there are none.
+    pub fn from_points<I: IntoIterator<Item = Point3D<T>>>(points: I) -> Option<Box3D<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Box3D::new(first, first), |bounds, point| {
+            Box3D::new(bounds.min.min(point), bounds.max.max(point))
+        }))
+    }
+
+    /// Whether the box encloses no volume, i.e. it is inverted or flat along some axis.
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x < self.max.x && self.min.y < self.max.y && self.min.z < self.max.z)
+    }
+
+    /// Smallest box containing both boxes.
+    pub fn union(&self, other: &Box3D<T>) -> Box3D<T> {
+        Box3D::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Whether the boxes overlap or touch.
+    pub fn intersects(&self, other: &Box3D<T>) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    /// Whether the point lies inside the box or on its boundary.
+    pub fn contains(&self, point: &Point3D<T>) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+            && self.min.z <= point.z
+            && point.z <= self.max.z
+    }
+
+    pub fn center(&self) -> Point3D<T> {
+        self.min.lerp(&self.max, T::from(0.5).unwrap())
+    }
+
+    /// Total area of the six faces, zero for inverted boxes.
+    pub fn surface_area(&self) -> T {
+        let size = (self.max - self.min).max(Point3D::zero());
+        let two = T::one() + T::one();
+
+        two * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+}
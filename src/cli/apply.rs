@@ -0,0 +1,276 @@
+//! `matrix apply`, streams a file of points through a transform.
+
+use cli::{self, Error};
+use matrix::matrix4d;
+use matrix::transform::{Rows, Transform};
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::str;
+
+pub const USAGE: &str = "\
+Usage: matrix apply --transform <TRANSFORM> [OPTIONS]
+
+Applies the transform list, see 'matrix compose --help', to every row of a
+point file. Rows hold comma-separated or whitespace-separated columns, the
+first two or three of them are the coordinates. They are replaced in place,
+all other columns pass through untouched. Comma-separated fields may be put in
+double quotes, which keeps the commas inside them. Blank lines and lines
+starting with '#' are copied as they are. Points are mapped in double precision
+and the file is streamed, so it may be of any size.
+
+Options:
+    --transform <TRANSFORM>  Transform list to apply
+    --input <FILE>           File to read, stdin when missing or '-'
+    --output <FILE>          File to write, stdout when missing or '-'
+    --3d                     Read x, y and z from the first three columns
+    --header                 Copy the first line as it is
+    --invert                 Apply the inverse of the transform
+    --skip-invalid           Report malformed lines and go on without them,
+                             instead of stopping at the first one
+    -h, --help               Print this help
+";
+
+/// Maps the coordinates in place through the double precision matrix, z being zero for 2D
+/// points. Returns false when the point ends up behind the viewer, like
+/// `Matrix4D::transform_point3d`.
+fn map(rows: &Rows, coordinates: &mut [f64]) -> bool {
+    let (x, y, z) = (coordinates[0], coordinates[1], coordinates.get(2).cloned().unwrap_or(0.0));
+    let mut mapped = [0.0; 4];
+
+    for (column, value) in mapped.iter_mut().enumerate() {
+        *value = x * rows[0][column] + y * rows[1][column] + z * rows[2][column] + rows[3][column];
+    }
+
+    if mapped[3] <= f64::EPSILON {
+        return false;
+    }
+    for (coordinate, value) in coordinates.iter_mut().zip(&mapped) {
+        *coordinate = value / mapped[3];
+    }
+    true
+}
+
+pub fn run(arguments: &[String]) -> Result<(), Error> {
+    let (mut transform, mut input, mut output) = (None, None, None);
+    let (mut is_3d, mut header, mut invert, mut skip_invalid) = (false, false, false, false);
+
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        let mut option_value = || {
+            arguments.next().ok_or_else(|| Error::Usage(format!("option '{}' needs a value", argument)))
+        };
+
+        match argument.as_str() {
+            "--transform" => {
+                let text = option_value()?;
+                transform = Some(Transform::parse(text).map_err(|error| Error::Failure(error.to_string()))?);
+            }
+            "--input" => input = Some(option_value()?.clone()),
+            "--output" => output = Some(option_value()?.clone()),
+            "--3d" => is_3d = true,
+            "--header" => header = true,
+            "--invert" => invert = true,
+            "--skip-invalid" => skip_invalid = true,
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return Ok(());
+            }
+            other => return Err(Error::Usage(format!("unexpected argument '{}'", other))),
+        }
+    }
+
+    let transform = transform.ok_or_else(|| Error::Usage("missing the transform, see --transform".to_owned()))?;
+
+    let mut rows = transform.to_rows();
+    if rows.iter().flat_map(|row| row.iter()).any(|value| !value.is_finite()) {
+        return Err(Error::Failure("the transform overflows the range of numbers".to_owned()));
+    }
+    if invert {
+        rows = matrix4d::invert_rows(rows).ok_or_else(|| Error::Failure("the transform is not invertible".to_owned()))?;
+    }
+
+    let stdin = io::stdin();
+    let reader: Box<dyn BufRead> = match input.as_deref() {
+        None | Some("-") => Box::new(stdin.lock()),
+        Some(path) => Box::new(BufReader::new(open(path, File::open(path))?)),
+    };
+
+    let stdout = io::stdout();
+    let writer: Box<dyn Write> = match output.as_deref() {
+        None | Some("-") => Box::new(stdout.lock()),
+        Some(path) => Box::new(open(path, File::create(path))?),
+    };
+
+    let options = Options {
+        dimensions: if is_3d { 3 } else { 2 },
+        header: header,
+        skip_invalid: skip_invalid,
+    };
+
+    let skipped = stream(reader, BufWriter::new(writer), &rows, &options)?;
+    if skipped > 0 {
+        eprintln!("matrix: skipped {} malformed line(s)", skipped);
+    }
+
+    Ok(())
+}
+
+struct Options {
+    dimensions: usize,
+    header: bool,
+    skip_invalid: bool,
+}
+
+fn open(path: &str, result: io::Result<File>) -> Result<File, Error> {
+    result.map_err(|error| Error::Failure(format!("can't open '{}': {}", path, error)))
+}
+
+/// Copies the input to the output line by line, transforming the coordinates on the way.
+/// Returns the number of lines skipped as malformed.
+fn stream<R: BufRead, W: Write>(mut reader: R, mut writer: W, rows: &Rows, options: &Options) -> Result<usize, Error> {
+    let (mut line, mut transformed) = (Vec::new(), String::new());
+    let mut coordinates = [0.0; 3];
+    let (mut number, mut skipped) = (0, 0);
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        number += 1;
+
+        let mut content = &line[..];
+        while let Some((b'\n', rest)) | Some((b'\r', rest)) = content.split_last() {
+            content = rest;
+        }
+        let trimmed = content.trim_ascii_start();
+
+        // Copied as bytes, so that they go through even when they aren't UTF-8.
+        if (number == 1 && options.header) || trimmed.is_empty() || trimmed.starts_with(b"#") {
+            writer.write_all(content)?;
+            writer.write_all(b"\n")?;
+            continue;
+        }
+
+        let coordinates = &mut coordinates[..options.dimensions];
+        let result = str::from_utf8(content)
+            .map_err(|_| "invalid UTF-8".to_owned())
+            .and_then(|content| transform_line(content, coordinates, rows, &mut transformed));
+
+        match result {
+            Ok(()) => writeln!(writer, "{}", transformed)?,
+            Err(message) if options.skip_invalid => {
+                eprintln!("matrix: line {}: {}", number, message);
+                skipped += 1;
+            }
+            Err(message) => return Err(Error::Failure(format!("line {}: {}", number, message))),
+        }
+    }
+
+    writer.flush()?;
+    Ok(skipped)
+}
+
+/// Rewrites the line into `output` with its leading coordinates transformed, keeping every
+/// other byte, separators and quotes included, as it was.
+fn transform_line(line: &str, coordinates: &mut [f64], rows: &Rows, output: &mut String) -> Result<(), String> {
+    let spans = field_spans(line, coordinates.len())?;
+    if spans.len() < coordinates.len() {
+        return Err(format!("expected at least {} columns, found {}", coordinates.len(), spans.len()));
+    }
+
+    for (coordinate, &(start, end)) in coordinates.iter_mut().zip(&spans) {
+        let text = &line[start..end];
+
+        *coordinate = match text.parse::<f64>() {
+            Ok(value) if value.is_finite() => value,
+            _ => return Err(format!("invalid number '{}'", text)),
+        };
+    }
+
+    if !map(rows, coordinates) {
+        return Err("the point maps behind the viewer".to_owned());
+    }
+    if coordinates.iter().any(|coordinate| !coordinate.is_finite()) {
+        return Err("the point maps beyond the range of numbers".to_owned());
+    }
+
+    output.clear();
+    let mut position = 0;
+
+    for (coordinate, &(start, end)) in coordinates.iter().zip(&spans) {
+        output.push_str(&line[position..start]);
+        output.push_str(&cli::format_number(*coordinate));
+        position = end;
+    }
+    output.push_str(&line[position..]);
+
+    Ok(())
+}
+
+/// Byte ranges of the first `count` fields, which are separated by commas when the line
+/// has any outside quotes and by whitespace otherwise. Spaces around comma-separated fields
+/// are excluded, and so are the quotes of quoted ones.
+fn field_spans(line: &str, count: usize) -> Result<Vec<(usize, usize)>, String> {
+    let mut spans = Vec::with_capacity(count);
+
+    if let Some(fields) = comma_fields(line)? {
+        for (start, end) in fields.into_iter().take(count) {
+            let field = &line[start..end];
+            let (start, end) = (start + field.len() - field.trim_start().len(), start + field.trim_end().len());
+
+            match line[start..end].strip_prefix('"').and_then(|field| field.strip_suffix('"')) {
+                Some(_) => spans.push((start + 1, end - 1)),
+                None => spans.push((start, end)),
+            }
+        }
+        return Ok(spans);
+    }
+
+    let mut field_start = None;
+    for (index, c) in line.char_indices().chain(Some((line.len(), ' '))) {
+        match (c.is_whitespace(), field_start) {
+            (false, None) => field_start = Some(index),
+            (true, Some(start)) => {
+                spans.push((start, index));
+                field_start = None;
+
+                if spans.len() == count {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(spans)
+}
+
+/// Splits at commas outside double quotes, where `""` stands for a quote. None when the line
+/// has no such comma, an error when a quote is left open.
+fn comma_fields(line: &str) -> Result<Option<Vec<(usize, usize)>>, String> {
+    let (mut fields, mut start, mut quoted) = (Vec::new(), 0, false);
+
+    // An escaped quote toggles twice, which leaves the state as it was.
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push((start, index));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if quoted {
+        return Err("unterminated quote".to_owned());
+    }
+    if fields.is_empty() {
+        return Ok(None);
+    }
+
+    fields.push((start, line.len()));
+    Ok(Some(fields))
+}
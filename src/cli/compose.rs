@@ -0,0 +1,108 @@
+//! `matrix compose`, builds a matrix from a CSS-like transform list and applies it to points.
+
+use cli::{self, Error, InputPoint};
+use matrix::matrix4d::Matrix4D;
+use matrix::transform::Transform;
+
+use std::io::{self, BufRead, BufWriter, Write};
+
+pub const USAGE: &str = "\
+Usage: matrix compose <TRANSFORM> [OPTIONS] [POINT]...
+
+Composes the transform list, e.g. \"translate(10, 20) rotate(30deg) scale(2)\",
+and prints the resulting matrix. The functions apply from right to left as in CSS.
+
+When points are given as 'x,y' or 'x,y,z' they are transformed and printed
+instead, one per line. A '-' reads further points from stdin, one per line.
+
+Options:
+    --invert    Use the inverse of the composed transform
+    --3d        Print the 4x4 matrix even when the transform is 2D
+    --json      Print JSON instead of plain text
+    -h, --help  Print this help
+";
+
+pub fn run(arguments: &[String]) -> Result<(), Error> {
+    let mut transform = None;
+    let mut points = Vec::new();
+    let (mut invert, mut force_3d, mut json, mut stdin) = (false, false, false, false);
+
+    for argument in arguments {
+        match argument.as_str() {
+            "--invert" => invert = true,
+            "--3d" => force_3d = true,
+            "--json" => json = true,
+            "-" => stdin = true,
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return Ok(());
+            }
+            option if option.starts_with("--") => return Err(Error::Usage(format!("unknown option '{}'", option))),
+            text if transform.is_none() => {
+                transform = Some(Transform::parse(text).map_err(|error| Error::Failure(error.to_string()))?)
+            }
+            text => points.push(cli::parse_point(text).map_err(Error::Usage)?),
+        }
+    }
+
+    let transform = transform.ok_or_else(|| Error::Usage("missing the transform to compose".to_owned()))?;
+    let is_2d = transform.is_2d() && !force_3d;
+
+    let mut matrix = transform.to_matrix();
+    if invert {
+        matrix = matrix.inverse().ok_or_else(|| Error::Failure("the transform is not invertible".to_owned()))?;
+    }
+    if matrix.rows().flat_map(|row| row.to_vec()).any(|value| !value.is_finite()) {
+        return Err(Error::Failure("the transform overflows the range of numbers".to_owned()));
+    }
+
+    let output = io::stdout();
+    let mut output = BufWriter::new(output.lock());
+
+    if points.is_empty() && !stdin {
+        writeln!(output, "{}", cli::format_matrix(&matrix, is_2d, json))?;
+        return Ok(output.flush()?);
+    }
+
+    if stdin {
+        let input = io::stdin();
+        for (index, line) in input.lock().lines().enumerate() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                points.push(cli::parse_point(&line).map_err(|error| Error::Failure(format!("line {}: {}", index + 1, error)))?);
+            }
+        }
+    }
+
+    let mut results = Vec::with_capacity(points.len());
+    for input in &points {
+        results.push(cli::format_coordinates(&apply(&matrix, input)?, json));
+    }
+
+    if json {
+        writeln!(output, "[{}]", results.join(", "))?;
+    } else {
+        for result in &results {
+            writeln!(output, "{}", result)?;
+        }
+    }
+
+    Ok(output.flush()?)
+}
+
+/// Transforms the point and keeps as many coordinates as it came with.
+fn apply(matrix: &Matrix4D, input: &InputPoint) -> Result<Vec<f32>, Error> {
+    let coordinates = [input.point.x, input.point.y, input.point.z];
+    let dimensions = if input.is_3d { 3 } else { 2 };
+    let failure = |reason: &str| {
+        Error::Failure(format!("point '{}' maps {}", cli::format_coordinates(&coordinates[..dimensions], false), reason))
+    };
+
+    let point = matrix.transform_point3d(&input.point).ok_or_else(|| failure("behind the viewer"))?;
+    let mapped = [point.x, point.y, point.z];
+    if mapped.iter().any(|coordinate| !coordinate.is_finite()) {
+        return Err(failure("beyond the range of numbers"));
+    }
+
+    Ok(mapped[..dimensions].to_vec())
+}
@@ -0,0 +1,145 @@
+//! `matrix convert`, converts lengths between physical, CSS and device units.
+
+use cli::{self, Error};
+use matrix::conversion::{AnyUnit, UnitGraph};
+use matrix::length::Length;
+use matrix::num::Rounding;
+use matrix::units::{self, CssPx, PhysicalUnit};
+
+use std::io::{self, BufRead, BufWriter, Write};
+
+pub const USAGE: &str = "\
+Usage: matrix convert <VALUE>... --to <UNIT> [OPTIONS]
+
+Converts lengths such as '12pt' or '210mm' into another unit and prints one
+result per line. A '-' reads further values from stdin, separated by spaces
+or new lines.
+
+Units:
+    in, px, pt, pc, mm, cm  Physical and CSS units, 96px to the inch
+    au                      App units, 60 to the CSS pixel
+    dpx                     Device pixels, see --dpi and --scale
+
+Options:
+    --to <UNIT>         Unit to convert into
+    --from <UNIT>       Unit of the values given without one
+    --dpi <N>           Device resolution in dots per inch
+    --scale <F>         Device pixels per CSS pixel, 1 by default
+    --round <MODE>      Round the results: nearest, floor, ceil or trunc
+    --json              Print JSON instead of plain text
+    -h, --help          Print this help
+";
+
+pub fn run(arguments: &[String]) -> Result<(), Error> {
+    let mut values = Vec::new();
+    let (mut to, mut from, mut dpi, mut scale, mut rounding) = (None, None, None, None, None);
+    let (mut json, mut stdin) = (false, false);
+
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        let mut option_value = || {
+            arguments.next().ok_or_else(|| Error::Usage(format!("option '{}' needs a value", argument)))
+        };
+
+        match argument.as_str() {
+            "--to" => to = Some(option_value()?.clone()),
+            "--from" => from = Some(option_value()?.clone()),
+            "--dpi" => dpi = Some(parse_positive(argument, option_value()?)?),
+            "--scale" => scale = Some(parse_positive(argument, option_value()?)?),
+            "--round" => rounding = Some(parse_rounding(option_value()?)?),
+            "--json" => json = true,
+            "-" => stdin = true,
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return Ok(());
+            }
+            option if option.starts_with("--") => return Err(Error::Usage(format!("unknown option '{}'", option))),
+            value => values.push(value.to_owned()),
+        }
+    }
+
+    let to = to.ok_or_else(|| Error::Usage("missing the unit to convert into, see --to".to_owned()))?;
+    let device_dpi = match (dpi, scale) {
+        (Some(_), Some(_)) => return Err(Error::Usage("--dpi and --scale can't be combined".to_owned())),
+        (Some(dpi), None) => dpi,
+        (None, scale) => scale.unwrap_or(1.0) * CssPx::PER_INCH,
+    };
+
+    let graph = units::unit_graph(device_dpi).map_err(|error| Error::Failure(error.to_string()))?;
+    if !graph.contains(&to) {
+        return Err(Error::Usage(format!("unknown unit '{}'", to)));
+    }
+
+    if values.is_empty() && !stdin {
+        return Err(Error::Usage("missing the values to convert".to_owned()));
+    }
+
+    if stdin {
+        let input = io::stdin();
+        for line in input.lock().lines() {
+            values.extend(line?.split_whitespace().map(str::to_owned));
+        }
+    }
+
+    let mut results = Vec::with_capacity(values.len());
+    for value in &values {
+        let result = convert(&graph, value, from.as_deref(), &to)?;
+        results.push(cli::format_number(rounding.map_or(result, |rounding: Rounding| rounding.apply(result))));
+    }
+
+    let output = io::stdout();
+    let mut output = BufWriter::new(output.lock());
+
+    if json {
+        writeln!(output, "[{}]", results.join(", "))?;
+    } else {
+        for result in &results {
+            writeln!(output, "{}", result)?;
+        }
+    }
+
+    Ok(output.flush()?)
+}
+
+/// Converts a value like `12pt`, taking `default_unit` when it comes without a suffix.
+fn convert(graph: &UnitGraph<f64>, text: &str, default_unit: Option<&str>, to: &str) -> Result<f64, Error> {
+    let split = text.trim_end_matches(|c: char| c.is_ascii_alphabetic()).len();
+    let (number, unit) = text.split_at(split);
+
+    let unit = match (unit, default_unit) {
+        ("", Some(unit)) => unit,
+        ("", None) => return Err(Error::Failure(format!("'{}' has no unit, add one or use --from", text))),
+        (unit, _) => unit,
+    };
+
+    let length: Length<AnyUnit, f64> = match number.parse::<f64>() {
+        Ok(number) if number.is_finite() => Length::new(number),
+        _ => return Err(Error::Failure(format!("invalid number in '{}'", text))),
+    };
+
+    let converted: Length<AnyUnit, f64> = graph
+        .convert_length(length, unit, to)
+        .map_err(|error| Error::Failure(error.to_string()))?;
+
+    if !converted.get().is_finite() {
+        return Err(Error::Failure(format!("'{}' converts beyond the range of numbers", text)));
+    }
+    Ok(converted.get())
+}
+
+fn parse_positive(option: &str, text: &str) -> Result<f64, Error> {
+    match text.parse::<f64>() {
+        Ok(value) if value > 0.0 && value.is_finite() => Ok(value),
+        _ => Err(Error::Usage(format!("option '{}' needs a positive number, got '{}'", option, text))),
+    }
+}
+
+fn parse_rounding(text: &str) -> Result<Rounding, Error> {
+    match text {
+        "nearest" => Ok(Rounding::Nearest),
+        "floor" => Ok(Rounding::Floor),
+        "ceil" => Ok(Rounding::Ceil),
+        "trunc" => Ok(Rounding::Trunc),
+        _ => Err(Error::Usage(format!("unknown rounding mode '{}'", text))),
+    }
+}
@@ -0,0 +1,120 @@
+//! Helpers shared by the commands of the `matrix` binary.
+
+pub mod apply;
+pub mod compose;
+pub mod convert;
+
+use matrix::matrix4d::Matrix4D;
+use matrix::point::Point3D;
+
+use std::fmt;
+
+/// Failure of a command, the exit code tells usage mistakes apart from bad input.
+#[derive(Debug)]
+pub enum Error {
+    Usage(String),
+    Failure(String),
+}
+
+impl Error {
+    pub fn code(&self) -> i32 {
+        match *self {
+            Error::Usage(_) => 2,
+            Error::Failure(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Usage(ref message) => write!(formatter, "{}\nTry 'matrix --help' for more information.", message),
+            Error::Failure(ref message) => write!(formatter, "{}", message),
+        }
+    }
+}
+
+impl From<::std::io::Error> for Error {
+    fn from(error: ::std::io::Error) -> Error {
+        Error::Failure(error.to_string())
+    }
+}
+
+/// Point read from the command line or input, remembering whether it came with a z coordinate.
+#[derive(Clone, Copy, Debug)]
+pub struct InputPoint {
+    pub point: Point3D<f32>,
+    pub is_3d: bool,
+}
+
+/// Parses `x,y` or `x,y,z`, whitespace works as a separator as well.
+pub fn parse_point(text: &str) -> Result<InputPoint, String> {
+    let values: Vec<&str> = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .collect();
+
+    let mut coordinates = [0.0; 3];
+    if values.len() != 2 && values.len() != 3 {
+        return Err(format!("expected 'x,y' or 'x,y,z', got '{}'", text.trim()));
+    }
+
+    for (coordinate, value) in coordinates.iter_mut().zip(&values) {
+        *coordinate = match value.parse::<f32>() {
+            Ok(number) if number.is_finite() => number,
+            _ => return Err(format!("invalid number '{}'", value)),
+        };
+    }
+
+    Ok(InputPoint {
+        point: Point3D::new(coordinates[0], coordinates[1], coordinates[2]),
+        is_3d: values.len() == 3,
+    })
+}
+
+/// Prints a number rounded to six decimals, which hides the float noise of composed transforms.
+///
+/// Commands reject non-finite results before printing, neither the plain nor the JSON output has a
+/// spelling for them.
+pub fn format_number(value: f64) -> String {
+    let value = (value * 1.0e6).round() / 1.0e6;
+
+    // Rounding may leave a negative zero behind.
+    format!("{}", if value == 0.0 { 0.0 } else { value })
+}
+
+/// Formats point coordinates, space-separated in plain output or as a JSON array.
+pub fn format_coordinates(coordinates: &[f32], json: bool) -> String {
+    let values: Vec<String> = coordinates.iter().map(|&value| format_number(f64::from(value))).collect();
+
+    if json {
+        format!("[{}]", values.join(", "))
+    } else {
+        values.join(" ")
+    }
+}
+
+/// Formats the matrix as a grid of rows, or as JSON holding the rows, keeping only the
+/// 3x2 affine part when `is_2d`.
+pub fn format_matrix(matrix: &Matrix4D, is_2d: bool, json: bool) -> String {
+    let rows: Vec<Vec<f32>> = if is_2d {
+        matrix.to_2d().rows().map(|row| row.to_vec()).collect()
+    } else {
+        matrix.rows().map(|row| row.to_vec()).collect()
+    };
+
+    if json {
+        let rows: Vec<String> = rows.iter().map(|row| format_coordinates(row, true)).collect();
+        return format!("{{\"dimensions\": {}, \"rows\": [{}]}}", if is_2d { 2 } else { 3 }, rows.join(", "));
+    }
+
+    let rows: Vec<Vec<String>> = rows.iter().map(|row| row.iter().map(|&value| format_number(f64::from(value))).collect()).collect();
+    let width = rows.iter().flat_map(|row| row.iter().map(String::len)).max().unwrap_or(0);
+
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|row| row.iter().map(|value| format!("{:>width$}", value, width = width)).collect::<Vec<_>>().join(" "))
+        .collect();
+
+    lines.join("\n")
+}
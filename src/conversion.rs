@@ -0,0 +1,209 @@
+use length::Length;
+use scale_factor::ScaleFactor;
+
+use num_lib::Float;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+/// Unit known only by its name at runtime.
+#[derive(Clone, Copy, Debug)]
+pub enum AnyUnit {}
+
+/// Scale factor between two runtime units.
+pub type AnyScaleFactor<T> = ScaleFactor<AnyUnit, AnyUnit, T>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConversionError {
+    /// The unit was never registered.
+    UnknownUnit(String),
+    /// Both units are known, but no chain of factors connects them.
+    NoPath(String, String),
+    /// The new factor disagrees with the one already implied by a path between the units.
+    Inconsistent(String, String),
+    /// Factors must be finite and non-zero to be invertible.
+    InvalidFactor(String, String),
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConversionError::UnknownUnit(ref unit) => write!(formatter, "unknown unit '{}'", unit),
+            ConversionError::NoPath(ref from, ref to) => write!(formatter, "no conversion from '{}' to '{}'", from, to),
+            ConversionError::Inconsistent(ref from, ref to) => {
+                write!(formatter, "factor from '{}' to '{}' contradicts existing conversions", from, to)
+            }
+            ConversionError::InvalidFactor(ref from, ref to) => {
+                write!(formatter, "factor from '{}' to '{}' is zero or not finite", from, to)
+            }
+        }
+    }
+}
+
+impl Error for ConversionError {}
+
+/// Registry of scale factors between named units.
+///
+/// Units form the nodes of a graph and every registered factor adds an edge both
+/// ways, the reverse one being its inverse. A conversion between two units composes
+/// the factors along the shortest path connecting them.
+///
+/// Adding a factor between units that are already connected checks it against the
+/// existing path, so the graph never holds a cycle whose product differs from one.
+#[derive(Clone, Debug)]
+pub struct UnitGraph<T> {
+    indices: HashMap<String, usize>,
+    names: Vec<String>,
+    edges: Vec<Vec<(usize, AnyScaleFactor<T>)>>,
+    tolerance: T,
+}
+
+impl<T: Float> Default for UnitGraph<T> {
+    fn default() -> UnitGraph<T> {
+        UnitGraph::new()
+    }
+}
+
+impl<T: Float> UnitGraph<T> {
+    /// Creates an empty graph, factors are compared with a relative tolerance of 1e-6.
+    pub fn new() -> UnitGraph<T> {
+        UnitGraph::with_tolerance(T::from(1.0e-6).unwrap())
+    }
+
+    /// Creates an empty graph with the relative tolerance used to detect inconsistent cycles.
+    pub fn with_tolerance(tolerance: T) -> UnitGraph<T> {
+        UnitGraph {
+            indices: HashMap::new(),
+            names: Vec::new(),
+            edges: Vec::new(),
+            tolerance: tolerance,
+        }
+    }
+
+    /// Registers a unit without any conversion, does nothing when it exists.
+    pub fn add_unit(&mut self, name: &str) {
+        self.index_or_insert(name);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.indices.contains_key(name)
+    }
+
+    /// Iterates over the registered unit names in insertion order.
+    pub fn units(&self) -> ::std::slice::Iter<'_, String> {
+        self.names.iter()
+    }
+
+    /// Registers the factor converting `from` into `to`, e.g. `("in", "mm", 25.4)`.
+    ///
+    /// Fails when the factor isn't invertible, or when the units are already
+    /// connected through a path with a different product. A unit is always connected
+    /// to itself, so `from == to` only accepts a factor of one.
+    pub fn add<S, D>(&mut self, from: &str, to: &str, factor: ScaleFactor<S, D, T>) -> Result<(), ConversionError> {
+        let value = factor.get();
+
+        if value.is_zero() || !value.is_finite() {
+            return Err(ConversionError::InvalidFactor(from.to_owned(), to.to_owned()));
+        }
+
+        // A unit converts into itself by one, whether or not it's registered yet.
+        let existing = if from == to {
+            Ok(ScaleFactor::new(T::one()))
+        } else {
+            self.factor(from, to)
+        };
+
+        if let Ok(existing) = existing {
+            let difference = (existing.get() - value).abs();
+
+            if difference > self.tolerance * value.abs() {
+                return Err(ConversionError::Inconsistent(from.to_owned(), to.to_owned()));
+            }
+            self.index_or_insert(from);
+            return Ok(());
+        }
+
+        let factor: AnyScaleFactor<T> = ScaleFactor::new(value);
+        let (a, b) = (self.index_or_insert(from), self.index_or_insert(to));
+
+        self.edges[a].push((b, factor));
+        self.edges[b].push((a, factor.inv()));
+
+        Ok(())
+    }
+
+    /// Drops the direct factor between two units, returns whether there was one.
+    pub fn remove(&mut self, from: &str, to: &str) -> bool {
+        match (self.indices.get(from), self.indices.get(to)) {
+            (Some(&a), Some(&b)) => {
+                let count = self.edges[a].len();
+
+                self.edges[a].retain(|&(node, _)| node != b);
+                self.edges[b].retain(|&(node, _)| node != a);
+
+                count != self.edges[a].len()
+            }
+            _ => false,
+        }
+    }
+
+    /// Finds the factor converting `from` into `to` by composing factors along a path.
+    pub fn factor(&self, from: &str, to: &str) -> Result<AnyScaleFactor<T>, ConversionError> {
+        let source = self.index(from)?;
+        let target = self.index(to)?;
+
+        let mut factors: Vec<Option<AnyScaleFactor<T>>> = vec![None; self.names.len()];
+        let mut queue = VecDeque::new();
+
+        factors[source] = Some(ScaleFactor::new(T::one()));
+        queue.push_back(source);
+
+        while let Some(node) = queue.pop_front() {
+            if node == target {
+                break;
+            }
+
+            let current = factors[node].unwrap();
+            for &(next, edge) in &self.edges[node] {
+                if factors[next].is_none() {
+                    factors[next] = Some(current * edge);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        factors[target].ok_or_else(|| ConversionError::NoPath(from.to_owned(), to.to_owned()))
+    }
+
+    /// Same as `factor`, but hands the result out with compile-time units attached.
+    pub fn typed_factor<S, D>(&self, from: &str, to: &str) -> Result<ScaleFactor<S, D, T>, ConversionError> {
+        self.factor(from, to).map(|factor| ScaleFactor::new(factor.get()))
+    }
+
+    /// Converts a value given in `from` units into `to` units.
+    pub fn convert(&self, value: T, from: &str, to: &str) -> Result<T, ConversionError> {
+        self.factor(from, to).map(|factor| value * factor.get())
+    }
+
+    /// Converts a typed length, the unit names describe its source and target units.
+    pub fn convert_length<S, D>(&self, length: Length<S, T>, from: &str, to: &str) -> Result<Length<D, T>, ConversionError> {
+        self.typed_factor(from, to).map(|factor| length * factor)
+    }
+
+    fn index(&self, name: &str) -> Result<usize, ConversionError> {
+        self.indices.get(name).cloned().ok_or_else(|| ConversionError::UnknownUnit(name.to_owned()))
+    }
+
+    fn index_or_insert(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+
+        let index = self.names.len();
+        self.indices.insert(name.to_owned(), index);
+        self.names.push(name.to_owned());
+        self.edges.push(Vec::new());
+
+        index
+    }
+}
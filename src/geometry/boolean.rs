@@ -0,0 +1,401 @@
+use geometry::predicates::orient2d;
+use geometry::signed_area;
+use point::Point2D;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// How a boolean operation combines the subject and the clip polygon.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    Union,
+    Intersection,
+    /// The subject less the clip polygon.
+    Difference,
+    /// Either polygon but not both.
+    Xor,
+}
+
+impl Operation {
+    fn apply(self, subject: bool, clip: bool) -> bool {
+        match self {
+            Operation::Union => subject || clip,
+            Operation::Intersection => subject && clip,
+            Operation::Difference => subject && !clip,
+            Operation::Xor => subject != clip,
+        }
+    }
+}
+
+/// Which points the contours of a polygon cover, going by how often they wind around them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FillRule {
+    /// Points wound around an odd number of times.
+    EvenOdd,
+    /// Points wound around at all, whichever way.
+    NonZero,
+}
+
+impl FillRule {
+    fn covers(self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// Piece of the input edges between two splitting points, no other edge crossing it.
+#[derive(Clone, Debug)]
+struct Piece {
+    from: Point2D<f64>,
+    to: Point2D<f64>,
+    /// How many more times the contours of each polygon run from `from` to `to` than back.
+    windings: [i32; 2],
+}
+
+/// Combines two polygons given as lists of contours, each filled by the fill rule.
+///
+/// Returns the contours of the result, outlines counter-clockwise and holes clockwise, which
+/// neither cross nor overlap each other, only touch at vertices. Collinear vertices are
+/// left out.
+///
+/// The edges of both polygons are split wherever they cross or touch another edge, and
+/// edges running along each other come out as identical pieces, merged into one. Winding
+/// numbers on both sides of every piece then tell whether it bounds the result. So shared
+/// edges, coincident vertices and self-intersecting contours are all handled alike. Edge
+/// ends lying on other edges are found exactly, crossing points are rounded to floats.
+/// Takes O(n²) for n edges, except for intersecting two convex contours, which goes through
+/// `clip_convex`.
+pub fn boolean<S, C>(subject: &[S], clip: &[C], operation: Operation, fill_rule: FillRule) -> Vec<Vec<Point2D<f64>>>
+where
+    S: AsRef<[Point2D<f64>]>,
+    C: AsRef<[Point2D<f64>]>,
+{
+    if let (Operation::Intersection, [subject], [clip]) = (operation, subject, clip) {
+        let (subject, clip) = (subject.as_ref(), clip.as_ref());
+        if is_convex(subject) && is_convex(clip) {
+            let mut result = simplify(&clip_convex(subject, clip));
+            if signed_area(&result) < 0.0 {
+                result.reverse();
+            }
+            return if result.len() >= 3 { vec![result] } else { Vec::new() };
+        }
+    }
+
+    let mut edges = Vec::new();
+    let subject = subject.iter().map(|contour| (contour.as_ref(), 0));
+    for (contour, polygon) in subject.chain(clip.iter().map(|contour| (contour.as_ref(), 1))) {
+        for (i, &from) in contour.iter().enumerate() {
+            let to = contour[(i + 1) % contour.len()];
+            if from != to {
+                edges.push((from, to, polygon));
+            }
+        }
+    }
+
+    let pieces = split(&edges);
+    let mut boundary = Vec::new();
+    for (index, piece) in pieces.iter().enumerate() {
+        if piece.windings == [0, 0] {
+            continue;
+        }
+
+        let (left, right) = side_windings(&pieces, index);
+        let inside = |windings: [i32; 2]| operation.apply(fill_rule.covers(windings[0]), fill_rule.covers(windings[1]));
+        match (inside(left), inside(right)) {
+            (true, false) => boundary.push((piece.from, piece.to)),
+            (false, true) => boundary.push((piece.to, piece.from)),
+            _ => {}
+        }
+    }
+
+    contours(&boundary)
+        .iter()
+        .map(|contour| simplify(contour))
+        .filter(|contour| contour.len() >= 3)
+        .collect()
+}
+
+/// Clips the polygon against a convex one, after Sutherland and Hodgman.
+///
+/// Each edge of the clip polygon in turn cuts away the part of the subject beyond it, in
+/// O(nm) for n subject and m clip vertices. The clip polygon may run either way, the result
+/// runs the way the subject does. A concave subject falling apart into several pieces
+/// comes out as a single contour, with edges doubling back along the clip boundary joining
+/// the pieces. Winding numbers inside the clip polygon stay the same, so the result still
+/// covers the right area under either fill rule. A clip polygon with fewer than three distinct
+/// vertices encloses nothing and leaves nothing of the subject.
+pub fn clip_convex(subject: &[Point2D<f64>], clip: &[Point2D<f64>]) -> Vec<Point2D<f64>> {
+    let first = match clip.first() {
+        Some(&first) => first,
+        None => return Vec::new(),
+    };
+    let second = match clip.iter().find(|&&point| point != first) {
+        Some(&second) => second,
+        None => return Vec::new(),
+    };
+    if !clip.iter().any(|&point| point != first && point != second) {
+        return Vec::new();
+    }
+
+    let mut clip = clip.to_vec();
+    if signed_area(&clip) < 0.0 {
+        clip.reverse();
+    }
+
+    let mut result = subject.to_vec();
+    for (i, &from) in clip.iter().enumerate() {
+        let to = clip[(i + 1) % clip.len()];
+        if from == to || result.is_empty() {
+            continue;
+        }
+
+        let input = result;
+        result = Vec::with_capacity(input.len() + 2);
+        for (j, &point) in input.iter().enumerate() {
+            let next = input[(j + 1) % input.len()];
+            let (side, next_side) = (orient2d(from, to, point), orient2d(from, to, next));
+
+            if side >= 0.0 {
+                result.push(point);
+            }
+            if (side > 0.0 && next_side < 0.0) || (side < 0.0 && next_side > 0.0) {
+                result.push(point.lerp(&next, side / (side - next_side)));
+            }
+        }
+    }
+    result
+}
+
+/// Whether the contour is a convex polygon, running either way.
+fn is_convex(contour: &[Point2D<f64>]) -> bool {
+    let (mut left, mut right) = (false, false);
+    let mut directions = Vec::new();
+
+    for (i, &point) in contour.iter().enumerate() {
+        let (next, after) = (contour[(i + 1) % contour.len()], contour[(i + 2) % contour.len()]);
+        let side = orient2d(point, next, after);
+        left |= side > 0.0;
+        right |= side < 0.0;
+
+        if next.x != point.x {
+            directions.push(next.x > point.x);
+        }
+    }
+
+    // Turning one way, a simple contour only changes between going left and right twice.
+    let changes = (0..directions.len())
+        .filter(|&i| directions[i] != directions[(i + 1) % directions.len()])
+        .count();
+    left != right && changes <= 2
+}
+
+/// Splits the edges, tagged with their polygon, where they cross or touch another one, and
+/// merges the pieces running along each other.
+fn split(edges: &[(Point2D<f64>, Point2D<f64>, usize)]) -> Vec<Piece> {
+    let mut splits: Vec<Vec<Point2D<f64>>> = edges.iter().map(|&(from, to, _)| vec![from, to]).collect();
+
+    // Sweeps along x, only edges overlapping in x can meet.
+    let bounds: Vec<(Point2D<f64>, Point2D<f64>)> = edges.iter().map(|&(from, to, _)| (from.min(to), from.max(to))).collect();
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&a, &b| bounds[a].0.x.partial_cmp(&bounds[b].0.x).unwrap_or(Ordering::Equal));
+
+    for (i, &a) in order.iter().enumerate() {
+        for &b in &order[i + 1..] {
+            if bounds[b].0.x > bounds[a].1.x {
+                break;
+            }
+            if bounds[b].0.y > bounds[a].1.y || bounds[a].0.y > bounds[b].1.y {
+                continue;
+            }
+
+            let ((p, q, _), (r, s, _)) = (edges[a], edges[b]);
+            let (r_side, s_side) = (orient2d(p, q, r), orient2d(p, q, s));
+            let (p_side, q_side) = (orient2d(r, s, p), orient2d(r, s, q));
+
+            // Ends lying on the other edge split it exactly.
+            for &(side, point, edge) in &[(r_side, r, a), (s_side, s, a), (p_side, p, b), (q_side, q, b)] {
+                if side == 0.0 && contains(bounds[edge], point) {
+                    splits[edge].push(point);
+                }
+            }
+
+            if opposite_sides(r_side, s_side) && opposite_sides(p_side, q_side) {
+                let point = p.lerp(&q, p_side / (p_side - q_side));
+                // Keeps the rounded point within both edges.
+                let point = point.max(bounds[a].0.max(bounds[b].0)).min(bounds[a].1.min(bounds[b].1));
+                splits[a].push(point);
+                splits[b].push(point);
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut indices = HashMap::new();
+    for (&(from, to, polygon), mut points) in edges.iter().zip(splits) {
+        let direction = to - from;
+        points.sort_by(|&a, &b| {
+            (a - from)
+                .dot(direction)
+                .partial_cmp(&(b - from).dot(direction))
+                .unwrap_or(Ordering::Equal)
+        });
+        points.dedup();
+
+        for pair in points.windows(2) {
+            let (from, to, sign) = match lexicographic(pair[0], pair[1]) {
+                Ordering::Less => (pair[0], pair[1], 1),
+                _ => (pair[1], pair[0], -1),
+            };
+
+            let index = *indices.entry((key(from), key(to))).or_insert_with(|| {
+                pieces.push(Piece {
+                    from: from,
+                    to: to,
+                    windings: [0, 0],
+                });
+                pieces.len() - 1
+            });
+            pieces[index].windings[polygon] += sign;
+        }
+    }
+    pieces
+}
+
+/// Winding numbers of both polygons left and right of the piece.
+///
+/// Counts the crossings of a ray from its middle, turned a quarter so that the piece isn't
+/// horizontal. The ray passes right of the piece going up, the piece itself adds its own
+/// windings on the left.
+fn side_windings(pieces: &[Piece], index: usize) -> ([i32; 2], [i32; 2]) {
+    let piece = &pieces[index];
+    let turn = |point: Point2D<f64>| {
+        if piece.from.y == piece.to.y {
+            Point2D::new(-point.y, point.x)
+        } else {
+            point
+        }
+    };
+    let middle = turn(piece.from.lerp(&piece.to, 0.5));
+
+    let mut right = [0, 0];
+    for (other, crossed) in pieces.iter().enumerate() {
+        let (from, to) = (turn(crossed.from), turn(crossed.to));
+        let sign = if other == index {
+            0
+        } else if from.y <= middle.y && to.y > middle.y && orient2d(from, to, middle) > 0.0 {
+            1
+        } else if to.y <= middle.y && from.y > middle.y && orient2d(from, to, middle) < 0.0 {
+            -1
+        } else {
+            0
+        };
+
+        right[0] += sign * crossed.windings[0];
+        right[1] += sign * crossed.windings[1];
+    }
+
+    // Going up, the right of the piece is the right of the ray's crossings.
+    let upward = if turn(piece.from).y < turn(piece.to).y { 1 } else { -1 };
+    let left = [right[0] + upward * piece.windings[0], right[1] + upward * piece.windings[1]];
+    if upward > 0 {
+        (left, right)
+    } else {
+        (right, left)
+    }
+}
+
+/// Chains the directed boundary edges into contours, keeping the inside on their left.
+///
+/// Where several contours meet at a vertex, each leaves by the first edge clockwise from
+/// the one it came in by, which keeps contours from crossing themselves.
+fn contours(boundary: &[(Point2D<f64>, Point2D<f64>)]) -> Vec<Vec<Point2D<f64>>> {
+    let mut outgoing: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+    for (index, &(from, _)) in boundary.iter().enumerate() {
+        outgoing.entry(key(from)).or_default().push(index);
+    }
+
+    let mut used = vec![false; boundary.len()];
+    let mut contours = Vec::new();
+    for start in 0..boundary.len() {
+        if used[start] {
+            continue;
+        }
+
+        let mut contour = Vec::new();
+        let mut edge = start;
+        while !used[edge] {
+            used[edge] = true;
+            let (from, to) = boundary[edge];
+            contour.push(from);
+
+            edge = outgoing[&key(to)]
+                .iter()
+                .cloned()
+                .min_by(|&a, &b| clockwise(to, from, boundary[a].1, boundary[b].1))
+                .unwrap();
+        }
+        contours.push(contour);
+    }
+    contours
+}
+
+/// Orders `a` and `b` by how far clockwise they lie from `back`, seen from `center`.
+fn clockwise(center: Point2D<f64>, back: Point2D<f64>, a: Point2D<f64>, b: Point2D<f64>) -> Ordering {
+    let half = |point: Point2D<f64>| {
+        let side = orient2d(center, back, point);
+        if side < 0.0 {
+            0
+        } else if side > 0.0 {
+            2
+        } else if (point - center).dot(back - center) < 0.0 {
+            1
+        } else {
+            3
+        }
+    };
+
+    half(a).cmp(&half(b)).then_with(|| {
+        let side = orient2d(center, a, b);
+        if side < 0.0 {
+            Ordering::Less
+        } else if side > 0.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    })
+}
+
+/// Leaves out vertices in the middle of straight runs.
+fn simplify(contour: &[Point2D<f64>]) -> Vec<Point2D<f64>> {
+    let count = contour.len();
+    (0..count)
+        .filter(|&i| {
+            let (prev, point, next) = (contour[(i + count - 1) % count], contour[i], contour[(i + 1) % count]);
+            point != next && (orient2d(prev, point, next) != 0.0 || (point - prev).dot(next - point) < 0.0)
+        })
+        .map(|i| contour[i])
+        .collect()
+}
+
+fn contains(bounds: (Point2D<f64>, Point2D<f64>), point: Point2D<f64>) -> bool {
+    let (min, max) = bounds;
+    point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y
+}
+
+fn opposite_sides(a: f64, b: f64) -> bool {
+    (a > 0.0 && b < 0.0) || (a < 0.0 && b > 0.0)
+}
+
+fn lexicographic(a: Point2D<f64>, b: Point2D<f64>) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+/// Bits of the coordinates, the same for equal points.
+fn key(point: Point2D<f64>) -> (u64, u64) {
+    ((point.x + 0.0).to_bits(), (point.y + 0.0).to_bits())
+}
@@ -0,0 +1,572 @@
+use geometry::predicates::{incircle, orient2d};
+use point::Point2D;
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+
+/// Vertex at infinity, the triangles using it are ghosts wrapping the hull.
+const GHOST: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct Triangle {
+    /// Counter-clockwise, ghosts keep the ghost vertex where the hull edge would continue.
+    vertices: [usize; 3],
+    /// Triangle across the edge opposite each vertex.
+    neighbours: [usize; 3],
+    /// Whether the edge opposite each vertex is constrained.
+    constrained: [bool; 3],
+    live: bool,
+}
+
+impl Triangle {
+    fn is_ghost(&self) -> bool {
+        self.vertices.contains(&GHOST)
+    }
+
+    fn index_of(&self, vertex: usize) -> usize {
+        self.vertices.iter().position(|&other| other == vertex).unwrap()
+    }
+}
+
+/// Delaunay triangulation of scattered points, optionally constrained to contain given edges.
+///
+/// Points are inserted one at a time in lexicographic order, each one replacing the
+/// triangles whose circumcircle it falls into by a fan around it, after Bowyer and Watson.
+/// Ghost triangles joining the hull edges to a vertex at infinity keep the mesh closed, so
+/// points outside the hull are inserted the same way. Constrained edges then flip the edges
+/// crossing them away, after Sloan, and the remaining edges are flipped until every one is
+/// locally Delaunay, skipping the constrained ones.
+///
+/// All decisions go through the exact predicates, so nearly cocircular or collinear points
+/// can't make the construction inconsistent. Cocircular points are triangulated arbitrarily
+/// among the valid choices. Duplicate points are left out, their first occurrence stands in
+/// for them. Points all on one line have no triangles. Coordinates have to be finite.
+#[derive(Clone, Debug)]
+pub struct Delaunay {
+    points: Vec<Point2D<f64>>,
+    /// The first occurrence of every point, itself for distinct points.
+    representatives: Vec<usize>,
+    /// Distinct points in lexicographic order.
+    order: Vec<usize>,
+    mesh: Vec<Triangle>,
+    free: Vec<usize>,
+    /// Scratch marks of the triangles in the cavity being replaced.
+    marks: Vec<bool>,
+    /// Some live triangle around every inserted vertex.
+    vertex_triangles: Vec<usize>,
+    /// Recently created real triangle the point location walks from.
+    last: usize,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Delaunay {
+    pub fn new(points: &[Point2D<f64>]) -> Delaunay {
+        Delaunay::with_constraints(points, &[])
+    }
+
+    /// Triangulates the points so that the edges between the given pairs of points are part
+    /// of the triangulation. Edges passing through other points get split at them.
+    ///
+    /// # Panics
+    ///
+    /// Panics when an index is out of bounds or two constrained edges cross.
+    pub fn with_constraints(points: &[Point2D<f64>], edges: &[(usize, usize)]) -> Delaunay {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        order.sort_by(|&a, &b| lexicographic(points[a], points[b]));
+
+        let mut representatives: Vec<usize> = (0..points.len()).collect();
+        for pair in order.windows(2) {
+            if points[pair[0]] == points[pair[1]] {
+                representatives[pair[1]] = representatives[pair[0]];
+            }
+        }
+        order.dedup_by(|a, b| points[*a] == points[*b]);
+
+        let mut delaunay = Delaunay {
+            points: points.to_vec(),
+            representatives: representatives,
+            order: order,
+            mesh: Vec::new(),
+            free: Vec::new(),
+            marks: Vec::new(),
+            vertex_triangles: vec![GHOST; points.len()],
+            last: 0,
+            triangles: Vec::new(),
+        };
+
+        if delaunay.build() {
+            for &(a, b) in edges {
+                let (a, b) = (delaunay.representatives[a], delaunay.representatives[b]);
+                delaunay.constrain(a, b);
+            }
+            if !edges.is_empty() {
+                delaunay.legalize();
+            }
+
+            delaunay.triangles = delaunay
+                .mesh
+                .iter()
+                .filter(|triangle| triangle.live && !triangle.is_ghost())
+                .map(|triangle| triangle.vertices)
+                .collect();
+        }
+        delaunay
+    }
+
+    pub fn points(&self) -> &[Point2D<f64>] {
+        &self.points
+    }
+
+    /// Triangles as indices into the points, counter-clockwise.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Points on the convex hull, counter-clockwise, including those in the middle of its
+    /// edges. Points all on one line are listed along it.
+    pub fn hull(&self) -> Vec<usize> {
+        let start = match self.mesh.iter().position(|triangle| triangle.live && triangle.is_ghost()) {
+            Some(start) => start,
+            None => return self.order.clone(),
+        };
+
+        let mut hull = Vec::new();
+        let mut ghost = start;
+        loop {
+            // The ghost (u, v, ∞) lies across the hull edge from v to u.
+            let triangle = &self.mesh[ghost];
+            let index = triangle.index_of(GHOST);
+            let v = triangle.vertices[(index + 2) % 3];
+            hull.push(v);
+
+            ghost = triangle.neighbours[(index + 2) % 3];
+            if ghost == start {
+                return hull;
+            }
+        }
+    }
+
+    /// Points sharing an edge with the point, counter-clockwise around it. Points all on one
+    /// line neighbour those next to them along it.
+    pub fn neighbours(&self, point: usize) -> Vec<usize> {
+        let vertex = self.representatives[point];
+
+        if self.mesh.is_empty() {
+            let position = self.order.iter().position(|&other| other == vertex).unwrap();
+            let before = position.checked_sub(1).map(|before| self.order[before]);
+            return before.into_iter().chain(self.order.get(position + 1).cloned()).collect();
+        }
+
+        self.around(vertex)
+            .into_iter()
+            .map(|(triangle, index)| self.mesh[triangle].vertices[(index + 1) % 3])
+            .filter(|&other| other != GHOST)
+            .collect()
+    }
+
+    /// Whether the edge between the points was constrained.
+    pub fn is_constrained(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.representatives[a], self.representatives[b]);
+        self.find_edge(a, b)
+            .is_some_and(|(triangle, index)| self.mesh[triangle].constrained[index])
+    }
+
+    /// Inserts the distinct points, returns false when they are all collinear.
+    fn build(&mut self) -> bool {
+        if self.order.len() < 3 {
+            return false;
+        }
+
+        let (a, b) = (self.order[0], self.order[1]);
+        let third = (2..self.order.len()).find(|&i| orient2d(self.points[a], self.points[b], self.points[self.order[i]]) != 0.0);
+        let third = match third {
+            Some(third) => third,
+            None => return false,
+        };
+
+        self.initialize(a, b, self.order[third]);
+        for i in 2..self.order.len() {
+            if i != third {
+                let vertex = self.order[i];
+                self.insert(vertex);
+            }
+        }
+        true
+    }
+
+    /// Sets up the mesh as one real triangle and three ghosts.
+    fn initialize(&mut self, a: usize, b: usize, c: usize) {
+        let (b, c) = if orient2d(self.points[a], self.points[b], self.points[c]) > 0.0 {
+            (b, c)
+        } else {
+            (c, b)
+        };
+        let triangles = [[a, b, c], [c, b, GHOST], [a, c, GHOST], [b, a, GHOST]];
+
+        for vertices in &triangles {
+            self.allocate(*vertices);
+        }
+
+        // Every edge of one triangle runs backwards in another.
+        for t in 0..4 {
+            for i in 0..3 {
+                let (from, to) = (triangles[t][(i + 1) % 3], triangles[t][(i + 2) % 3]);
+                let other = (0..4)
+                    .find(|&other| (0..3).any(|j| triangles[other][(j + 1) % 3] == to && triangles[other][(j + 2) % 3] == from))
+                    .unwrap();
+                self.mesh[t].neighbours[i] = other;
+            }
+        }
+        self.last = 0;
+    }
+
+    fn allocate(&mut self, vertices: [usize; 3]) -> usize {
+        let triangle = Triangle {
+            vertices: vertices,
+            neighbours: [GHOST; 3],
+            constrained: [false; 3],
+            live: true,
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.mesh[index] = triangle;
+                index
+            }
+            None => {
+                self.mesh.push(triangle);
+                self.marks.push(false);
+                self.mesh.len() - 1
+            }
+        };
+
+        for &vertex in &vertices {
+            if vertex != GHOST {
+                self.vertex_triangles[vertex] = index;
+            }
+        }
+        if !self.mesh[index].is_ghost() {
+            self.last = index;
+        }
+        index
+    }
+
+    /// Replaces the triangles the vertex conflicts with by a fan around it.
+    fn insert(&mut self, vertex: usize) {
+        let point = self.points[vertex];
+        let start = self.locate(point);
+
+        let mut cavity = vec![start];
+        let mut stack = vec![start];
+        self.marks[start] = true;
+        while let Some(triangle) = stack.pop() {
+            for i in 0..3 {
+                let neighbour = self.mesh[triangle].neighbours[i];
+                if !self.marks[neighbour] && self.conflicts(neighbour, point) {
+                    self.marks[neighbour] = true;
+                    cavity.push(neighbour);
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        // The edges between the cavity and the rest, counter-clockwise around the cavity.
+        let mut boundary = Vec::new();
+        for &triangle in &cavity {
+            let Triangle {
+                vertices,
+                neighbours,
+                constrained,
+                ..
+            } = self.mesh[triangle];
+            for i in 0..3 {
+                if !self.marks[neighbours[i]] {
+                    boundary.push((
+                        vertices[(i + 1) % 3],
+                        vertices[(i + 2) % 3],
+                        neighbours[i],
+                        triangle,
+                        constrained[i],
+                    ));
+                }
+            }
+        }
+
+        let mut starting = HashMap::with_capacity(boundary.len());
+        let mut ending = HashMap::with_capacity(boundary.len());
+        let mut fan = Vec::with_capacity(boundary.len());
+        for &(from, to, outside, old, constrained) in &boundary {
+            let triangle = self.allocate([from, to, vertex]);
+            self.mesh[triangle].neighbours[2] = outside;
+            self.mesh[triangle].constrained[2] = constrained;
+            self.replace_neighbour(outside, old, triangle);
+
+            starting.insert(from, triangle);
+            ending.insert(to, triangle);
+            fan.push(triangle);
+        }
+
+        for triangle in fan {
+            let [from, to, _] = self.mesh[triangle].vertices;
+            self.mesh[triangle].neighbours[0] = starting[&to];
+            self.mesh[triangle].neighbours[1] = ending[&from];
+        }
+
+        // Freed only now so that no new triangle takes the place of an old one still linked.
+        for &triangle in &cavity {
+            self.marks[triangle] = false;
+            self.mesh[triangle].live = false;
+            self.free.push(triangle);
+        }
+    }
+
+    /// Walks towards the point from the last triangle, returns the triangle containing it or
+    /// the ghost across the hull edge it lies beyond.
+    fn locate(&self, point: Point2D<f64>) -> usize {
+        let mut triangle = self.last;
+
+        'walk: loop {
+            let current = &self.mesh[triangle];
+            if current.is_ghost() {
+                return triangle;
+            }
+
+            for i in 0..3 {
+                let (from, to) = (current.vertices[(i + 1) % 3], current.vertices[(i + 2) % 3]);
+                if orient2d(self.points[from], self.points[to], point) < 0.0 {
+                    triangle = current.neighbours[i];
+                    continue 'walk;
+                }
+            }
+            return triangle;
+        }
+    }
+
+    /// Whether the point lies inside the circumcircle of the triangle. For ghosts, whether it
+    /// lies beyond their hull edge or inside it.
+    fn conflicts(&self, triangle: usize, point: Point2D<f64>) -> bool {
+        let triangle = &self.mesh[triangle];
+
+        if !triangle.is_ghost() {
+            let [a, b, c] = triangle.vertices;
+            return incircle(self.points[a], self.points[b], self.points[c], point) > 0.0;
+        }
+
+        let index = triangle.index_of(GHOST);
+        let (from, to) = (
+            self.points[triangle.vertices[(index + 1) % 3]],
+            self.points[triangle.vertices[(index + 2) % 3]],
+        );
+        let side = orient2d(from, to, point);
+        side > 0.0 || (side == 0.0 && (point - from).dot(point - to) < 0.0)
+    }
+
+    fn replace_neighbour(&mut self, triangle: usize, old: usize, new: usize) {
+        let neighbours = &mut self.mesh[triangle].neighbours;
+        if let Some(slot) = neighbours.iter_mut().find(|neighbour| **neighbour == old) {
+            *slot = new;
+        }
+    }
+
+    /// Triangles around the vertex counter-clockwise, with the index of the vertex in each.
+    fn around(&self, vertex: usize) -> Vec<(usize, usize)> {
+        let start = self.vertex_triangles[vertex];
+        let mut around = Vec::new();
+        let mut triangle = start;
+
+        loop {
+            let index = self.mesh[triangle].index_of(vertex);
+            around.push((triangle, index));
+
+            triangle = self.mesh[triangle].neighbours[(index + 1) % 3];
+            if triangle == start {
+                return around;
+            }
+        }
+    }
+
+    /// Triangle with the edge running from `a` to `b`, and the index of the vertex opposite.
+    fn find_edge(&self, a: usize, b: usize) -> Option<(usize, usize)> {
+        if self.mesh.is_empty() || self.vertex_triangles[a] == GHOST {
+            return None;
+        }
+
+        self.around(a)
+            .into_iter()
+            .find(|&(triangle, index)| self.mesh[triangle].vertices[(index + 1) % 3] == b)
+            .map(|(triangle, index)| (triangle, (index + 2) % 3))
+    }
+
+    /// Flips the edge opposite the vertex at `index` of the triangle, the triangles (a, b, c)
+    /// and (d, c, b) becoming (a, b, d) and (d, c, a).
+    fn flip(&mut self, triangle: usize, index: usize) {
+        let other = self.mesh[triangle].neighbours[index];
+        let t = self.mesh[triangle].clone();
+        let o = self.mesh[other].clone();
+
+        let (a, b, c) = (t.vertices[index], t.vertices[(index + 1) % 3], t.vertices[(index + 2) % 3]);
+        let j = o.index_of(c) + 2;
+        let d = o.vertices[j % 3];
+
+        // Edges and flags across b-d, d-c, c-a and a-b.
+        let (bd, dc) = (
+            (o.neighbours[(j + 1) % 3], o.constrained[(j + 1) % 3]),
+            (o.neighbours[(j + 2) % 3], o.constrained[(j + 2) % 3]),
+        );
+        let (ca, ab) = (
+            (t.neighbours[(index + 1) % 3], t.constrained[(index + 1) % 3]),
+            (t.neighbours[(index + 2) % 3], t.constrained[(index + 2) % 3]),
+        );
+
+        self.mesh[triangle].vertices = [a, b, d];
+        self.mesh[triangle].neighbours = [bd.0, other, ab.0];
+        self.mesh[triangle].constrained = [bd.1, false, ab.1];
+        self.mesh[other].vertices = [d, c, a];
+        self.mesh[other].neighbours = [ca.0, triangle, dc.0];
+        self.mesh[other].constrained = [ca.1, false, dc.1];
+
+        self.replace_neighbour(bd.0, other, triangle);
+        self.replace_neighbour(ca.0, triangle, other);
+
+        for &(vertex, owner) in &[(a, triangle), (b, triangle), (c, other), (d, other)] {
+            if vertex != GHOST {
+                self.vertex_triangles[vertex] = owner;
+            }
+        }
+        self.last = triangle;
+    }
+
+    /// Makes the segment between the vertices an edge, splitting it at vertices on it.
+    fn constrain(&mut self, a: usize, b: usize) {
+        let mut segments = vec![(a, b)];
+
+        while let Some((a, b)) = segments.pop() {
+            if a == b {
+                continue;
+            }
+
+            let (crossed, end) = self.crossed_edges(a, b);
+            if end != b {
+                segments.push((end, b));
+            }
+
+            self.flip_crossed_edges(a, end, crossed);
+            for &(from, to) in &[(a, end), (end, a)] {
+                let (triangle, index) = self.find_edge(from, to).unwrap();
+                self.mesh[triangle].constrained[index] = true;
+            }
+        }
+    }
+
+    /// Edges crossed by the segment from `a` towards `b`, up to `b` or the first vertex on the
+    /// segment, which is returned too.
+    fn crossed_edges(&self, a: usize, b: usize) -> (VecDeque<(usize, usize)>, usize) {
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let mut crossed = VecDeque::new();
+
+        // The triangle around `a` the segment leaves through, or a vertex on the segment.
+        let mut entry = None;
+        for (triangle, index) in self.around(a) {
+            let vertices = self.mesh[triangle].vertices;
+            let (right, left) = (vertices[(index + 1) % 3], vertices[(index + 2) % 3]);
+            if right == GHOST {
+                continue;
+            }
+
+            let side = orient2d(pa, pb, self.points[right]);
+            if side == 0.0 && (self.points[right] - pa).dot(pb - pa) > 0.0 {
+                return (crossed, right);
+            }
+            if left != GHOST && side < 0.0 && orient2d(pa, pb, self.points[left]) > 0.0 {
+                entry = Some((triangle, index));
+                break;
+            }
+        }
+
+        let (mut triangle, mut index) = entry.unwrap();
+        loop {
+            let vertices = self.mesh[triangle].vertices;
+            let (right, left) = (vertices[(index + 1) % 3], vertices[(index + 2) % 3]);
+            assert!(
+                !self.mesh[triangle].constrained[index],
+                "constrained edges ({}, {}) and ({}, {}) cross",
+                a, b, left, right
+            );
+            crossed.push_back((left, right));
+
+            let next = self.mesh[triangle].neighbours[index];
+            let opposite = self.mesh[next].vertices[(self.mesh[next].index_of(right) + 1) % 3];
+            let side = orient2d(pa, pb, self.points[opposite]);
+
+            if opposite == b || side == 0.0 {
+                return (crossed, opposite);
+            }
+
+            // Leave through the edge joining the opposite vertex to the other side.
+            triangle = next;
+            index = self.mesh[next].index_of(if side > 0.0 { left } else { right });
+        }
+    }
+
+    /// Flips the crossed edges until none crosses the segment, after Sloan.
+    fn flip_crossed_edges(&mut self, a: usize, b: usize, mut crossed: VecDeque<(usize, usize)>) {
+        let (pa, pb) = (self.points[a], self.points[b]);
+
+        while let Some((left, right)) = crossed.pop_front() {
+            let (triangle, index) = self.find_edge(left, right).unwrap();
+            let near = self.mesh[triangle].vertices[index];
+            let neighbour = self.mesh[triangle].neighbours[index];
+            let far = self.mesh[neighbour].vertices[(self.mesh[neighbour].index_of(left) + 1) % 3];
+
+            // Only a convex quadrilateral can swap diagonals.
+            let (p, q) = (self.points[near], self.points[far]);
+            if !opposite_sides(orient2d(p, q, self.points[left]), orient2d(p, q, self.points[right])) {
+                crossed.push_back((left, right));
+                continue;
+            }
+
+            self.flip(triangle, index);
+            let shares_end = near == a || near == b || far == a || far == b;
+            if !shares_end && opposite_sides(orient2d(pa, pb, p), orient2d(pa, pb, q)) {
+                crossed.push_back((near, far));
+            }
+        }
+    }
+
+    /// Flips edges until all unconstrained ones are locally Delaunay, after Lawson.
+    fn legalize(&mut self) {
+        let mut stack: Vec<(usize, usize)> = (0..self.mesh.len())
+            .flat_map(|triangle| (0..3).map(move |index| (triangle, index)))
+            .collect();
+
+        while let Some((triangle, index)) = stack.pop() {
+            let current = &self.mesh[triangle];
+            if !current.live || current.is_ghost() || current.constrained[index] {
+                continue;
+            }
+
+            let neighbour = current.neighbours[index];
+            if self.mesh[neighbour].is_ghost() {
+                continue;
+            }
+
+            let [a, b, c] = current.vertices;
+            let far = self.mesh[neighbour].vertices[(self.mesh[neighbour].index_of(current.vertices[(index + 1) % 3]) + 1) % 3];
+            if incircle(self.points[a], self.points[b], self.points[c], self.points[far]) > 0.0 {
+                self.flip(triangle, index);
+                stack.extend_from_slice(&[(triangle, 0), (triangle, 2), (neighbour, 0), (neighbour, 2)]);
+            }
+        }
+    }
+}
+
+fn lexicographic(a: Point2D<f64>, b: Point2D<f64>) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+/// Whether two orientations put their points strictly on opposite sides of a line.
+fn opposite_sides(a: f64, b: f64) -> bool {
+    (a > 0.0 && b < 0.0) || (a < 0.0 && b > 0.0)
+}
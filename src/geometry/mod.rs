@@ -0,0 +1,35 @@
+//! Planar algorithms over `Point2D` polygons and point sets.
+//!
+//! Polygons are slices of vertices with an implied closing edge. Counter-clockwise means
+//! counter-clockwise with the y axis pointing up, the orientation of positive area.
+
+pub mod boolean;
+pub mod delaunay;
+pub mod predicates;
+pub mod triangulate;
+pub mod voronoi;
+
+pub use self::boolean::{boolean, clip_convex, FillRule, Operation};
+pub use self::delaunay::Delaunay;
+pub use self::triangulate::{triangulate, triangulate_with_holes};
+pub use self::voronoi::Voronoi;
+
+use point::Point2D;
+
+use num_lib::Float;
+
+/// Area of the polygon, positive when its vertices run counter-clockwise.
+pub fn signed_area<T: Float>(polygon: &[Point2D<T>]) -> T {
+    let mut sum = T::zero();
+
+    for (i, point) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        sum = sum + point.x * next.y - next.x * point.y;
+    }
+    sum / (T::one() + T::one())
+}
+
+/// Twice the signed area of the triangle, positive when `c` lies left of `a` to `b`.
+fn orient<T: Float>(a: Point2D<T>, b: Point2D<T>, c: Point2D<T>) -> T {
+    (b - a).cross(c - a)
+}
@@ -0,0 +1,174 @@
+//! Orientation and in-circle tests with exact signs.
+//!
+//! Both tests first evaluate their determinant in plain floating point and return it when
+//! an error bound shows its sign is right, which is nearly always. Otherwise they evaluate
+//! it again exactly on expansions, sums of non-overlapping floats after Shewchuk's
+//! "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates".
+//! Only the signs are exact, the magnitudes approximate the determinants.
+//!
+//! The coordinates have to be finite, and small enough for their products not to overflow.
+
+use point::Point2D;
+
+/// Half the distance between 1 and the next float, the relative rounding error.
+const EPSILON: f64 = f64::EPSILON / 2.0;
+
+/// Splits a float into two halves of 26 significant bits.
+const SPLITTER: f64 = 134_217_729.0;
+
+const ORIENT_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const INCIRCLE_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Twice the signed area of the triangle, positive when `c` lies left of the line from `a`
+/// to `b`, negative when it lies right of it and zero when the points are collinear.
+pub fn orient2d(a: Point2D<f64>, b: Point2D<f64>, c: Point2D<f64>) -> f64 {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let determinant = left - right;
+
+    if (left > 0.0 && right <= 0.0) || (left < 0.0 && right >= 0.0) || left == 0.0 {
+        // The terms don't cancel out, the sign can't be wrong.
+        return determinant;
+    }
+
+    if determinant.abs() >= ORIENT_BOUND * (left.abs() + right.abs()) {
+        return determinant;
+    }
+
+    let left = product(&difference(a.x, c.x), &difference(b.y, c.y));
+    let right = product(&difference(a.y, c.y), &difference(b.x, c.x));
+    estimate(&sum(&left, &negate(&right)))
+}
+
+/// Positive when `d` lies inside the circle through `a`, `b` and `c`, negative when it lies
+/// outside and zero when the four points are cocircular. The first three points have to
+/// run counter-clockwise, the signs flip otherwise.
+pub fn incircle(a: Point2D<f64>, b: Point2D<f64>, c: Point2D<f64>, d: Point2D<f64>) -> f64 {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let determinant = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent =
+        (bdxcdy.abs() + cdxbdy.abs()) * alift + (cdxady.abs() + adxcdy.abs()) * blift + (adxbdy.abs() + bdxady.abs()) * clift;
+
+    if determinant.abs() > INCIRCLE_BOUND * permanent {
+        return determinant;
+    }
+
+    let (adx, ady) = (difference(a.x, d.x), difference(a.y, d.y));
+    let (bdx, bdy) = (difference(b.x, d.x), difference(b.y, d.y));
+    let (cdx, cdy) = (difference(c.x, d.x), difference(c.y, d.y));
+
+    let lift = |dx: &[f64], dy: &[f64]| sum(&product(dx, dx), &product(dy, dy));
+    let cross = |ax: &[f64], ay: &[f64], bx: &[f64], by: &[f64]| sum(&product(ax, by), &negate(&product(ay, bx)));
+
+    let a_term = product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy));
+    let b_term = product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady));
+    let c_term = product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy));
+    estimate(&sum(&sum(&a_term, &b_term), &c_term))
+}
+
+/// Sum and rounding error of two floats, `a` being at least as large as `b`.
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+/// Sum and rounding error of two floats.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Product and rounding error of two floats.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_high, a_low) = split(a);
+    let (b_high, b_low) = split(b);
+    let error = x - a_high * b_high - a_low * b_high - a_high * b_low;
+    (x, a_low * b_low - error)
+}
+
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let high = c - (c - a);
+    (high, a - high)
+}
+
+/// Exact difference of two floats as an expansion.
+fn difference(a: f64, b: f64) -> Vec<f64> {
+    let x = a - b;
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    let error = (a - a_virtual) + (b_virtual - b);
+    compress(vec![error, x])
+}
+
+/// Drops the zero components, keeping a single zero for a zero expansion.
+fn compress(mut expansion: Vec<f64>) -> Vec<f64> {
+    expansion.retain(|&component| component != 0.0);
+    if expansion.is_empty() {
+        expansion.push(0.0);
+    }
+    expansion
+}
+
+/// Adds a float to an expansion, Shewchuk's GROW-EXPANSION.
+fn grow(expansion: &[f64], b: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(expansion.len() + 1);
+    let mut q = b;
+
+    for &component in expansion {
+        let (sum, error) = two_sum(q, component);
+        result.push(error);
+        q = sum;
+    }
+    result.push(q);
+    compress(result)
+}
+
+fn sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |sum, &component| grow(&sum, component))
+}
+
+fn negate(e: &[f64]) -> Vec<f64> {
+    e.iter().map(|&component| -component).collect()
+}
+
+/// Multiplies an expansion by a float, Shewchuk's SCALE-EXPANSION.
+fn scale(e: &[f64], b: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(2 * e.len());
+    let (mut q, error) = two_product(e[0], b);
+    result.push(error);
+
+    for &component in &e[1..] {
+        let (high, low) = two_product(component, b);
+        let (sum, error) = two_sum(q, low);
+        result.push(error);
+        let (sum, error) = fast_two_sum(high, sum);
+        result.push(error);
+        q = sum;
+    }
+    result.push(q);
+    compress(result)
+}
+
+fn product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter()
+        .fold(vec![0.0], |sum, &component| self::sum(&sum, &scale(e, component)))
+}
+
+/// The largest component, which carries the sign of the expansion.
+fn estimate(e: &[f64]) -> f64 {
+    e[e.len() - 1]
+}
@@ -0,0 +1,551 @@
+use geometry::{orient, signed_area};
+use point::Point2D;
+
+use num_lib::Float;
+use std::cmp::Ordering;
+
+/// Splits the polygon into triangles, see `triangulate_with_holes`.
+pub fn triangulate<T: Float>(outline: &[Point2D<T>]) -> Vec<u32> {
+    triangulate_with_holes::<T, &[Point2D<T>]>(outline, &[])
+}
+
+/// Splits the polygon with holes into triangles by ear clipping.
+///
+/// Returns an index buffer with three vertices per triangle, each triangle running
+/// counter-clockwise whatever the orientation of the input. Vertices are numbered through
+/// the outline first and then through the holes in order, as if they were concatenated.
+///
+/// Holes are joined to the outline by a bridge edge running to a vertex they can see,
+/// which turns the polygon into a single ring walking around the holes. Duplicate and
+/// collinear vertices are skipped, they add no area. Holes may touch the outline and each
+/// other at vertices. Self-intersecting outlines and overlapping holes give some
+/// triangulation of the outline, but not necessarily one covering the area a fill rule
+/// would. Takes O(n²) for n vertices.
+///
+/// # Panics
+///
+/// Panics when there are more vertices than 32 bit indices can number.
+pub fn triangulate_with_holes<T: Float, H: AsRef<[Point2D<T>]>>(outline: &[Point2D<T>], holes: &[H]) -> Vec<u32> {
+    let count = outline.len() + holes.iter().map(|hole| hole.as_ref().len()).sum::<usize>();
+    assert!(count <= u32::MAX as usize, "{} vertices don't fit 32 bit indices", count);
+
+    let mut rings = Rings {
+        nodes: Vec::with_capacity(count + 2 * holes.len()),
+        triangles: Vec::new(),
+    };
+
+    let mut outer = match rings.ring(outline, 0, true) {
+        Some(outer) if rings.nodes[outer].next != rings.nodes[outer].prev => outer,
+        _ => return Vec::new(),
+    };
+
+    let mut leftmost = Vec::new();
+    let mut offset = outline.len();
+    for hole in holes {
+        let hole = hole.as_ref();
+        if hole.len() >= 3 {
+            if let Some(node) = rings.ring(hole, offset, false) {
+                leftmost.push(rings.leftmost(node));
+            }
+        }
+        offset += hole.len();
+    }
+
+    // Bridging from left to right keeps the bridges of later holes clear of earlier ones.
+    leftmost.sort_by(|&a, &b| {
+        let (a, b) = (rings.nodes[a].point, rings.nodes[b].point);
+        a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal)
+    });
+    for hole in leftmost {
+        outer = rings.eliminate_hole(hole, outer);
+    }
+
+    let outer = rings.filter(outer, None);
+    rings.clip(outer, Pass::Plain);
+    rings.triangles
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Node<T> {
+    /// Index of the vertex in the input, bridges duplicate it into a second node.
+    vertex: usize,
+    point: Point2D<T>,
+    prev: usize,
+    next: usize,
+}
+
+/// How hard clipping tried before getting stuck on a ring without ears.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Pass {
+    Plain,
+    /// Duplicate and collinear vertices were removed.
+    Filtered,
+    /// Local self-intersections were clipped off too.
+    Cured,
+}
+
+/// Doubly linked vertex rings, removed nodes are unlinked and left in place.
+struct Rings<T> {
+    nodes: Vec<Node<T>>,
+    triangles: Vec<u32>,
+}
+
+impl<T: Float> Rings<T> {
+    fn point(&self, node: usize) -> Point2D<T> {
+        self.nodes[node].point
+    }
+
+    fn next(&self, node: usize) -> usize {
+        self.nodes[node].next
+    }
+
+    fn prev(&self, node: usize) -> usize {
+        self.nodes[node].prev
+    }
+
+    /// Links the points into a ring running in the requested orientation, returns its last
+    /// node or None when there are no points.
+    fn ring(&mut self, points: &[Point2D<T>], offset: usize, counter_clockwise: bool) -> Option<usize> {
+        let reverse = (signed_area(points) > T::zero()) != counter_clockwise;
+        let mut last = None;
+
+        for i in 0..points.len() {
+            let i = if reverse { points.len() - 1 - i } else { i };
+            last = Some(self.insert(offset + i, points[i], last));
+        }
+
+        let last = last?;
+        let next = self.next(last);
+        if self.point(last) == self.point(next) {
+            self.remove(last);
+            return Some(next);
+        }
+        Some(last)
+    }
+
+    fn insert(&mut self, vertex: usize, point: Point2D<T>, after: Option<usize>) -> usize {
+        let node = self.nodes.len();
+        let (prev, next) = match after {
+            Some(after) => (after, self.next(after)),
+            None => (node, node),
+        };
+
+        self.nodes.push(Node {
+            vertex: vertex,
+            point: point,
+            prev: prev,
+            next: next,
+        });
+        self.nodes[prev].next = node;
+        self.nodes[next].prev = node;
+        node
+    }
+
+    fn remove(&mut self, node: usize) {
+        let (prev, next) = (self.prev(node), self.next(node));
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+    }
+
+    /// Removes duplicate and collinear vertices from `start` on until `end` is reached
+    /// without removing anything, returns a node still in the ring.
+    fn filter(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut node = start;
+
+        loop {
+            let (prev, next) = (self.prev(node), self.next(node));
+            let degenerate =
+                self.point(node) == self.point(next) || orient(self.point(prev), self.point(node), self.point(next)) == T::zero();
+
+            if degenerate {
+                self.remove(node);
+                node = prev;
+                end = prev;
+                if node == self.next(node) {
+                    break;
+                }
+            } else {
+                node = next;
+                if node == end {
+                    break;
+                }
+            }
+        }
+        end
+    }
+
+    /// Clips ears off the ring until a triangle is left. When no ear is found the ring gets
+    /// filtered, then cured of local self-intersections, then split in two.
+    fn clip(&mut self, start: usize, pass: Pass) {
+        let (mut ear, mut stop) = (start, start);
+
+        while self.prev(ear) != self.next(ear) {
+            let (prev, next) = (self.prev(ear), self.next(ear));
+
+            if self.is_ear(ear) {
+                let vertices = [self.nodes[prev].vertex, self.nodes[ear].vertex, self.nodes[next].vertex];
+                self.triangles.extend(vertices.iter().map(|&vertex| vertex as u32));
+                self.remove(ear);
+
+                // Skipping the next vertex avoids fans of sliver triangles.
+                ear = self.next(next);
+                stop = ear;
+                continue;
+            }
+
+            ear = next;
+            if ear == stop {
+                match pass {
+                    Pass::Plain => {
+                        let ear = self.filter(ear, None);
+                        self.clip(ear, Pass::Filtered);
+                    }
+                    Pass::Filtered => {
+                        let ear = self.filter(ear, None);
+                        let ear = self.cure_local_intersections(ear);
+                        self.clip(ear, Pass::Cured);
+                    }
+                    Pass::Cured => self.split(ear),
+                }
+                break;
+            }
+        }
+    }
+
+    /// Whether the corner is convex with no reflex vertex of the ring inside its triangle.
+    fn is_ear(&self, ear: usize) -> bool {
+        let (prev, next) = (self.prev(ear), self.next(ear));
+        let (a, b, c) = (self.point(prev), self.point(ear), self.point(next));
+
+        if orient(a, b, c) <= T::zero() {
+            return false;
+        }
+
+        let (min, max) = (a.min(b).min(c), a.max(b).max(c));
+        let mut node = self.next(next);
+        while node != prev {
+            let point = self.point(node);
+            let inside = point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y;
+
+            if inside
+                && in_triangle(a, b, c, point)
+                && orient(self.point(self.prev(node)), point, self.point(self.next(node))) <= T::zero()
+            {
+                return false;
+            }
+            node = self.next(node);
+        }
+        true
+    }
+
+    /// Clips the triangles of corners where two edges a vertex apart cross each other.
+    fn cure_local_intersections(&mut self, start: usize) -> usize {
+        let (mut start, mut node) = (start, start);
+
+        loop {
+            let (a, next) = (self.prev(node), self.next(node));
+            let b = self.next(next);
+
+            if self.point(a) != self.point(b)
+                && intersects(self.point(a), self.point(node), self.point(next), self.point(b))
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                let vertices = [self.nodes[a].vertex, self.nodes[node].vertex, self.nodes[b].vertex];
+                self.triangles.extend(vertices.iter().map(|&vertex| vertex as u32));
+                self.remove(node);
+                self.remove(next);
+                node = b;
+                start = b;
+            }
+
+            node = self.next(node);
+            if node == start {
+                break;
+            }
+        }
+        self.filter(node, None)
+    }
+
+    /// Splits the ring along a diagonal inside it and clips both halves.
+    fn split(&mut self, start: usize) {
+        let mut a = start;
+
+        loop {
+            let mut b = self.next(self.next(a));
+            while b != self.prev(a) {
+                if self.nodes[a].vertex != self.nodes[b].vertex && self.is_valid_diagonal(a, b) {
+                    let c = self.split_polygon(a, b);
+                    let (a_next, c_next) = (self.next(a), self.next(c));
+                    let a = self.filter(a, Some(a_next));
+                    let c = self.filter(c, Some(c_next));
+
+                    self.clip(a, Pass::Plain);
+                    self.clip(c, Pass::Plain);
+                    return;
+                }
+                b = self.next(b);
+            }
+
+            a = self.next(a);
+            if a == start {
+                return;
+            }
+        }
+    }
+
+    /// Node of the ring furthest left, the lowest one among equals.
+    fn leftmost(&self, start: usize) -> usize {
+        let (mut leftmost, mut node) = (start, self.next(start));
+
+        while node != start {
+            let (point, best) = (self.point(node), self.point(leftmost));
+            if point.x < best.x || (point.x == best.x && point.y < best.y) {
+                leftmost = node;
+            }
+            node = self.next(node);
+        }
+        leftmost
+    }
+
+    /// Joins the hole ring into the outer ring, returns a node of the joined ring.
+    fn eliminate_hole(&mut self, hole: usize, outer: usize) -> usize {
+        let bridge = match self.find_bridge(hole, outer) {
+            Some(bridge) => bridge,
+            None => return outer,
+        };
+
+        let reverse = self.split_polygon(bridge, hole);
+        let reverse_next = self.next(reverse);
+        self.filter(reverse, Some(reverse_next));
+
+        let bridge_next = self.next(bridge);
+        self.filter(bridge, Some(bridge_next))
+    }
+
+    /// Outer node the leftmost hole node can be connected to without crossing an edge.
+    ///
+    /// Casts a ray to the left and takes the nearer end of the edge it hits first, unless
+    /// vertices inside the triangle between the hit and the hole block the view. Then the
+    /// one closest in angle to the ray is taken, nothing can hide it.
+    fn find_bridge(&self, hole: usize, outer: usize) -> Option<usize> {
+        let target = self.point(hole);
+        let (mut hit, mut found) = (T::neg_infinity(), None);
+        let mut node = outer;
+
+        // A hole touching the ring at a vertex gets an empty bridge, at a copy of the vertex
+        // whose wedge the hole lies in when the ring passes the point more than once.
+        let mut touching = None;
+        loop {
+            if self.point(node) == target {
+                if self.locally_inside(node, self.next(hole)) {
+                    return Some(node);
+                }
+                touching = touching.or(Some(node));
+            }
+
+            node = self.next(node);
+            if node == outer {
+                break;
+            }
+        }
+        if touching.is_some() {
+            return touching;
+        }
+
+        loop {
+            let (point, next) = (self.point(node), self.point(self.next(node)));
+
+            if target.y <= point.y && target.y >= next.y && next.y != point.y {
+                let x = point.x + (target.y - point.y) * (next.x - point.x) / (next.y - point.y);
+                if x <= target.x && x > hit {
+                    hit = x;
+                    let closest = if point.x < next.x { node } else { self.next(node) };
+                    if x == target.x {
+                        // The hole touches the edge, the endpoint is visible.
+                        return Some(closest);
+                    }
+                    found = Some(closest);
+                }
+            }
+
+            node = self.next(node);
+            if node == outer {
+                break;
+            }
+        }
+
+        let mut best = found?;
+        let (stop, corner) = (best, self.point(best));
+        let (left, right) = (Point2D::new(hit, target.y), target);
+        let (first, last) = if target.y < corner.y { (right, left) } else { (left, right) };
+        let mut tan_min = T::infinity();
+
+        node = best;
+        loop {
+            let point = self.point(node);
+
+            if target.x >= point.x && point.x >= corner.x && target.x != point.x && in_triangle(first, corner, last, point) {
+                let tan = (target.y - point.y).abs() / (target.x - point.x);
+                let best_x = self.point(best).x;
+
+                if self.locally_inside(node, hole)
+                    && (tan < tan_min
+                        || (tan == tan_min
+                            && (point.x > best_x || (point.x == best_x && self.sector_contains_sector(best, node)))))
+                {
+                    best = node;
+                    tan_min = tan;
+                }
+            }
+
+            node = self.next(node);
+            if node == stop {
+                break;
+            }
+        }
+        Some(best)
+    }
+
+    /// Whether the wedge at `m` contains the wedge at `p`, both at the same point.
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        orient(self.point(self.prev(m)), self.point(m), self.point(self.prev(p))) > T::zero()
+            && orient(self.point(self.next(p)), self.point(m), self.point(self.next(m))) > T::zero()
+    }
+
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let (pa, pb) = (self.point(a), self.point(b));
+        let (a_prev, a_next, b_prev, b_next) = (self.prev(a), self.next(a), self.prev(b), self.next(b));
+
+        if self.nodes[a_next].vertex == self.nodes[b].vertex
+            || self.nodes[a_prev].vertex == self.nodes[b].vertex
+            || self.intersects_ring(a, b)
+        {
+            return false;
+        }
+
+        let visible = self.locally_inside(a, b)
+            && self.locally_inside(b, a)
+            && self.middle_inside(a, b)
+            && (orient(self.point(a_prev), pa, self.point(b_prev)) != T::zero()
+                || orient(pa, self.point(b_prev), pb) != T::zero());
+        let zero_length = pa == pb
+            && orient(self.point(a_prev), pa, self.point(a_next)) < T::zero()
+            && orient(self.point(b_prev), pb, self.point(b_next)) < T::zero();
+
+        visible || zero_length
+    }
+
+    /// Whether the segment crosses an edge of the ring not sharing its vertices.
+    fn intersects_ring(&self, a: usize, b: usize) -> bool {
+        let (vertex_a, vertex_b) = (self.nodes[a].vertex, self.nodes[b].vertex);
+        let mut node = a;
+
+        loop {
+            let next = self.next(node);
+            let (vertex, next_vertex) = (self.nodes[node].vertex, self.nodes[next].vertex);
+
+            if vertex != vertex_a
+                && next_vertex != vertex_a
+                && vertex != vertex_b
+                && next_vertex != vertex_b
+                && intersects(self.point(node), self.point(next), self.point(a), self.point(b))
+            {
+                return true;
+            }
+
+            node = next;
+            if node == a {
+                return false;
+            }
+        }
+    }
+
+    /// Whether the segment from `a` to `b` starts off inside the ring.
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (pa, pb) = (self.point(a), self.point(b));
+        let (prev, next) = (self.point(self.prev(a)), self.point(self.next(a)));
+
+        if orient(prev, pa, next) > T::zero() {
+            orient(pa, pb, next) <= T::zero() && orient(pa, prev, pb) <= T::zero()
+        } else {
+            orient(pa, pb, prev) > T::zero() || orient(pa, next, pb) > T::zero()
+        }
+    }
+
+    /// Whether the middle of the segment lies inside the ring, by the even-odd rule.
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let two = T::one() + T::one();
+        let middle = Point2D::new(
+            (self.point(a).x + self.point(b).x) / two,
+            (self.point(a).y + self.point(b).y) / two,
+        );
+        let (mut inside, mut node) = (false, a);
+
+        loop {
+            let (point, next) = (self.point(node), self.point(self.next(node)));
+
+            if (point.y > middle.y) != (next.y > middle.y)
+                && next.y != point.y
+                && middle.x < (next.x - point.x) * (middle.y - point.y) / (next.y - point.y) + point.x
+            {
+                inside = !inside;
+            }
+
+            node = self.next(node);
+            if node == a {
+                return inside;
+            }
+        }
+    }
+
+    /// Connects `a` and `b` by a diagonal, splitting the ring in two. Both get copied so that
+    /// `a` and `b` stay in one ring and their copies form the other, the copy of `b` is
+    /// returned.
+    fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+        let (a_next, b_prev) = (self.next(a), self.prev(b));
+        let a2 = self.nodes.len();
+        let b2 = a2 + 1;
+
+        self.nodes.push(Node {
+            next: a_next,
+            prev: b2,
+            ..self.nodes[a]
+        });
+        self.nodes.push(Node {
+            next: a2,
+            prev: b_prev,
+            ..self.nodes[b]
+        });
+
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[a_next].prev = a2;
+        self.nodes[b_prev].next = b2;
+        b2
+    }
+}
+
+/// Whether the point lies in the counter-clockwise triangle, boundary included.
+fn in_triangle<T: Float>(a: Point2D<T>, b: Point2D<T>, c: Point2D<T>, point: Point2D<T>) -> bool {
+    orient(a, b, point) >= T::zero() && orient(b, c, point) >= T::zero() && orient(c, a, point) >= T::zero()
+}
+
+/// Whether the segments share a point, touching ones included.
+fn intersects<T: Float>(p1: Point2D<T>, q1: Point2D<T>, p2: Point2D<T>, q2: Point2D<T>) -> bool {
+    let sign = |value: T| value.partial_cmp(&T::zero()).unwrap_or(Ordering::Equal);
+    let (o1, o2) = (sign(orient(p1, q1, p2)), sign(orient(p1, q1, q2)));
+    let (o3, o4) = (sign(orient(p2, q2, p1)), sign(orient(p2, q2, q1)));
+
+    (o1 != o2 && o3 != o4)
+        || (o1 == Ordering::Equal && on_segment(p1, p2, q1))
+        || (o2 == Ordering::Equal && on_segment(p1, q2, q1))
+        || (o3 == Ordering::Equal && on_segment(p2, p1, q2))
+        || (o4 == Ordering::Equal && on_segment(p2, q1, q2))
+}
+
+/// Whether `q`, collinear with the segment from `p` to `r`, lies within its bounds.
+fn on_segment<T: Float>(p: Point2D<T>, q: Point2D<T>, r: Point2D<T>) -> bool {
+    q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+}
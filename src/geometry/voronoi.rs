@@ -0,0 +1,73 @@
+use geometry::delaunay::Delaunay;
+use point::Point2D;
+use rect::Rect;
+
+/// Voronoi diagram of sites clipped to a rect, the dual of their Delaunay triangulation.
+///
+/// The cell of a site is the part of the rect closer to it than to any other site, the rect
+/// cut by the bisectors between the site and its Delaunay neighbours. Duplicate sites share
+/// the cell of their first occurrence.
+#[derive(Clone, Debug)]
+pub struct Voronoi {
+    cells: Vec<Vec<Point2D<f64>>>,
+}
+
+impl Voronoi {
+    pub fn new(sites: &[Point2D<f64>], bounds: &Rect<f64>) -> Voronoi {
+        let delaunay = Delaunay::new(sites);
+        let (min, max) = (bounds.min(), bounds.max());
+        let corners = vec![min, Point2D::new(max.x, min.y), max, Point2D::new(min.x, max.y)];
+
+        let cells = sites
+            .iter()
+            .enumerate()
+            .map(|(site, &point)| {
+                delaunay
+                    .neighbours(site)
+                    .into_iter()
+                    .fold(corners.clone(), |cell, neighbour| clip(&cell, point, sites[neighbour]))
+            })
+            .collect();
+
+        Voronoi { cells: cells }
+    }
+
+    /// Cell of the site, a counter-clockwise convex polygon, empty when the site's cell
+    /// misses the rect.
+    pub fn cell(&self, site: usize) -> &[Point2D<f64>] {
+        &self.cells[site]
+    }
+
+    pub fn cells(&self) -> &[Vec<Point2D<f64>>] {
+        &self.cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+/// Keeps the part of the convex polygon closer to `site` than to `other`.
+fn clip(polygon: &[Point2D<f64>], site: Point2D<f64>, other: Point2D<f64>) -> Vec<Point2D<f64>> {
+    let middle = site.lerp(&other, 0.5);
+    let normal = other - site;
+    let distance = |point: Point2D<f64>| (point - middle).dot(normal);
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &point) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let (d, d_next) = (distance(point), distance(next));
+
+        if d <= 0.0 {
+            clipped.push(point);
+        }
+        if (d < 0.0 && d_next > 0.0) || (d > 0.0 && d_next < 0.0) {
+            clipped.push(point.lerp(&next, d / (d - d_next)));
+        }
+    }
+    clipped
+}
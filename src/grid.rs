@@ -0,0 +1,68 @@
+use core::fmt::{Debug, Display, Formatter, Result, Write};
+
+/// Formats a single matrix element, honouring the requested precision.
+pub type Cell<T> = fn(&T, Option<usize>, &mut dyn Write) -> Result;
+
+/// Formats a matrix element for `Display`, honouring the requested precision.
+pub fn display_cell<T: Display>(value: &T, precision: Option<usize>, output: &mut dyn Write) -> Result {
+    match precision {
+        Some(precision) => write!(output, "{:.*}", precision, value),
+        None => write!(output, "{}", value),
+    }
+}
+
+/// Formats a matrix element for `Debug`, honouring the requested precision.
+pub fn debug_cell<T: Debug>(value: &T, precision: Option<usize>, output: &mut dyn Write) -> Result {
+    match precision {
+        Some(precision) => write!(output, "{:.*?}", precision, value),
+        None => write!(output, "{:?}", value),
+    }
+}
+
+/// Counts the characters written to it, measures cells without buffering them.
+struct Width(usize);
+
+impl Write for Width {
+    fn write_str(&mut self, text: &str) -> Result {
+        self.0 += text.chars().count();
+        Ok(())
+    }
+}
+
+/// Formatting errors surface once the cell is written for real.
+fn width<T>(value: &T, cell: Cell<T>, precision: Option<usize>) -> usize {
+    let mut width = Width(0);
+    let _ = cell(value, precision, &mut width);
+    width.0
+}
+
+/// Writes the rows one per line, every column right-aligned to its widest element.
+pub fn write_grid<T, R: AsRef<[T]>>(formatter: &mut Formatter, rows: &[R], cell: Cell<T>) -> Result {
+    let precision = formatter.precision();
+
+    for (index, row) in rows.iter().enumerate() {
+        if index > 0 {
+            writeln!(formatter)?;
+        }
+
+        write!(formatter, "[")?;
+        for (column, value) in row.as_ref().iter().enumerate() {
+            if column > 0 {
+                write!(formatter, ", ")?;
+            }
+
+            let mut column_width = 0;
+            for other in rows {
+                column_width = column_width.max(width(&other.as_ref()[column], cell, precision));
+            }
+
+            for _ in width(value, cell, precision)..column_width {
+                formatter.write_char(' ')?;
+            }
+            cell(value, precision, formatter)?;
+        }
+        write!(formatter, "]")?;
+    }
+
+    Ok(())
+}
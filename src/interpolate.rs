@@ -0,0 +1,223 @@
+use length::Length;
+use matrix2d::Matrix2D;
+use matrix4d::Matrix4D;
+use num::{One, Zero};
+use point::{Point2D, Point3D, Point4D};
+use scale_factor::ScaleFactor;
+use size::Size2D;
+
+use num_lib::Float;
+use core::ops::{Add, Mul, Sub};
+
+/// Blends two values, `t` of zero gives `self` and `t` of one gives `other`.
+///
+/// `F` is the type of the progress value, the component type for the geometry types.
+/// Values of `t` outside `[0, 1]` extrapolate.
+pub trait Interpolate<F> {
+    fn lerp(&self, other: &Self, t: F) -> Self;
+
+    /// Blends with the progress mapped through an easing curve first.
+    fn ease<E: Fn(F) -> F>(&self, other: &Self, t: F, curve: E) -> Self
+        where Self: Sized
+    {
+        self.lerp(other, curve(t))
+    }
+}
+
+/// Blends floats, so that point components of any `Float` type interpolate.
+impl<F: Float> Interpolate<F> for F {
+    #[inline]
+    fn lerp(&self, other: &F, t: F) -> F {
+        *self + (*other - *self) * t
+    }
+}
+
+impl<U, F, T: Interpolate<F>> Interpolate<F> for Length<U, T> {
+    #[inline]
+    fn lerp(&self, other: &Length<U, T>, t: F) -> Length<U, T> {
+        Length::new(self.0.lerp(&other.0, t))
+    }
+}
+
+impl<S, D, F, T: Interpolate<F>> Interpolate<F> for ScaleFactor<S, D, T> {
+    #[inline]
+    fn lerp(&self, other: &ScaleFactor<S, D, T>, t: F) -> ScaleFactor<S, D, T> {
+        ScaleFactor::new(self.0.lerp(&other.0, t))
+    }
+}
+
+impl<F: Copy, T: Interpolate<F>> Interpolate<F> for Point2D<T> {
+    #[inline]
+    fn lerp(&self, other: &Point2D<T>, t: F) -> Point2D<T> {
+        Point2D::new(self.x.lerp(&other.x, t), self.y.lerp(&other.y, t))
+    }
+}
+
+impl<F: Copy, T: Interpolate<F>> Interpolate<F> for Point3D<T> {
+    #[rustfmt::skip]
+    fn lerp(&self, other: &Point3D<T>, t: F) -> Point3D<T> {
+        Point3D::new(
+            self.x.lerp(&other.x, t),
+            self.y.lerp(&other.y, t),
+            self.z.lerp(&other.z, t)
+        )
+    }
+}
+
+impl<F: Copy, T: Interpolate<F>> Interpolate<F> for Point4D<T> {
+    #[rustfmt::skip]
+    fn lerp(&self, other: &Point4D<T>, t: F) -> Point4D<T> {
+        Point4D::new(
+            self.x.lerp(&other.x, t),
+            self.y.lerp(&other.y, t),
+            self.z.lerp(&other.z, t),
+            self.t.lerp(&other.t, t)
+        )
+    }
+}
+
+impl<F: Copy, T: Clone + Interpolate<F>> Interpolate<F> for Size2D<T> {
+    #[inline]
+    fn lerp(&self, other: &Size2D<T>, t: F) -> Size2D<T> {
+        Size2D::new(self.width.lerp(&other.width, t), self.height.lerp(&other.height, t))
+    }
+}
+
+/// Blends matrices element-wise, which suits translation and scale animations.
+#[rustfmt::skip]
+impl<F, T> Interpolate<F> for Matrix2D<T>
+    where F: Copy,
+          T: Copy + PartialOrd + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + One + Zero + Interpolate<F>
+{
+    fn lerp(&self, other: &Matrix2D<T>, t: F) -> Matrix2D<T> {
+        let (a, b) = (self.to_array(), other.to_array());
+
+        Matrix2D::new(
+            a[0].lerp(&b[0], t), a[1].lerp(&b[1], t),
+            a[2].lerp(&b[2], t), a[3].lerp(&b[3], t),
+            a[4].lerp(&b[4], t), a[5].lerp(&b[5], t)
+        )
+    }
+}
+
+/// Blends matrices element-wise, which suits translation and scale animations.
+impl Interpolate<f32> for Matrix4D {
+    #[rustfmt::skip]
+    fn lerp(&self, other: &Matrix4D, t: f32) -> Matrix4D {
+        let (a, b) = (self.as_array(), other.as_array());
+
+        Matrix4D::new(
+            a[0].lerp(&b[0], t), a[1].lerp(&b[1], t), a[2].lerp(&b[2], t), a[3].lerp(&b[3], t),
+            a[4].lerp(&b[4], t), a[5].lerp(&b[5], t), a[6].lerp(&b[6], t), a[7].lerp(&b[7], t),
+            a[8].lerp(&b[8], t), a[9].lerp(&b[9], t), a[10].lerp(&b[10], t), a[11].lerp(&b[11], t),
+            a[12].lerp(&b[12], t), a[13].lerp(&b[13], t), a[14].lerp(&b[14], t), a[15].lerp(&b[15], t)
+        )
+    }
+}
+
+// @section:begin Easing curves, they map linear progress in [0, 1] onto [0, 1].
+
+#[inline]
+pub fn linear<F: Float>(t: F) -> F {
+    t
+}
+
+/// Quadratic curve, starts slow.
+#[inline]
+pub fn ease_in<F: Float>(t: F) -> F {
+    t * t
+}
+
+/// Quadratic curve, ends slow.
+#[inline]
+pub fn ease_out<F: Float>(t: F) -> F {
+    let _2: F = F::one() + F::one();
+    t * (_2 - t)
+}
+
+/// Smoothstep curve, starts and ends slow.
+#[inline]
+pub fn ease_in_out<F: Float>(t: F) -> F {
+    let _2: F = F::one() + F::one();
+    let _3: F = _2 + F::one();
+    t * t * (_3 - _2 * t)
+}
+
+/// CSS-like `cubic-bezier(x1, y1, x2, y2)` timing curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier<F> {
+    pub x1: F,
+    pub y1: F,
+    pub x2: F,
+    pub y2: F,
+}
+
+impl<F: Float> CubicBezier<F> {
+    pub fn new(x1: F, y1: F, x2: F, y2: F) -> CubicBezier<F> {
+        CubicBezier {
+            x1: x1,
+            y1: y1,
+            x2: x2,
+            y2: y2,
+        }
+    }
+
+    /// Samples a coordinate of the curve with control values `a` and `b` at parameter `s`.
+    #[inline]
+    fn sample(a: F, b: F, s: F) -> F {
+        let _3: F = F::one() + F::one() + F::one();
+        let r = F::one() - s;
+        _3 * r * r * s * a + _3 * r * s * s * b + s * s * s
+    }
+
+    #[inline]
+    fn slope(a: F, b: F, s: F) -> F {
+        let _3: F = F::one() + F::one() + F::one();
+        let _6: F = _3 + _3;
+        let r = F::one() - s;
+        _3 * r * r * a + _6 * r * s * (b - a) + _3 * s * s * (F::one() - b)
+    }
+
+    /// Maps progress through the curve, solving for `x` with Newton steps kept on the
+    /// curve and falling back to bisection where the slope is too flat.
+    pub fn ease(&self, t: F) -> F {
+        let t = t.max(F::zero()).min(F::one());
+        let epsilon: F = F::epsilon().sqrt();
+        let mut s = t;
+
+        for _ in 0..8 {
+            let error = CubicBezier::sample(self.x1, self.x2, s) - t;
+            if error.abs() < epsilon {
+                return CubicBezier::sample(self.y1, self.y2, s);
+            }
+
+            let slope = CubicBezier::slope(self.x1, self.x2, s);
+            if slope.abs() < epsilon {
+                break;
+            }
+            // Steep control points overshoot, and outside [0, 1] the cubic leaves the curve.
+            s = (s - error / slope).max(F::zero()).min(F::one());
+        }
+
+        let (mut low, mut high) = (F::zero(), F::one());
+        s = t;
+
+        for _ in 0..64 {
+            let x = CubicBezier::sample(self.x1, self.x2, s);
+            if (x - t).abs() < epsilon {
+                break;
+            }
+
+            if x < t {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / (F::one() + F::one());
+        }
+
+        CubicBezier::sample(self.y1, self.y2, s)
+    }
+}
+
+// @section:end
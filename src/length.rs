@@ -1,39 +1,44 @@
-#[cfg(feature = "plugins")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use num::Zero;
+use num::{Round, Rounding, Zero};
 use scale_factor::ScaleFactor;
 
 use num_lib::NumCast;
+use num_lib::{CheckedAdd, CheckedMul, CheckedSub, SaturatingAdd, SaturatingMul, SaturatingSub};
+use num_lib::{WrappingAdd, WrappingMul, WrappingSub};
 
-use std::cmp::Ordering;
-use std::marker::PhantomData;
-use std::ops::{Add, Sub, Mul, Div, Neg};
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg};
 
-#[derive(Copy, RustcDecodable, RustcEncodable, Debug)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf))]
+#[derive(Copy, Debug)]
+#[repr(transparent)]
 pub struct Length<U, T>(pub T, PhantomData<U>);
 
-#[cfg(feature = "plugins")]
-impl<U, T> Deserialize for Length<U, T> where T: Deserialize
+/// Deserializes length from its bare scalar value.
+#[cfg(feature = "serde")]
+impl<'de, U, T> Deserialize<'de> for Length<U, T> where T: Deserialize<'de>
 {
-    #[rustfmt_skip]
-    fn deserialize<D>(deserializer: &mut D) -> Result<Length<U, T>, D::Error> where D: Deserilizer {
-        Ok(Length(try!(Deserialize::deserialize(deserializer)), PhantomData))
+    #[rustfmt::skip]
+    fn deserialize<D>(deserializer: D) -> Result<Length<U, T>, D::Error> where D: Deserializer<'de> {
+        Ok(Length(Deserialize::deserialize(deserializer)?, PhantomData))
     }
 }
 
-#[cfg(feature = "plugins")]
+/// Serializes length as its bare scalar value, the unit is compile-time only.
+#[cfg(feature = "serde")]
 impl<U, T> Serialize for Length<U, T> where T: Serialize
 {
-    #[rustfmt_skip]
-    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: Serializer {
-        self.0.serilize(serializer)
+    #[rustfmt::skip]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        self.0.serialize(serializer)
     }
 }
+
 /// Creates new instance an Length implementation.
 impl<U, T> Length<U, T> {
-    pub fn new(x: T) -> Length<U, T> {
+    pub const fn new(x: T) -> Length<U, T> {
         Length(x, PhantomData)
     }
 }
@@ -55,11 +60,12 @@ impl<U, T: Clone + Add<T, Output = T>> Add for Length<U, T> {
 }
 
 /// Overloads "+=" increment assignment operator.
-// impl<U, T: Clone + AddAssign<T>> AddAssign for Length<U, T> {
-//     fn add_assign(&mut self, other: Length<U, T>) {
-//         self.0 += other.get();
-//     }
-// }
+impl<U, T: AddAssign<T>> AddAssign for Length<U, T> {
+    fn add_assign(&mut self, other: Length<U, T>) {
+        self.0 += other.0;
+    }
+}
+
 /// Overloads "-" subtract operator.
 impl<U, T: Clone + Sub<T, Output = T>> Sub<Length<U, T>> for Length<U, T> {
     type Output = Length<U, T>;
@@ -70,11 +76,26 @@ impl<U, T: Clone + Sub<T, Output = T>> Sub<Length<U, T>> for Length<U, T> {
 }
 
 /// Overloads "-=" decrement assignment operator.
-// impl<U, T: Clone + SubAssign<T>> SubAssign for Length<U, T> {
-//     fn sub_assign(&mut self, other: Length<U, T>) {
-//         self.0 -= other.get()
-//     }
-// }
+impl<U, T: SubAssign<T>> SubAssign for Length<U, T> {
+    fn sub_assign(&mut self, other: Length<U, T>) {
+        self.0 -= other.0;
+    }
+}
+
+/// Overloads "*=" operator for scaling length by scalar.
+impl<U, T: MulAssign<T>> MulAssign<T> for Length<U, T> {
+    fn mul_assign(&mut self, scale: T) {
+        self.0 *= scale;
+    }
+}
+
+/// Overloads "/=" operator for descaling length by scalar.
+impl<U, T: DivAssign<T>> DivAssign<T> for Length<U, T> {
+    fn div_assign(&mut self, scale: T) {
+        self.0 /= scale;
+    }
+}
+
 /// Overloads "/" divide operator.
 impl<S, D, T: Clone + Div<T, Output = T>> Div<Length<S, T>> for Length<D, T> {
     type Output = ScaleFactor<S, D, T>;
@@ -114,6 +135,64 @@ impl<U, T: Clone + Neg<Output = T>> Neg for Length<U, T> {
     }
 }
 
+/// Implements overflow-checked arithmetic, so typed points and sizes get it too.
+impl<U, T: Clone + CheckedAdd> CheckedAdd for Length<U, T> {
+    fn checked_add(&self, other: &Length<U, T>) -> Option<Length<U, T>> {
+        self.0.checked_add(&other.0).map(Length::new)
+    }
+}
+
+impl<U, T: Clone + CheckedSub> CheckedSub for Length<U, T> {
+    fn checked_sub(&self, other: &Length<U, T>) -> Option<Length<U, T>> {
+        self.0.checked_sub(&other.0).map(Length::new)
+    }
+}
+
+/// Implements saturating arithmetic.
+impl<U, T: Clone + SaturatingAdd> SaturatingAdd for Length<U, T> {
+    fn saturating_add(&self, other: &Length<U, T>) -> Length<U, T> {
+        Length::new(self.0.saturating_add(&other.0))
+    }
+}
+
+impl<U, T: Clone + SaturatingSub> SaturatingSub for Length<U, T> {
+    fn saturating_sub(&self, other: &Length<U, T>) -> Length<U, T> {
+        Length::new(self.0.saturating_sub(&other.0))
+    }
+}
+
+/// Implements wrapping arithmetic.
+impl<U, T: Clone + WrappingAdd> WrappingAdd for Length<U, T> {
+    fn wrapping_add(&self, other: &Length<U, T>) -> Length<U, T> {
+        Length::new(self.0.wrapping_add(&other.0))
+    }
+}
+
+impl<U, T: Clone + WrappingSub> WrappingSub for Length<U, T> {
+    fn wrapping_sub(&self, other: &Length<U, T>) -> Length<U, T> {
+        Length::new(self.0.wrapping_sub(&other.0))
+    }
+}
+
+/// Scales length by a scalar with overflow handling.
+impl<U, T: CheckedMul> Length<U, T> {
+    pub fn checked_mul(&self, scale: T) -> Option<Length<U, T>> {
+        self.0.checked_mul(&scale).map(Length::new)
+    }
+}
+
+impl<U, T: SaturatingMul> Length<U, T> {
+    pub fn saturating_mul(&self, scale: T) -> Length<U, T> {
+        Length::new(self.0.saturating_mul(&scale))
+    }
+}
+
+impl<U, T: WrappingMul> Length<U, T> {
+    pub fn wrapping_mul(&self, scale: T) -> Length<U, T> {
+        Length::new(self.0.wrapping_mul(&scale))
+    }
+}
+
 /// Implements numeric casting.
 impl<U, S: NumCast + Clone> Length<U, S> {
     pub fn cast<D: NumCast + Clone>(&self) -> Option<Length<U, D>> {
@@ -121,6 +200,31 @@ impl<U, S: NumCast + Clone> Length<U, S> {
     }
 }
 
+/// Implements numeric casting that rounds rather than truncates.
+impl<U, S: NumCast + Clone + Round> Length<U, S> {
+    pub fn cast_round<D: NumCast + Clone>(&self, rounding: Rounding) -> Option<Length<U, D>> {
+        NumCast::from(rounding.apply(self.get())).map(Length::new)
+    }
+}
+
+impl<U, T: Round> Round for Length<U, T> {
+    fn round(&self) -> Length<U, T> {
+        Length::new(self.0.round())
+    }
+
+    fn floor(&self) -> Length<U, T> {
+        Length::new(self.0.floor())
+    }
+
+    fn ceil(&self) -> Length<U, T> {
+        Length::new(self.0.ceil())
+    }
+
+    fn trunc(&self) -> Length<U, T> {
+        Length::new(self.0.trunc())
+    }
+}
+
 /// Implements clone.
 impl<U, T: Clone> Clone for Length<U, T> {
     fn clone(&self) -> Length<U, T> {
@@ -6,34 +6,52 @@
 // ##    ##  ##     ## ##    ##    ##       ##               ##    ##  ##     ## ##       ##       ##       ##    ##
 // ##     ##  #######   ######     ##       ##               ##     ##  #######  ######## ######## ######## ##     ##
 
-#[feature(asm, custom_derive, plugin, repr_simd, test)]
-#[feature(augmented_assignments)]
-#[feature(op_assign_traits)]
-#[cfg_attr(feature = "plugins", plugin(heapsize_plugin))]
-#[cfg_attr(feature = "plugins", plugin(serde_macros))]
-#[cfg(feature = "plugins")]
-#[macro_use]
-extern crate heapsize;
-
-#[macro_use]
+#![allow(clippy::clone_on_copy, clippy::just_underscores_and_digits)]
+#![allow(clippy::needless_return, clippy::redundant_field_names, clippy::too_many_arguments)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// The float functions come from `std`, or from `libm` on targets without it.
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("the `std` or the `libm` feature has to be enabled");
+
+// Brings `core` into scope under `std` too, so the modules use the same paths either way.
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(feature = "log")]
 extern crate log;
-extern crate rustc_serialize;
-#[cfg(feature = "plugins")]
+#[cfg(feature = "serde")]
 extern crate serde;
 
 #[cfg(test)]
 extern crate rand;
-extern crate num as num_lib;
+extern crate num_traits as num_lib;
 
 // public exports
 
 // public modules
 pub mod scale_factor;
+pub mod app_unit;
 pub mod approxeq;
+pub mod box3d;
+#[cfg(feature = "std")]
+pub mod conversion;
+#[cfg(feature = "std")]
+pub mod geometry;
+pub mod interpolate;
 pub mod length;
 pub mod matrix2d;
 pub mod matrix4d;
 
 pub mod point;
+pub mod rect;
 pub mod size;
+#[cfg(feature = "std")]
+pub mod spatial;
+#[cfg(feature = "std")]
+pub mod transform;
+pub mod units;
 pub mod num;
+pub mod pod;
+
+// private modules
+mod grid;
@@ -1,3 +1,39 @@
+#![allow(clippy::redundant_field_names)]
+
+extern crate matrix;
+
+mod cli;
+
+use std::env;
+use std::process;
+
+const USAGE: &str = "\
+Usage: matrix <COMMAND> [ARGUMENTS]...
+
+Commands:
+    apply       Transform the points of a file, row by row
+    compose     Compose a transform list, print or apply the matrix
+    convert     Convert lengths between physical, CSS and device units
+
+Run 'matrix <COMMAND> --help' for the options of a command.
+";
+
 fn main() {
-    println!("Hello, world!");
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    let result = match arguments.first().map(String::as_str) {
+        Some("apply") => cli::apply::run(&arguments[1..]),
+        Some("compose") => cli::compose::run(&arguments[1..]),
+        Some("convert") => cli::convert::run(&arguments[1..]),
+        Some("-h") | Some("--help") | None => {
+            print!("{}", USAGE);
+            return;
+        }
+        Some(command) => Err(cli::Error::Usage(format!("unknown command '{}'", command))),
+    };
+
+    if let Err(error) = result {
+        eprintln!("matrix: {}", error);
+        process::exit(error.code());
+    }
 }
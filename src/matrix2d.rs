@@ -1,9 +1,20 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use grid;
 use num::{One, Zero};
 use point::Point2D;
-use std::ops::{Add, Mul, Sub};
+use core::array;
+use core::fmt::{Debug, Display, Formatter, Result};
+use core::ops::{Add, Index, Mul, MulAssign, Sub};
 
-#[derive(Clone, Copy)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf, Deserialize, Serialize))]
+/// Affine 2D transform as a 3x2 matrix, with an implicit third column of `(0, 0, 1)`.
+///
+/// Elements are named `mRC` by row and column. Points are row vectors multiplied from
+/// the left, `p' = p * M`, so the translation sits in the last row. Arrays taken and
+/// returned by the matrix are row-major unless the method name says otherwise.
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Matrix2D<T> {
     m11: T,
     m12: T,
@@ -13,7 +24,7 @@ pub struct Matrix2D<T> {
     m32: T,
 }
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
+#[rustfmt::skip]
 impl <T: Copy + Copy + PartialOrd +
          Add<T, Output=T> + Sub<T, Output=T> + Mul<T, Output=T> +
          One + Zero> Matrix2D<T> {
@@ -47,11 +58,12 @@ impl <T: Copy + Copy + PartialOrd +
         return self.mul(&matrix);
     }
 
+    /// Scales before applying this transform, like `translate`.
     pub fn scale(&self, x: T, y: T) -> Matrix2D<T> {
         Matrix2D::new (
             self.m11 * x,
-            self.m12.clone(),
-            self.m21.clone(),
+            self.m12 * x,
+            self.m21 * y,
             self.m22 * y,
             self.m31.clone(),
             self.m32.clone(),
@@ -71,6 +83,7 @@ impl <T: Copy + Copy + PartialOrd +
         );
     }
 
+    /// Same as `to_row_major`.
     pub fn to_array(&self) -> [T; 6] {
         [
             self.m11.clone(),
@@ -85,7 +98,115 @@ impl <T: Copy + Copy + PartialOrd +
     pub fn transform_point(&self, point : &Point2D<T>) -> Point2D<T> {
         Point2D::new(
             point.x * self.m11 + point.y * self.m21 + self.m31,
-            point.x + self.m12 + point.y * self.m22 + self.m32
+            point.x * self.m12 + point.y * self.m22 + self.m32
         )
     }
 }
+
+impl<T> Matrix2D<T> {
+    /// Creates the matrix from elements in row-major order, the inverse of `to_row_major`.
+    #[rustfmt::skip]
+    pub fn from_array(array: [T; 6]) -> Matrix2D<T> {
+        let [m11, m12, m21, m22, m31, m32] = array;
+
+        Matrix2D { m11: m11, m12: m12, m21: m21, m22: m22, m31: m31, m32: m32 }
+    }
+
+    /// Creates the matrix from its three rows, the last one holding the translation.
+    #[rustfmt::skip]
+    pub fn from_row_arrays(rows: [[T; 2]; 3]) -> Matrix2D<T> {
+        let [[m11, m12], [m21, m22], [m31, m32]] = rows;
+
+        Matrix2D { m11: m11, m12: m12, m21: m21, m22: m22, m31: m31, m32: m32 }
+    }
+
+    /// Creates the matrix from its two columns.
+    #[rustfmt::skip]
+    pub fn from_column_arrays(columns: [[T; 3]; 2]) -> Matrix2D<T> {
+        let [[m11, m21, m31], [m12, m22, m32]] = columns;
+
+        Matrix2D { m11: m11, m12: m12, m21: m21, m22: m22, m31: m31, m32: m32 }
+    }
+}
+
+impl<T: Copy> Matrix2D<T> {
+    #[rustfmt::skip]
+    pub fn to_row_major(&self) -> [T; 6] {
+        [
+            self.m11, self.m12,
+            self.m21, self.m22,
+            self.m31, self.m32,
+        ]
+    }
+
+    #[rustfmt::skip]
+    pub fn to_column_major(&self) -> [T; 6] {
+        [
+            self.m11, self.m21, self.m31,
+            self.m12, self.m22, self.m32,
+        ]
+    }
+
+    pub fn to_row_arrays(&self) -> [[T; 2]; 3] {
+        [[self.m11, self.m12], [self.m21, self.m22], [self.m31, self.m32]]
+    }
+
+    pub fn to_column_arrays(&self) -> [[T; 3]; 2] {
+        [[self.m11, self.m21, self.m31], [self.m12, self.m22, self.m32]]
+    }
+
+    /// Iterates over the three rows, from top to bottom.
+    pub fn rows(&self) -> array::IntoIter<[T; 2], 3> {
+        IntoIterator::into_iter(self.to_row_arrays())
+    }
+
+    /// Iterates over the two columns, from left to right.
+    pub fn columns(&self) -> array::IntoIter<[T; 3], 2> {
+        IntoIterator::into_iter(self.to_column_arrays())
+    }
+}
+
+/// Gets the element at `(row, column)`, both counted from zero.
+///
+/// Panics when the row is beyond 2 or the column beyond 1.
+impl<T> Index<(usize, usize)> for Matrix2D<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        match (row, column) {
+            (0, 0) => &self.m11,
+            (0, 1) => &self.m12,
+            (1, 0) => &self.m21,
+            (1, 1) => &self.m22,
+            (2, 0) => &self.m31,
+            (2, 1) => &self.m32,
+            _ => panic!("index ({}, {}) out of range for a 3x2 matrix", row, column),
+        }
+    }
+}
+
+/// Lays out the rows in a grid, one per line.
+impl<T: Copy + Debug> Debug for Matrix2D<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        writeln!(formatter, "Matrix2D")?;
+        grid::write_grid(formatter, &self.to_row_arrays(), grid::debug_cell)
+    }
+}
+
+/// Lays out the rows in a grid, one per line.
+impl<T: Copy + Display> Display for Matrix2D<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        grid::write_grid(formatter, &self.to_row_arrays(), grid::display_cell)
+    }
+}
+
+/// Overloads "*=" operator, same as `mul`.
+#[rustfmt::skip]
+impl <T: Copy + PartialOrd +
+         Add<T, Output=T> + Sub<T, Output=T> + Mul<T, Output=T> +
+         One + Zero> MulAssign for Matrix2D<T> {
+    #[inline]
+    fn mul_assign(&mut self, other: Matrix2D<T>) {
+        *self = Matrix2D::mul(self, &other);
+    }
+}
@@ -1,9 +1,29 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use approxeq::ApproxEq;
-use point::{Point2D, Point4D};
+use box3d::Box3D;
+use grid;
+use matrix2d::Matrix2D;
+use num_lib::Float;
+use point::{Point2D, Point3D, Point4D};
+use rect::Rect;
+use core::array;
+use core::fmt::{Debug, Display, Formatter, Result};
+use core::ops::{Index, MulAssign};
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf, Deserialize, Serialize))]
+/// Homogeneous coordinate of the plane shapes are clipped against, just in front of the viewer.
+const W_CLIP: f32 = 1.0e-6;
+
+/// Transform in homogeneous 3D space as a 4x4 matrix.
+///
+/// Elements are named `mRC` by row and column. Points are row vectors multiplied from
+/// the left, `p' = p * M`, so the translation sits in the last row and the projective
+/// terms in the last column. Arrays taken and returned by the matrix are row-major
+/// unless the method name says otherwise.
+#[rustfmt::skip]
+#[derive(Copy, Clone, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Matrix4D {
     m11: f32, m12: f32, m13: f32, m14: f32,
     m21: f32, m22: f32, m23: f32, m24: f32,
@@ -12,7 +32,7 @@ pub struct Matrix4D {
 }
 
 impl Matrix4D {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn new(
             m11: f32, m12: f32, m13: f32, m14: f32,
             m21: f32, m22: f32, m23: f32, m24: f32,
@@ -27,7 +47,7 @@ impl Matrix4D {
         }
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn ortho(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Matrix4D {
         let tx = -((left + right) / (left - right));
         let ty = -((bottom + top) / (bottom - top));
@@ -41,7 +61,7 @@ impl Matrix4D {
         )
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn indentity() -> Matrix4D {
         Matrix4D::new(
             1.0, 0.0, 0.0, 0.0,
@@ -51,7 +71,7 @@ impl Matrix4D {
         )
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn mul(&self, m: &Matrix4D) -> Matrix4D {
         Matrix4D::new(
             m.m11*self.m11 + m.m12*self.m21 + m.m13*self.m31 + m.m14*self.m41,
@@ -73,7 +93,7 @@ impl Matrix4D {
         )
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn approx_eq(&self, other: &Matrix4D) -> bool {
         self.m11.approx_eq(&other.m11) && self.m12.approx_eq(&other.m12) &&
         self.m13.approx_eq(&other.m13) && self.m14.approx_eq(&other.m14) &&
@@ -85,7 +105,7 @@ impl Matrix4D {
         self.m43.approx_eq(&other.m43) && self.m44.approx_eq(&other.m44)
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn spread(&self, x: f32) -> Matrix4D {
         Matrix4D::new(
             self.m11 * x, self.m12 * x, self.m13 * x, self.m14 * x,
@@ -95,18 +115,93 @@ impl Matrix4D {
         )
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    /// Scales before applying this transform, like `translate`.
+    #[rustfmt::skip]
     pub fn scale(&self, x: f32, y: f32, z: f32) -> Matrix4D {
         Matrix4D::new(
-            self.m11 * x, self.m12, self.m13, self.m14,
-            self.m21, self.m22 * y, self.m23, self.m24,
-            self.m31, self.m32, self.m33 * z, self.m34,
+            self.m11 * x, self.m12 * x, self.m13 * x, self.m14 * x,
+            self.m21 * y, self.m22 * y, self.m23 * y, self.m24 * y,
+            self.m31 * z, self.m32 * z, self.m33 * z, self.m34 * z,
             self.m41, self.m42, self.m43, self.m44
         )
     }
 
+    /// Rotates by `angle` in radians around the axis `(x, y, z)` before applying this transform.
+    ///
+    /// The rotation is counter-clockwise when the axis points at the viewer in a y-up space,
+    /// the axis doesn't need unit length, a zero axis leaves the matrix unchanged.
+    pub fn rotate(&self, x: f32, y: f32, z: f32, angle: f32) -> Matrix4D {
+        match rotation_rows(x, y, z, angle) {
+            Some(rows) => self.mul(&Matrix4D::from_row_arrays(rows)),
+            None => *self,
+        }
+    }
+
+    /// Skews by the angles in radians along the x and y axes before applying this transform.
+    #[rustfmt::skip]
+    pub fn skew(&self, x: f32, y: f32) -> Matrix4D {
+        let matrix = Matrix4D::new(
+            1.0, Float::tan(y), 0.0, 0.0,
+            Float::tan(x), 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0
+        );
+
+        self.mul(&matrix)
+    }
+
+    /// Projects with the viewer at `distance` along z before applying this transform.
+    ///
+    /// The distance has to be positive, zero divides by zero and negative flips the scene.
+    #[rustfmt::skip]
+    pub fn perspective(&self, distance: f32) -> Matrix4D {
+        let matrix = Matrix4D::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, -1.0 / distance,
+            0.0, 0.0, 0.0, 1.0
+        );
+
+        self.mul(&matrix)
+    }
+
+    /// Computes the inverse transform, None when the matrix is singular or holds
+    /// non-finite values.
+    ///
+    /// Pivots are compared with the largest entry of their column, so a matrix with
+    /// small but well-conditioned entries, like `scale(1e-4, 1e-4, 1e-4)`, still inverts.
+    pub fn inverse(&self) -> Option<Matrix4D> {
+        invert_rows(self.to_row_arrays()).map(Matrix4D::from_row_arrays)
+    }
+
+    /// Whether the matrix only maps the plane onto itself, so `to_2d` loses nothing.
+    #[rustfmt::skip]
+    pub fn is_2d(&self) -> bool {
+        self.m13 == 0.0 && self.m14 == 0.0 &&
+        self.m23 == 0.0 && self.m24 == 0.0 &&
+        self.m31 == 0.0 && self.m32 == 0.0 && self.m33 == 1.0 && self.m34 == 0.0 &&
+        self.m43 == 0.0 && self.m44 == 1.0
+    }
+
+    /// Keeps the part of the matrix acting on the plane, see `is_2d`.
+    pub fn to_2d(&self) -> Matrix2D<f32> {
+        Matrix2D::from_array([self.m11, self.m12, self.m21, self.m22, self.m41, self.m42])
+    }
+
+    #[rustfmt::skip]
+    pub fn from_2d(matrix: &Matrix2D<f32>) -> Matrix4D {
+        let [m11, m12, m21, m22, m41, m42] = matrix.to_row_major();
+
+        Matrix4D::new(
+            m11, m12, 0.0, 0.0,
+            m21, m22, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            m41, m42, 0.0, 1.0
+        )
+    }
+
     #[inline]
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn transform_point2d(&self, point: &Point2D<f32>) -> Point2D<f32> {
         Point2D::new(
             point.x * self.m11 + point.y * self.m21 + self.m41,
@@ -114,18 +209,109 @@ impl Matrix4D {
         )
     }
 
+    /// Transforms a homogeneous point, treated as a row vector multiplied from the left.
     #[inline]
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn transform_point4d(&self, point: &Point4D<f32>) -> Point4D<f32> {
-        let x = point.x * self.m11 + point.y * self.m12 + point.z * self.m13 + self.m14;
-        let y = point.x * self.m21 + point.y * self.m22 + point.z * self.m23 + self.m24;
-        let z = point.x * self.m31 + point.y * self.m32 + point.z * self.m33 + self.m34;
-        let t = point.x * self.m41 + point.y * self.m42 + point.z * self.m43 + self.m44;
+        let x = point.x * self.m11 + point.y * self.m21 + point.z * self.m31 + point.t * self.m41;
+        let y = point.x * self.m12 + point.y * self.m22 + point.z * self.m32 + point.t * self.m42;
+        let z = point.x * self.m13 + point.y * self.m23 + point.z * self.m33 + point.t * self.m43;
+        let t = point.x * self.m14 + point.y * self.m24 + point.z * self.m34 + point.t * self.m44;
 
         Point4D::new(x, y, z, t)
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    /// Transforms a point and applies the perspective divide.
+    ///
+    /// Returns None when the point ends up at or behind the camera plane, where `t` isn't positive.
+    pub fn transform_point3d(&self, point: &Point3D<f32>) -> Option<Point3D<f32>> {
+        let point = self.transform_point4d(&Point4D::new(point.x, point.y, point.z, 1.0));
+
+        if point.t <= W_CLIP {
+            return None;
+        }
+        point.to_point3d()
+    }
+
+    /// Transforms a rect lying in the `z = 0` plane and returns the bounds of its visible part.
+    ///
+    /// The quad is clipped against the `t = 0` plane in homogeneous space before the
+    /// perspective divide, so corners behind the viewer don't fold back into view.
+    /// Returns None when the rect is entirely behind the viewer.
+    pub fn transform_rect(&self, rect: &Rect<f32>) -> Option<Rect<f32>> {
+        let (min, max) = (rect.min(), rect.max());
+        let corners = [
+            Point4D::new(min.x, min.y, 0.0, 1.0),
+            Point4D::new(max.x, min.y, 0.0, 1.0),
+            Point4D::new(max.x, max.y, 0.0, 1.0),
+            Point4D::new(min.x, max.y, 0.0, 1.0),
+        ];
+
+        let bounds = self.visible_bounds(&corners, &[(0, 1), (1, 2), (2, 3), (3, 0)])?;
+
+        Some(Rect::from_corners(Point2D::new(bounds.min.x, bounds.min.y), Point2D::new(bounds.max.x, bounds.max.y)))
+    }
+
+    /// Transforms a box and returns the bounds of its visible part, see `transform_rect`.
+    pub fn transform_box(&self, bounds: &Box3D<f32>) -> Option<Box3D<f32>> {
+        let (min, max) = (bounds.min, bounds.max);
+        let mut corners = [Point4D::zero(); 8];
+        let mut edges = [(0, 0); 12];
+
+        // Corner `i` takes the maximal coordinate along the axes whose bit is set.
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let x = if i & 1 == 0 { min.x } else { max.x };
+            let y = if i & 2 == 0 { min.y } else { max.y };
+            let z = if i & 4 == 0 { min.z } else { max.z };
+            *corner = Point4D::new(x, y, z, 1.0);
+        }
+
+        // Edges join the corners differing along exactly one axis.
+        let mut count = 0;
+        for i in 0..8 {
+            for bit in &[1, 2, 4] {
+                if i & bit == 0 {
+                    edges[count] = (i, i | bit);
+                    count += 1;
+                }
+            }
+        }
+
+        self.visible_bounds(&corners, &edges)
+    }
+
+    /// Transforms the vertices of a convex shape and returns the bounds of the projected
+    /// vertices of its part in front of the viewer, edges crossing the clipping plane are
+    /// cut at the plane. Takes up to 8 vertices.
+    fn visible_bounds(&self, vertices: &[Point4D<f32>], edges: &[(usize, usize)]) -> Option<Box3D<f32>> {
+        let mut transformed = [Point4D::zero(); 8];
+        for (vertex, source) in transformed.iter_mut().zip(vertices) {
+            *vertex = self.transform_point4d(source);
+        }
+
+        let vertices = &transformed[..vertices.len()];
+        let visible = |vertex: &Point4D<f32>| vertex.t > W_CLIP;
+        let project = |vertex: Point4D<f32>| Point3D::new(vertex.x / vertex.t, vertex.y / vertex.t, vertex.z / vertex.t);
+
+        let corners = vertices.iter().filter(|vertex| visible(vertex)).map(|&vertex| project(vertex));
+        let cuts = edges.iter().filter_map(|&(a, b)| {
+            let (a, b) = (vertices[a], vertices[b]);
+            if visible(&a) == visible(&b) {
+                return None;
+            }
+
+            let s = (W_CLIP - a.t) / (b.t - a.t);
+            let mut cut = a + (b - a) * s;
+
+            cut.t = W_CLIP;
+            Some(project(cut))
+        });
+
+        Box3D::from_points(corners.chain(cuts))
+    }
+
+    /// Same as `to_row_major`.
+    #[rustfmt::skip]
     pub fn to_array(&self) -> [f32; 16] {
         [
             self.m11, self.m12, self.m13, self.m14,
@@ -135,7 +321,13 @@ impl Matrix4D {
         ]
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    /// Views the matrix as an array without copying, the layout is `#[repr(C)]`.
+    #[inline]
+    pub fn as_array(&self) -> &[f32; 16] {
+        unsafe { &*(self as *const Matrix4D as *const [f32; 16]) }
+    }
+
+    #[rustfmt::skip]
     pub fn translate(&self, x: f32, y: f32, z: f32) -> Matrix4D {
         let matrix = Matrix4D::new(
             1.0, 0.0, 0.0, 0.0,
@@ -147,3 +339,196 @@ impl Matrix4D {
         self.mul(&matrix)
     }
 }
+
+impl Matrix4D {
+    /// Creates the matrix from elements in row-major order, the inverse of `to_row_major`.
+    #[rustfmt::skip]
+    pub fn from_array(array: [f32; 16]) -> Matrix4D {
+        Matrix4D::new(
+            array[0], array[1], array[2], array[3],
+            array[4], array[5], array[6], array[7],
+            array[8], array[9], array[10], array[11],
+            array[12], array[13], array[14], array[15]
+        )
+    }
+
+    /// Creates the matrix from its four rows, the last one holding the translation.
+    pub fn from_row_arrays(rows: [[f32; 4]; 4]) -> Matrix4D {
+        let [a, b, c, d] = rows;
+        Matrix4D::from_array([
+            a[0], a[1], a[2], a[3], b[0], b[1], b[2], b[3], c[0], c[1], c[2], c[3], d[0], d[1], d[2], d[3],
+        ])
+    }
+
+    /// Creates the matrix from its four columns.
+    pub fn from_column_arrays(columns: [[f32; 4]; 4]) -> Matrix4D {
+        Matrix4D::from_row_arrays(columns).transpose()
+    }
+
+    #[inline]
+    pub fn to_row_major(&self) -> [f32; 16] {
+        self.to_array()
+    }
+
+    #[inline]
+    pub fn to_column_major(&self) -> [f32; 16] {
+        self.transpose().to_array()
+    }
+
+    #[rustfmt::skip]
+    pub fn to_row_arrays(&self) -> [[f32; 4]; 4] {
+        [
+            [self.m11, self.m12, self.m13, self.m14],
+            [self.m21, self.m22, self.m23, self.m24],
+            [self.m31, self.m32, self.m33, self.m34],
+            [self.m41, self.m42, self.m43, self.m44],
+        ]
+    }
+
+    #[inline]
+    pub fn to_column_arrays(&self) -> [[f32; 4]; 4] {
+        self.transpose().to_row_arrays()
+    }
+
+    /// Mirrors the matrix over its diagonal, which also swaps between row- and column-major arrays.
+    #[rustfmt::skip]
+    pub fn transpose(&self) -> Matrix4D {
+        Matrix4D::new(
+            self.m11, self.m21, self.m31, self.m41,
+            self.m12, self.m22, self.m32, self.m42,
+            self.m13, self.m23, self.m33, self.m43,
+            self.m14, self.m24, self.m34, self.m44
+        )
+    }
+
+    /// Iterates over the four rows, from top to bottom.
+    pub fn rows(&self) -> array::IntoIter<[f32; 4], 4> {
+        IntoIterator::into_iter(self.to_row_arrays())
+    }
+
+    /// Iterates over the four columns, from left to right.
+    pub fn columns(&self) -> array::IntoIter<[f32; 4], 4> {
+        IntoIterator::into_iter(self.to_column_arrays())
+    }
+}
+
+/// Gets the element at `(row, column)`, both counted from zero.
+///
+/// Panics when either is beyond 3.
+impl Index<(usize, usize)> for Matrix4D {
+    type Output = f32;
+
+    fn index(&self, (row, column): (usize, usize)) -> &f32 {
+        assert!(row < 4 && column < 4, "index ({}, {}) out of range for a 4x4 matrix", row, column);
+        &self.as_array()[row * 4 + column]
+    }
+}
+
+/// Lays out the rows in a grid, one per line.
+impl Debug for Matrix4D {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        writeln!(formatter, "Matrix4D")?;
+        grid::write_grid(formatter, &self.to_row_arrays(), grid::debug_cell)
+    }
+}
+
+/// Lays out the rows in a grid, one per line.
+impl Display for Matrix4D {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        grid::write_grid(formatter, &self.to_row_arrays(), grid::display_cell)
+    }
+}
+
+/// Overloads "*=" operator, same as `mul`.
+impl MulAssign for Matrix4D {
+    #[inline]
+    fn mul_assign(&mut self, other: Matrix4D) {
+        *self = Matrix4D::mul(self, &other);
+    }
+}
+
+/// Rows of the rotation by `angle` in radians around the axis `(x, y, z)`, in whichever float
+/// precision the caller needs. `Matrix4D::rotate` and the transform lists build on it.
+///
+/// Returns None when the axis has zero length.
+#[rustfmt::skip]
+pub fn rotation_rows<F: Float>(x: F, y: F, z: F, angle: F) -> Option<[[F; 4]; 4]> {
+    let length = (x * x + y * y + z * z).sqrt();
+    if length == F::zero() {
+        return None;
+    }
+
+    let (x, y, z) = (x / length, y / length, z / length);
+    let (sin, cos) = angle.sin_cos();
+    let rest = F::one() - cos;
+    let (zero, one) = (F::zero(), F::one());
+
+    Some([
+        [cos + x * x * rest, x * y * rest + z * sin, x * z * rest - y * sin, zero],
+        [x * y * rest - z * sin, cos + y * y * rest, y * z * rest + x * sin, zero],
+        [x * z * rest + y * sin, y * z * rest - x * sin, cos + z * z * rest, zero],
+        [zero, zero, zero, one],
+    ])
+}
+
+/// Inverts a matrix given as its rows by Gauss-Jordan elimination with partial pivoting,
+/// in whichever float precision the caller needs. `Matrix4D::inverse` runs on it.
+///
+/// Returns None when the matrix is singular or holds non-finite values, with pivots compared
+/// to the largest entry of their column.
+pub fn invert_rows<F: Float>(rows: [[F; 4]; 4]) -> Option<[[F; 4]; 4]> {
+    let mut left = rows;
+    let mut right = [[F::zero(); 4]; 4];
+    for (index, row) in right.iter_mut().enumerate() {
+        row[index] = F::one();
+    }
+
+    if left.iter().flat_map(|row| row.iter()).any(|value| !value.is_finite()) {
+        return None;
+    }
+
+    let mut magnitudes = [F::zero(); 4];
+    for row in &left {
+        for (magnitude, value) in magnitudes.iter_mut().zip(row) {
+            *magnitude = Float::max(*magnitude, Float::abs(*value));
+        }
+    }
+
+    // Gauss-Jordan elimination with partial pivoting.
+    for column in 0..4 {
+        let pivot = (column..4).fold(column, |best, row| {
+            if Float::abs(left[row][column]) > Float::abs(left[best][column]) {
+                row
+            } else {
+                best
+            }
+        });
+
+        // Also rejects NaN, which elimination can produce from huge finite entries.
+        let magnitude = Float::abs(left[pivot][column]);
+        if magnitude.is_nan() || magnitude <= F::epsilon() * magnitudes[column] {
+            return None;
+        }
+        left.swap(column, pivot);
+        right.swap(column, pivot);
+
+        let scale = F::one() / left[column][column];
+        for index in 0..4 {
+            left[column][index] = left[column][index] * scale;
+            right[column][index] = right[column][index] * scale;
+        }
+
+        for row in (0..4).filter(|&row| row != column) {
+            let factor = left[row][column];
+            for index in 0..4 {
+                left[row][index] = left[row][index] - factor * left[column][index];
+                right[row][index] = right[row][index] - factor * right[column][index];
+            }
+        }
+    }
+
+    if right.iter().flat_map(|row| row.iter()).any(|value| !value.is_finite()) {
+        return None;
+    }
+    Some(right)
+}
@@ -1,5 +1,8 @@
 use num_lib as num;
 
+pub use num_lib::{CheckedAdd, CheckedMul, CheckedSub, SaturatingAdd, SaturatingMul, SaturatingSub};
+pub use num_lib::{WrappingAdd, WrappingMul, WrappingSub};
+
 pub trait Zero {
     fn zero() -> Self;
 }
@@ -19,3 +22,84 @@ impl<T: num::One> One for T {
         num::One::one()
     }
 }
+
+/// Selects how a fractional value is brought onto the integer grid.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Rounding {
+    /// Rounds to the nearest integer, halfway cases away from zero.
+    Nearest,
+    /// Rounds towards negative infinity.
+    Floor,
+    /// Rounds towards positive infinity.
+    Ceil,
+    /// Rounds towards zero.
+    Trunc,
+}
+
+impl Rounding {
+    /// Applies rounding to a value.
+    #[inline]
+    pub fn apply<T: Round>(self, value: T) -> T {
+        match self {
+            Rounding::Nearest => value.round(),
+            Rounding::Floor => value.floor(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::Trunc => value.trunc(),
+        }
+    }
+}
+
+/// Rounds fractional values to whole ones, keeping the type.
+pub trait Round: Sized {
+    fn round(&self) -> Self;
+
+    fn floor(&self) -> Self;
+
+    fn ceil(&self) -> Self;
+
+    fn trunc(&self) -> Self;
+}
+
+impl Round for f32 {
+    #[inline]
+    fn round(&self) -> f32 {
+        num::Float::round(*self)
+    }
+
+    #[inline]
+    fn floor(&self) -> f32 {
+        num::Float::floor(*self)
+    }
+
+    #[inline]
+    fn ceil(&self) -> f32 {
+        num::Float::ceil(*self)
+    }
+
+    #[inline]
+    fn trunc(&self) -> f32 {
+        num::Float::trunc(*self)
+    }
+}
+
+impl Round for f64 {
+    #[inline]
+    fn round(&self) -> f64 {
+        num::Float::round(*self)
+    }
+
+    #[inline]
+    fn floor(&self) -> f64 {
+        num::Float::floor(*self)
+    }
+
+    #[inline]
+    fn ceil(&self) -> f64 {
+        num::Float::ceil(*self)
+    }
+
+    #[inline]
+    fn trunc(&self) -> f64 {
+        num::Float::trunc(*self)
+    }
+}
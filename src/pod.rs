@@ -0,0 +1,142 @@
+use app_unit::Au;
+use box3d::Box3D;
+use length::Length;
+use matrix2d::Matrix2D;
+use matrix4d::Matrix4D;
+use point::{Point2D, Point3D, Point4D};
+use rect::Rect;
+use scale_factor::ScaleFactor;
+use size::{Size2D, Size3D};
+
+use core::mem;
+use core::slice;
+
+/// Marker for "plain old data" types that may be viewed as raw bytes.
+///
+/// Every geometry type here is `#[repr(C)]` (or `#[repr(transparent)]`) over
+/// fields of a single component type, so it qualifies whenever the component does.
+///
+/// # Safety
+///
+/// Implementors must contain no padding and accept any bit pattern.
+pub unsafe trait Pod: Copy + 'static {
+    /// Views the value as its raw bytes, without copying.
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self as *const Self as *const u8, mem::size_of::<Self>()) }
+    }
+
+    /// Views the bytes as a value, returns None when the size or alignment don't fit.
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Option<&Self> {
+        if bytes.len() != mem::size_of::<Self>() || !is_aligned::<Self>(bytes.as_ptr()) {
+            return None;
+        }
+
+        Some(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+}
+
+#[inline]
+fn is_aligned<T>(ptr: *const u8) -> bool {
+    (ptr as usize).is_multiple_of(mem::align_of::<T>())
+}
+
+/// Views the slice as its raw bytes, e.g. to upload a vertex buffer.
+#[inline]
+pub fn slice_as_bytes<T: Pod>(values: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(values.as_ptr() as *const u8, mem::size_of_val(values)) }
+}
+
+/// Reinterprets a slice of one pod type as another, e.g. `&[Point3D<f32>]` as `&[f32]`.
+///
+/// Returns None when the byte length isn't a multiple of the target size,
+/// or when the data isn't aligned for the target type.
+#[inline]
+pub fn cast_slice<A: Pod, B: Pod>(values: &[A]) -> Option<&[B]> {
+    let bytes = mem::size_of_val(values);
+    let size = mem::size_of::<B>();
+
+    if size == 0 || !bytes.is_multiple_of(size) || !is_aligned::<B>(values.as_ptr() as *const u8) {
+        return None;
+    }
+
+    Some(unsafe { slice::from_raw_parts(values.as_ptr() as *const B, bytes / size) })
+}
+
+/// Mutable counterpart of `cast_slice`.
+#[inline]
+pub fn cast_slice_mut<A: Pod, B: Pod>(values: &mut [A]) -> Option<&mut [B]> {
+    let bytes = mem::size_of_val(values);
+    let size = mem::size_of::<B>();
+
+    if size == 0 || !bytes.is_multiple_of(size) || !is_aligned::<B>(values.as_ptr() as *const u8) {
+        return None;
+    }
+
+    Some(unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut B, bytes / size) })
+}
+
+unsafe impl Pod for u8 {}
+unsafe impl Pod for u16 {}
+unsafe impl Pod for u32 {}
+unsafe impl Pod for u64 {}
+unsafe impl Pod for usize {}
+unsafe impl Pod for i8 {}
+unsafe impl Pod for i16 {}
+unsafe impl Pod for i32 {}
+unsafe impl Pod for i64 {}
+unsafe impl Pod for isize {}
+unsafe impl Pod for f32 {}
+unsafe impl Pod for f64 {}
+unsafe impl Pod for Au {}
+
+unsafe impl<U: Copy + 'static, T: Pod> Pod for Length<U, T> {}
+unsafe impl<S: Copy + 'static, D: Copy + 'static, T: Pod> Pod for ScaleFactor<S, D, T> {}
+unsafe impl<T: Pod> Pod for Point2D<T> {}
+unsafe impl<T: Pod> Pod for Point3D<T> {}
+unsafe impl<T: Pod> Pod for Point4D<T> {}
+unsafe impl<T: Pod> Pod for Size2D<T> {}
+unsafe impl<T: Pod> Pod for Size3D<T> {}
+unsafe impl<T: Pod> Pod for Rect<T> {}
+unsafe impl<T: Pod> Pod for Box3D<T> {}
+unsafe impl<T: Pod> Pod for Matrix2D<T> {}
+unsafe impl Pod for Matrix4D {}
+
+// @section:begin GPU buffer layouts.
+
+/// `Point3D<f32>` padded to 16 bytes, as `vec3` is laid out in std140 uniform blocks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C, align(16))]
+pub struct Std140Point3D {
+    pub point: Point3D<f32>,
+    padding: f32,
+}
+
+/// std430 rounds `vec3` to 16 bytes just like std140, so the layouts coincide.
+pub type Std430Point3D = Std140Point3D;
+
+impl Std140Point3D {
+    pub fn new(point: Point3D<f32>) -> Std140Point3D {
+        Std140Point3D {
+            point: point,
+            padding: 0.0,
+        }
+    }
+}
+
+impl From<Point3D<f32>> for Std140Point3D {
+    fn from(point: Point3D<f32>) -> Std140Point3D {
+        Std140Point3D::new(point)
+    }
+}
+
+unsafe impl Pod for Std140Point3D {}
+
+/// Pads the points to a 16 bytes stride, ready to upload as a std140/std430 `vec3` array.
+#[cfg(feature = "std")]
+pub fn pad_points(points: &[Point3D<f32>]) -> Vec<Std140Point3D> {
+    points.iter().map(|point| Std140Point3D::new(*point)).collect()
+}
+
+// @section:end
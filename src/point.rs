@@ -1,18 +1,25 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use interpolate::Interpolate;
 use length::Length;
 use size::Size2D;
-use num::Zero;
+use num::{Round, Rounding, Zero};
+use scale_factor::ScaleFactor;
 
 use num_lib::NumCast;
-use num_lib::traits;
-use std::fmt as f;
-use std::ops::{Add, Neg, Mul, Sub, Div};
+use num_lib::Float;
+use num_lib::{CheckedAdd, CheckedMul, CheckedSub, SaturatingAdd, SaturatingMul, SaturatingSub};
+use num_lib::{WrappingAdd, WrappingMul, WrappingSub};
+use core::fmt as f;
+use core::ops::{Add, AddAssign, Neg, Mul, MulAssign, Sub, SubAssign, Div, DivAssign};
 
 // .
 // . Two dimensions point.
 // .
 
-#[derive(Clone, Copy, RustcDecodable, RustcEncodable, Eq, Hash, PartialEq)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf, Deserialize, Serialize))]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Point2D<T> {
     pub x: T,
     pub y: T,
@@ -20,7 +27,7 @@ pub struct Point2D<T> {
 
 /// Creates an instance of Point2D.
 impl<T> Point2D<T> {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     pub fn new(x: T, y: T) -> Point2D<T> {
         Point2D {
             x: x,
@@ -74,6 +81,14 @@ impl<T: Clone + Add<T, Output = T>> Add for Point2D<T> {
     }
 }
 
+impl<T: AddAssign<T>> AddAssign for Point2D<T> {
+    #[inline]
+    fn add_assign(&mut self, other: Point2D<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
 impl<T: Clone + Add<T, Output = T>> Add<Size2D<T>> for Point2D<T> {
     type Output = Point2D<T>;
 
@@ -99,6 +114,14 @@ impl<T: Clone + Sub<T, Output = T>> Sub for Point2D<T> {
     }
 }
 
+impl<T: SubAssign<T>> SubAssign for Point2D<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: Point2D<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
 impl<T: Clone + Neg<Output = T>> Neg for Point2D<T> {
     type Output = Point2D<T>;
 
@@ -108,7 +131,7 @@ impl<T: Clone + Neg<Output = T>> Neg for Point2D<T> {
     }
 }
 
-impl<T: traits::Float> Point2D<T> {
+impl<T: Float> Point2D<T> {
     pub fn min(self, other: Point2D<T>) -> Point2D<T> {
         Point2D::new(self.x.min(other.x), self.y.min(other.y))
     }
@@ -116,23 +139,173 @@ impl<T: traits::Float> Point2D<T> {
     pub fn max(self, other: Point2D<T>) -> Point2D<T> {
         Point2D::new(self.x.max(other.x), self.y.max(other.y))
     }
+
+    /// Clamps each component between the matching components of `min` and `max`.
+    pub fn clamp(self, min: Point2D<T>, max: Point2D<T>) -> Point2D<T> {
+        self.max(min).min(max)
+    }
+
+    pub fn abs(self) -> Point2D<T> {
+        Point2D::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Squared length of the vector, cheaper than `length` when only comparing.
+    #[inline]
+    pub fn square_length(self) -> T {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn length(self) -> T {
+        self.x.hypot(self.y)
+    }
+
+    pub fn distance_to(self, other: Point2D<T>) -> T {
+        (other - self).length()
+    }
+
+    /// Scales the vector to unit length, the zero vector stays zero instead of turning into NaN.
+    pub fn normalize(self) -> Point2D<T> {
+        let length = self.length();
+
+        if length.is_zero() {
+            return self;
+        }
+        Point2D::new(self.x / length, self.y / length)
+    }
+
+    /// Signed angle in radians, counter-clockwise from `self` to `other`, in `[-PI, PI]`.
+    ///
+    /// Zero when either vector is zero.
+    pub fn angle_to(self, other: Point2D<T>) -> T {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    /// Rotates counter-clockwise around the origin by `angle` in radians.
+    pub fn rotate(self, angle: T) -> Point2D<T> {
+        let (sin, cos) = angle.sin_cos();
+        Point2D::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Projects onto the line spanned by `onto`, which yields zero when `onto` is zero.
+    pub fn project_onto(self, onto: Point2D<T>) -> Point2D<T> {
+        let square_length = onto.square_length();
+
+        if square_length.is_zero() {
+            return Point2D::new(T::zero(), T::zero());
+        }
+        onto * (self.dot(onto) / square_length)
+    }
+
+    /// Reflects off a surface with the given normal, which doesn't need unit length.
+    ///
+    /// A zero normal leaves the vector unchanged.
+    pub fn reflect(self, normal: Point2D<T>) -> Point2D<T> {
+        self - self.project_onto(normal) * (T::one() + T::one())
+    }
+
+    /// Blends linearly, `t` of zero gives `self` and `t` of one gives `other`.
+    ///
+    /// Forwards to `Interpolate::lerp`, without having to import the trait.
+    #[inline]
+    pub fn lerp(&self, other: &Point2D<T>, t: T) -> Point2D<T> {
+        Interpolate::lerp(self, other, t)
+    }
 }
 
-impl<S: Copy, T: Mul<S, Output = R>, R: Clone> Mul<S> for Point2D<T> {
+/// Overflow-checked arithmetic, returns None when any component overflows.
+impl<T: CheckedAdd + CheckedSub> Point2D<T> {
+    pub fn checked_add(&self, other: &Point2D<T>) -> Option<Point2D<T>> {
+        match (self.x.checked_add(&other.x), self.y.checked_add(&other.y)) {
+            (Some(x), Some(y)) => Some(Point2D::new(x, y)),
+            _ => None,
+        }
+    }
+
+    pub fn checked_sub(&self, other: &Point2D<T>) -> Option<Point2D<T>> {
+        match (self.x.checked_sub(&other.x), self.y.checked_sub(&other.y)) {
+            (Some(x), Some(y)) => Some(Point2D::new(x, y)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CheckedMul> Point2D<T> {
+    pub fn checked_mul(&self, scale: T) -> Option<Point2D<T>> {
+        match (self.x.checked_mul(&scale), self.y.checked_mul(&scale)) {
+            (Some(x), Some(y)) => Some(Point2D::new(x, y)),
+            _ => None,
+        }
+    }
+}
+
+/// Saturating arithmetic, clamps each component to the numeric bounds.
+impl<T: SaturatingAdd + SaturatingSub> Point2D<T> {
+    pub fn saturating_add(&self, other: &Point2D<T>) -> Point2D<T> {
+        Point2D::new(self.x.saturating_add(&other.x), self.y.saturating_add(&other.y))
+    }
+
+    pub fn saturating_sub(&self, other: &Point2D<T>) -> Point2D<T> {
+        Point2D::new(self.x.saturating_sub(&other.x), self.y.saturating_sub(&other.y))
+    }
+}
+
+impl<T: SaturatingMul> Point2D<T> {
+    pub fn saturating_mul(&self, scale: T) -> Point2D<T> {
+        Point2D::new(self.x.saturating_mul(&scale), self.y.saturating_mul(&scale))
+    }
+}
+
+/// Wrapping arithmetic, wraps each component around the numeric bounds.
+impl<T: WrappingAdd + WrappingSub> Point2D<T> {
+    pub fn wrapping_add(&self, other: &Point2D<T>) -> Point2D<T> {
+        Point2D::new(self.x.wrapping_add(&other.x), self.y.wrapping_add(&other.y))
+    }
+
+    pub fn wrapping_sub(&self, other: &Point2D<T>) -> Point2D<T> {
+        Point2D::new(self.x.wrapping_sub(&other.x), self.y.wrapping_sub(&other.y))
+    }
+}
+
+impl<T: WrappingMul> Point2D<T> {
+    pub fn wrapping_mul(&self, scale: T) -> Point2D<T> {
+        Point2D::new(self.x.wrapping_mul(&scale), self.y.wrapping_mul(&scale))
+    }
+}
+
+/// Scales by a scalar, or moves a typed point to another space by `ScaleFactor<Src, Dst, T>`.
+impl<S: Clone, T: Mul<S, Output = R>, R: Clone> Mul<S> for Point2D<T> {
     type Output = Point2D<R>;
 
     #[inline]
     fn mul(self, scale: S) -> Point2D<R> {
-        Point2D::new(self.x * scale, self.y * scale)
+        Point2D::new(self.x * scale.clone(), self.y * scale)
     }
 }
 
-impl<S: Copy, T: Div<S, Output = R>, R: Clone> Div<S> for Point2D<T> {
+impl<S: Copy, T: MulAssign<S>> MulAssign<S> for Point2D<T> {
+    #[inline]
+    fn mul_assign(&mut self, scale: S) {
+        self.x *= scale;
+        self.y *= scale;
+    }
+}
+
+/// Descales by a scalar, or moves a typed point back to the source space of a `ScaleFactor`.
+impl<S: Clone, T: Div<S, Output = R>, R: Clone> Div<S> for Point2D<T> {
     type Output = Point2D<R>;
 
     #[inline]
     fn div(self, scale: S) -> Point2D<R> {
-        Point2D::new(self.x / scale, self.y / scale)
+        Point2D::new(self.x / scale.clone(), self.y / scale)
+    }
+}
+
+impl<S: Copy, T: DivAssign<S>> DivAssign<S> for Point2D<T> {
+    #[inline]
+    fn div_assign(&mut self, scale: S) {
+        self.x /= scale;
+        self.y /= scale;
     }
 }
 
@@ -146,7 +319,7 @@ impl<U, T: Clone> TypedPoint2D<U, T> {
     }
 
     pub fn to_untyped(&self) -> Point2D<T> {
-        Point2D::new(self.x.get(), self.x.get())
+        Point2D::new(self.x.get(), self.y.get())
     }
 
     pub fn from_untyped(point: &Point2D<T>) -> TypedPoint2D<U, T> {
@@ -175,11 +348,49 @@ impl<U, T: Clone + NumCast> Point2D<Length<U, T>> {
     }
 }
 
+/// Rounds components to whole values, keeping the type.
+impl<T: Round> Point2D<T> {
+    pub fn round(&self) -> Point2D<T> {
+        Point2D::new(self.x.round(), self.y.round())
+    }
+
+    pub fn floor(&self) -> Point2D<T> {
+        Point2D::new(self.x.floor(), self.y.floor())
+    }
+
+    pub fn ceil(&self) -> Point2D<T> {
+        Point2D::new(self.x.ceil(), self.y.ceil())
+    }
+
+    pub fn trunc(&self) -> Point2D<T> {
+        Point2D::new(self.x.trunc(), self.y.trunc())
+    }
+}
+
+impl<U, T: Clone + NumCast + Round> Point2D<Length<U, T>> {
+    /// Casts with explicit rounding, where `cast` would truncate.
+    pub fn cast_round<R: Clone + NumCast>(&self, rounding: Rounding) -> Option<Point2D<Length<U, R>>> {
+        match (self.x.cast_round(rounding), self.y.cast_round(rounding)) {
+            (Some(x), Some(y)) => Some(Point2D::new(x, y)),
+            _ => None,
+        }
+    }
+}
+
+impl<S, T: Clone + NumCast + Round + Mul<T, Output = T>> Point2D<Length<S, T>> {
+    /// Scales the point to device space and rounds it to the nearest device pixel.
+    pub fn snap_to_device<D>(&self, scale: ScaleFactor<S, D, T>) -> Option<TypedPoint2D<D, i32>> {
+        let point: TypedPoint2D<D, T> = Point2D::new(self.x.clone() * scale.clone(), self.y.clone() * scale);
+        point.cast_round(Rounding::Nearest)
+    }
+}
+
 // .
 // . Three dimensions point.
 // .
-#[derive(Clone, Copy, RustcDecodable, RustcEncodable, Eq, Hash, PartialEq)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf, Deserialize, Serialize))]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Point3D<T> {
     pub x: T,
     pub y: T,
@@ -189,7 +400,7 @@ pub struct Point3D<T> {
 /// Creates an instance of Point2D.
 impl<T> Point3D<T> {
     #[inline]
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     pub fn new(x: T, y: T, z: T) -> Point3D<T> {
         Point3D {
             x: x,
@@ -225,7 +436,7 @@ impl<T: f::Display> f::Display for Point3D<T> {
     }
 }
 
- #[cfg_attr(rustfmt, rustfmt_skip)]
+ #[rustfmt::skip]
 impl <T:Copy + Mul<T, Output=T> + Add<T, Output=T> + Sub<T, Output=T>> Point3D<T> {
     #[inline]
     pub fn dot(self, other: Point3D<T>) -> T {
@@ -247,7 +458,7 @@ impl <T:Copy + Mul<T, Output=T> + Add<T, Output=T> + Sub<T, Output=T>> Point3D<T
 impl<T: Clone + Add<T, Output = T>> Add for Point3D<T> {
     type Output = Point3D<T>;
 
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     fn add(self, other: Point3D<T>) -> Point3D<T> {
         Point3D::new(
             self.x + other.x,
@@ -257,10 +468,19 @@ impl<T: Clone + Add<T, Output = T>> Add for Point3D<T> {
     }
 }
 
+impl<T: AddAssign<T>> AddAssign for Point3D<T> {
+    #[inline]
+    fn add_assign(&mut self, other: Point3D<T>) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
 impl<T: Clone + Sub<T, Output = T>> Sub for Point3D<T> {
     type Output = Point3D<T>;
 
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     fn sub(self, other: Point3D<T>) -> Point3D<T> {
         Point3D::new(
             self.x - other.x,
@@ -270,11 +490,83 @@ impl<T: Clone + Sub<T, Output = T>> Sub for Point3D<T> {
     }
 }
 
+impl<T: SubAssign<T>> SubAssign for Point3D<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: Point3D<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+/// Scales by a scalar, or moves a typed point to another space by `ScaleFactor<Src, Dst, T>`.
+impl<S: Clone, T: Mul<S, Output = R>, R: Clone> Mul<S> for Point3D<T> {
+    type Output = Point3D<R>;
+
+    #[inline]
+    fn mul(self, scale: S) -> Point3D<R> {
+        Point3D::new(self.x * scale.clone(), self.y * scale.clone(), self.z * scale)
+    }
+}
+
+impl<S: Copy, T: MulAssign<S>> MulAssign<S> for Point3D<T> {
+    #[inline]
+    fn mul_assign(&mut self, scale: S) {
+        self.x *= scale;
+        self.y *= scale;
+        self.z *= scale;
+    }
+}
+
+impl<S: Copy, T: DivAssign<S>> DivAssign<S> for Point3D<T> {
+    #[inline]
+    fn div_assign(&mut self, scale: S) {
+        self.x /= scale;
+        self.y /= scale;
+        self.z /= scale;
+    }
+}
+
+/// Descales by a scalar, or moves a typed point back to the source space of a `ScaleFactor`.
+impl<S: Clone, T: Div<S, Output = R>, R: Clone> Div<S> for Point3D<T> {
+    type Output = Point3D<R>;
+
+    #[inline]
+    fn div(self, scale: S) -> Point3D<R> {
+        Point3D::new(self.x / scale.clone(), self.y / scale.clone(), self.z / scale)
+    }
+}
+
+// @section:begin Convenient aliases for Point3D with typed units.
+
+pub type TypedPoint3D<U, T> = Point3D<Length<U, T>>;
+
+impl<U, T: Clone> TypedPoint3D<U, T> {
+    pub fn typed(x: T, y: T, z: T) -> TypedPoint3D<U, T> {
+        Point3D::new(Length::new(x), Length::new(y), Length::new(z))
+    }
+
+    pub fn to_untyped(&self) -> Point3D<T> {
+        Point3D::new(self.x.get(), self.y.get(), self.z.get())
+    }
+
+    #[rustfmt::skip]
+    pub fn from_untyped(point: &Point3D<T>) -> TypedPoint3D<U, T> {
+        Point3D::new(
+            Length::new(point.x.clone()),
+            Length::new(point.y.clone()),
+            Length::new(point.z.clone())
+        )
+    }
+}
+
+// @section:end
+
 impl<T: Clone + Neg<Output = T>> Neg for Point3D<T> {
     type Output = Point3D<T>;
 
     #[inline]
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     fn neg(self) -> Point3D<T> {
         Point3D::new(
             -self.x,
@@ -284,8 +576,8 @@ impl<T: Clone + Neg<Output = T>> Neg for Point3D<T> {
     }
 }
 
-impl<T: traits::Float> Point3D<T> {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+impl<T: Float> Point3D<T> {
+    #[rustfmt::skip]
     pub fn min(self, other: Point3D<T>) -> Point3D<T> {
         Point3D::new(
             self.x.min(other.x),
@@ -294,7 +586,7 @@ impl<T: traits::Float> Point3D<T> {
         )
     }
 
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     pub fn max(self, other: Point3D<T>) -> Point3D<T> {
         Point3D::new(
             self.x.max(other.x),
@@ -302,13 +594,221 @@ impl<T: traits::Float> Point3D<T> {
             self.z.max(other.z)
         )
     }
+
+    /// Clamps each component between the matching components of `min` and `max`.
+    pub fn clamp(self, min: Point3D<T>, max: Point3D<T>) -> Point3D<T> {
+        self.max(min).min(max)
+    }
+
+    pub fn abs(self) -> Point3D<T> {
+        Point3D::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// Squared length of the vector, cheaper than `length` when only comparing.
+    #[inline]
+    pub fn square_length(self) -> T {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn length(self) -> T {
+        self.square_length().sqrt()
+    }
+
+    pub fn distance_to(self, other: Point3D<T>) -> T {
+        (other - self).length()
+    }
+
+    /// Scales the vector to unit length, the zero vector stays zero instead of turning into NaN.
+    pub fn normalize(self) -> Point3D<T> {
+        let length = self.length();
+
+        if length.is_zero() {
+            return self;
+        }
+        Point3D::new(self.x / length, self.y / length, self.z / length)
+    }
+
+    /// Unsigned angle in radians between the vectors, in `[0, PI]`.
+    ///
+    /// Zero when either vector is zero.
+    pub fn angle_to(self, other: Point3D<T>) -> T {
+        self.cross(other).length().atan2(self.dot(other))
+    }
+
+    /// Rotates by `angle` in radians around `axis`, counter-clockwise when the axis points
+    /// at the viewer. The axis doesn't need unit length, a zero axis leaves the vector unchanged.
+    #[rustfmt::skip]
+    pub fn rotate(self, axis: Point3D<T>, angle: T) -> Point3D<T> {
+        let axis = axis.normalize();
+        if axis.square_length().is_zero() {
+            return self;
+        }
+
+        let (sin, cos) = angle.sin_cos();
+
+        // Rodrigues' rotation formula.
+        self * cos +
+        axis.cross(self) * sin +
+        axis * (axis.dot(self) * (T::one() - cos))
+    }
+
+    /// Projects onto the line spanned by `onto`, which yields zero when `onto` is zero.
+    pub fn project_onto(self, onto: Point3D<T>) -> Point3D<T> {
+        let square_length = onto.square_length();
+
+        if square_length.is_zero() {
+            return Point3D::new(T::zero(), T::zero(), T::zero());
+        }
+        onto * (self.dot(onto) / square_length)
+    }
+
+    /// Reflects off a surface with the given normal, which doesn't need unit length.
+    ///
+    /// A zero normal leaves the vector unchanged.
+    pub fn reflect(self, normal: Point3D<T>) -> Point3D<T> {
+        self - self.project_onto(normal) * (T::one() + T::one())
+    }
+
+    /// Blends linearly, `t` of zero gives `self` and `t` of one gives `other`.
+    ///
+    /// Forwards to `Interpolate::lerp`, without having to import the trait.
+    #[inline]
+    pub fn lerp(&self, other: &Point3D<T>, t: T) -> Point3D<T> {
+        Interpolate::lerp(self, other, t)
+    }
+}
+
+/// Overflow-checked arithmetic, returns None when any component overflows.
+impl<T: CheckedAdd + CheckedSub> Point3D<T> {
+    #[rustfmt::skip]
+    pub fn checked_add(&self, other: &Point3D<T>) -> Option<Point3D<T>> {
+        match (
+            self.x.checked_add(&other.x),
+            self.y.checked_add(&other.y),
+            self.z.checked_add(&other.z)
+        ) {
+            (Some(x), Some(y), Some(z)) => Some(Point3D::new(x, y, z)),
+            _ => None,
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn checked_sub(&self, other: &Point3D<T>) -> Option<Point3D<T>> {
+        match (
+            self.x.checked_sub(&other.x),
+            self.y.checked_sub(&other.y),
+            self.z.checked_sub(&other.z)
+        ) {
+            (Some(x), Some(y), Some(z)) => Some(Point3D::new(x, y, z)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CheckedMul> Point3D<T> {
+    #[rustfmt::skip]
+    pub fn checked_mul(&self, scale: T) -> Option<Point3D<T>> {
+        match (
+            self.x.checked_mul(&scale),
+            self.y.checked_mul(&scale),
+            self.z.checked_mul(&scale)
+        ) {
+            (Some(x), Some(y), Some(z)) => Some(Point3D::new(x, y, z)),
+            _ => None,
+        }
+    }
+}
+
+/// Saturating arithmetic, clamps each component to the numeric bounds.
+impl<T: SaturatingAdd + SaturatingSub> Point3D<T> {
+    #[rustfmt::skip]
+    pub fn saturating_add(&self, other: &Point3D<T>) -> Point3D<T> {
+        Point3D::new(
+            self.x.saturating_add(&other.x),
+            self.y.saturating_add(&other.y),
+            self.z.saturating_add(&other.z)
+        )
+    }
+
+    #[rustfmt::skip]
+    pub fn saturating_sub(&self, other: &Point3D<T>) -> Point3D<T> {
+        Point3D::new(
+            self.x.saturating_sub(&other.x),
+            self.y.saturating_sub(&other.y),
+            self.z.saturating_sub(&other.z)
+        )
+    }
+}
+
+impl<T: SaturatingMul> Point3D<T> {
+    #[rustfmt::skip]
+    pub fn saturating_mul(&self, scale: T) -> Point3D<T> {
+        Point3D::new(
+            self.x.saturating_mul(&scale),
+            self.y.saturating_mul(&scale),
+            self.z.saturating_mul(&scale)
+        )
+    }
+}
+
+/// Wrapping arithmetic, wraps each component around the numeric bounds.
+impl<T: WrappingAdd + WrappingSub> Point3D<T> {
+    #[rustfmt::skip]
+    pub fn wrapping_add(&self, other: &Point3D<T>) -> Point3D<T> {
+        Point3D::new(
+            self.x.wrapping_add(&other.x),
+            self.y.wrapping_add(&other.y),
+            self.z.wrapping_add(&other.z)
+        )
+    }
+
+    #[rustfmt::skip]
+    pub fn wrapping_sub(&self, other: &Point3D<T>) -> Point3D<T> {
+        Point3D::new(
+            self.x.wrapping_sub(&other.x),
+            self.y.wrapping_sub(&other.y),
+            self.z.wrapping_sub(&other.z)
+        )
+    }
+}
+
+impl<T: WrappingMul> Point3D<T> {
+    #[rustfmt::skip]
+    pub fn wrapping_mul(&self, scale: T) -> Point3D<T> {
+        Point3D::new(
+            self.x.wrapping_mul(&scale),
+            self.y.wrapping_mul(&scale),
+            self.z.wrapping_mul(&scale)
+        )
+    }
+}
+
+/// Rounds components to whole values, keeping the type.
+impl<T: Round> Point3D<T> {
+    pub fn round(&self) -> Point3D<T> {
+        Point3D::new(self.x.round(), self.y.round(), self.z.round())
+    }
+
+    pub fn floor(&self) -> Point3D<T> {
+        Point3D::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+
+    pub fn ceil(&self) -> Point3D<T> {
+        Point3D::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+
+    pub fn trunc(&self) -> Point3D<T> {
+        Point3D::new(self.x.trunc(), self.y.trunc(), self.z.trunc())
+    }
 }
 
 // .
 // . Four dimensions point.
 // .
-#[derive(Clone, Copy, RustcDecodable, RustcEncodable, Eq, Hash, PartialEq)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf, Deserialize, Serialize))]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Point4D<T> {
     pub x: T,
     pub y: T,
@@ -318,7 +818,7 @@ pub struct Point4D<T> {
 
 impl<T> Point4D<T> {
     #[inline]
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     pub fn new(x: T, y: T, z: T, t: T) -> Point4D<T> {
         Point4D {
             x: x,
@@ -343,7 +843,7 @@ impl<T: Zero> Point4D<T> {
 
 /// Formats output for debugging purpose.
 impl<T: f::Debug> f::Debug for Point4D<T> {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     fn fmt(&self, formatter: &mut f::Formatter) -> f::Result {
         write!(formatter, "({:?}, {:?}, {:?}, {:?})", self.x, self.y, self.z, self.t)
     }
@@ -351,7 +851,7 @@ impl<T: f::Debug> f::Debug for Point4D<T> {
 
 /// Formats output.
 impl<T: f::Display> f::Display for Point4D<T> {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     fn fmt(&self, formatter: &mut f::Formatter) -> f::Result {
         write!(formatter, "({}, {}, {}, {})", self.x, self.y, self.z, self.t)
     }
@@ -361,7 +861,7 @@ impl<T: Clone + Add<T, Output = T>> Add for Point4D<T> {
     type Output = Point4D<T>;
 
     #[inline]
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     fn add(self, other: Point4D<T>) -> Point4D<T> {
         Point4D::new(
             self.x + other.x,
@@ -372,11 +872,21 @@ impl<T: Clone + Add<T, Output = T>> Add for Point4D<T> {
     }
 }
 
+impl<T: AddAssign<T>> AddAssign for Point4D<T> {
+    #[inline]
+    fn add_assign(&mut self, other: Point4D<T>) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+        self.t += other.t;
+    }
+}
+
 impl<T: Clone + Sub<T, Output = T>> Sub for Point4D<T> {
     type Output = Point4D<T>;
 
     #[inline]
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     fn sub(self, other:Point4D<T>) -> Point4D<T> {
         Point4D::new(
             self.x - other.x,
@@ -387,11 +897,71 @@ impl<T: Clone + Sub<T, Output = T>> Sub for Point4D<T> {
     }
 }
 
+impl<T: SubAssign<T>> SubAssign for Point4D<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: Point4D<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+        self.t -= other.t;
+    }
+}
+
+/// Scales all four components, which leaves the point it projects to unchanged.
+impl<S: Clone, T: Mul<S, Output = R>, R: Clone> Mul<S> for Point4D<T> {
+    type Output = Point4D<R>;
+
+    #[rustfmt::skip]
+    fn mul(self, scale: S) -> Point4D<R> {
+        Point4D::new(
+            self.x * scale.clone(),
+            self.y * scale.clone(),
+            self.z * scale.clone(),
+            self.t * scale
+        )
+    }
+}
+
+/// Descales all four components, which leaves the point it projects to unchanged.
+impl<S: Clone, T: Div<S, Output = R>, R: Clone> Div<S> for Point4D<T> {
+    type Output = Point4D<R>;
+
+    #[rustfmt::skip]
+    fn div(self, scale: S) -> Point4D<R> {
+        Point4D::new(
+            self.x / scale.clone(),
+            self.y / scale.clone(),
+            self.z / scale.clone(),
+            self.t / scale
+        )
+    }
+}
+
+impl<S: Copy, T: MulAssign<S>> MulAssign<S> for Point4D<T> {
+    #[inline]
+    fn mul_assign(&mut self, scale: S) {
+        self.x *= scale;
+        self.y *= scale;
+        self.z *= scale;
+        self.t *= scale;
+    }
+}
+
+impl<S: Copy, T: DivAssign<S>> DivAssign<S> for Point4D<T> {
+    #[inline]
+    fn div_assign(&mut self, scale: S) {
+        self.x /= scale;
+        self.y /= scale;
+        self.z /= scale;
+        self.t /= scale;
+    }
+}
+
 impl<T: Clone + Neg<Output = T>> Neg for Point4D<T> {
     type Output = Point4D<T>;
 
     #[inline]
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     fn neg(self) -> Point4D<T> {
         Point4D::new(
             -self.x,
@@ -402,8 +972,8 @@ impl<T: Clone + Neg<Output = T>> Neg for Point4D<T> {
     }
 }
 
-impl<T: traits::Float> Point4D<T> {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+impl<T: Float> Point4D<T> {
+    #[rustfmt::skip]
     pub fn min(self, other: Point4D<T>) -> Point4D<T> {
         Point4D::new(
             self.x.min(other.x),
@@ -413,7 +983,7 @@ impl<T: traits::Float> Point4D<T> {
         )
     }
 
-     #[cfg_attr(rustfmt, rustfmt_skip)]
+     #[rustfmt::skip]
     pub fn max(self, other:Point4D<T>) -> Point4D<T> {
         Point4D::new(
             self.x.max(other.x),
@@ -422,4 +992,149 @@ impl<T: traits::Float> Point4D<T> {
             self.t.max(other.t)
         )
     }
+
+    /// Projects the homogeneous point back to 3D by dividing through `t`.
+    ///
+    /// Returns None when `t` is too close to zero, i.e. the point lies at infinity.
+    pub fn to_point3d(self) -> Option<Point3D<T>> {
+        if self.t.abs() <= T::epsilon() {
+            return None;
+        }
+        Some(Point3D::new(self.x / self.t, self.y / self.t, self.z / self.t))
+    }
+
+    /// Projects the homogeneous point onto the plane by dividing through `t`, dropping `z`.
+    ///
+    /// Returns None when `t` is too close to zero, i.e. the point lies at infinity.
+    pub fn to_point2d(self) -> Option<Point2D<T>> {
+        if self.t.abs() <= T::epsilon() {
+            return None;
+        }
+        Some(Point2D::new(self.x / self.t, self.y / self.t))
+    }
+}
+
+impl<T: Copy + Mul<T, Output = T> + Add<T, Output = T>> Point4D<T> {
+    #[inline]
+    #[rustfmt::skip]
+    pub fn dot(self, other: Point4D<T>) -> T {
+        self.x * other.x +
+        self.y * other.y +
+        self.z * other.z +
+        self.t * other.t
+    }
+}
+
+/// Overflow-checked arithmetic, returns None when any component overflows.
+impl<T: CheckedAdd + CheckedSub> Point4D<T> {
+    #[rustfmt::skip]
+    pub fn checked_add(&self, other: &Point4D<T>) -> Option<Point4D<T>> {
+        match (
+            self.x.checked_add(&other.x),
+            self.y.checked_add(&other.y),
+            self.z.checked_add(&other.z),
+            self.t.checked_add(&other.t)
+        ) {
+            (Some(x), Some(y), Some(z), Some(t)) => Some(Point4D::new(x, y, z, t)),
+            _ => None,
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn checked_sub(&self, other: &Point4D<T>) -> Option<Point4D<T>> {
+        match (
+            self.x.checked_sub(&other.x),
+            self.y.checked_sub(&other.y),
+            self.z.checked_sub(&other.z),
+            self.t.checked_sub(&other.t)
+        ) {
+            (Some(x), Some(y), Some(z), Some(t)) => Some(Point4D::new(x, y, z, t)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CheckedMul> Point4D<T> {
+    #[rustfmt::skip]
+    pub fn checked_mul(&self, scale: T) -> Option<Point4D<T>> {
+        match (
+            self.x.checked_mul(&scale),
+            self.y.checked_mul(&scale),
+            self.z.checked_mul(&scale),
+            self.t.checked_mul(&scale)
+        ) {
+            (Some(x), Some(y), Some(z), Some(t)) => Some(Point4D::new(x, y, z, t)),
+            _ => None,
+        }
+    }
+}
+
+/// Saturating arithmetic, clamps each component to the numeric bounds.
+impl<T: SaturatingAdd + SaturatingSub> Point4D<T> {
+    #[rustfmt::skip]
+    pub fn saturating_add(&self, other: &Point4D<T>) -> Point4D<T> {
+        Point4D::new(
+            self.x.saturating_add(&other.x),
+            self.y.saturating_add(&other.y),
+            self.z.saturating_add(&other.z),
+            self.t.saturating_add(&other.t)
+        )
+    }
+
+    #[rustfmt::skip]
+    pub fn saturating_sub(&self, other: &Point4D<T>) -> Point4D<T> {
+        Point4D::new(
+            self.x.saturating_sub(&other.x),
+            self.y.saturating_sub(&other.y),
+            self.z.saturating_sub(&other.z),
+            self.t.saturating_sub(&other.t)
+        )
+    }
+}
+
+impl<T: SaturatingMul> Point4D<T> {
+    #[rustfmt::skip]
+    pub fn saturating_mul(&self, scale: T) -> Point4D<T> {
+        Point4D::new(
+            self.x.saturating_mul(&scale),
+            self.y.saturating_mul(&scale),
+            self.z.saturating_mul(&scale),
+            self.t.saturating_mul(&scale)
+        )
+    }
+}
+
+/// Wrapping arithmetic, wraps each component around the numeric bounds.
+impl<T: WrappingAdd + WrappingSub> Point4D<T> {
+    #[rustfmt::skip]
+    pub fn wrapping_add(&self, other: &Point4D<T>) -> Point4D<T> {
+        Point4D::new(
+            self.x.wrapping_add(&other.x),
+            self.y.wrapping_add(&other.y),
+            self.z.wrapping_add(&other.z),
+            self.t.wrapping_add(&other.t)
+        )
+    }
+
+    #[rustfmt::skip]
+    pub fn wrapping_sub(&self, other: &Point4D<T>) -> Point4D<T> {
+        Point4D::new(
+            self.x.wrapping_sub(&other.x),
+            self.y.wrapping_sub(&other.y),
+            self.z.wrapping_sub(&other.z),
+            self.t.wrapping_sub(&other.t)
+        )
+    }
+}
+
+impl<T: WrappingMul> Point4D<T> {
+    #[rustfmt::skip]
+    pub fn wrapping_mul(&self, scale: T) -> Point4D<T> {
+        Point4D::new(
+            self.x.wrapping_mul(&scale),
+            self.y.wrapping_mul(&scale),
+            self.z.wrapping_mul(&scale),
+            self.t.wrapping_mul(&scale)
+        )
+    }
 }
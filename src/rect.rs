@@ -0,0 +1,284 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use length::Length;
+use num::{Round, Rounding, Zero};
+use point::Point2D;
+use scale_factor::ScaleFactor;
+use size::Size2D;
+
+use num_lib::NumCast;
+use num_lib::{CheckedAdd, CheckedMul, SaturatingAdd, SaturatingMul, WrappingAdd, WrappingMul};
+use core::fmt::{Debug, Display, Formatter, Result};
+use core::ops::{Add, Div, Mul, Sub};
+
+/// Axis-aligned rectangle stored as its origin (minimal corner) and size.
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Rect<T> {
+    pub origin: Point2D<T>,
+    pub size: Size2D<T>,
+}
+
+/// Implements type-safe formatting for debug operations.
+impl<T: Debug> Debug for Rect<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        write!(formatter, "Rect({:?} at {:?})", self.size, self.origin)
+    }
+}
+
+/// Implements console-friendly format printing.
+impl<T: Display> Display for Rect<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        write!(formatter, "Rect({} at {})", self.size, self.origin)
+    }
+}
+
+/// Creates an instance of Rect.
+impl<T> Rect<T> {
+    pub fn new(origin: Point2D<T>, size: Size2D<T>) -> Rect<T> {
+        Rect {
+            origin: origin,
+            size: size,
+        }
+    }
+}
+
+/// Creates a zero singleton instance of Rect.
+impl<T: Zero> Rect<T> {
+    pub fn zero() -> Rect<T> {
+        Rect::new(Point2D::zero(), Size2D::zero())
+    }
+}
+
+impl<T: Clone + Add<T, Output = T>> Rect<T> {
+    #[inline]
+    pub fn min_x(&self) -> T {
+        self.origin.x.clone()
+    }
+
+    #[inline]
+    pub fn min_y(&self) -> T {
+        self.origin.y.clone()
+    }
+
+    #[inline]
+    pub fn max_x(&self) -> T {
+        self.origin.x.clone() + self.size.width.clone()
+    }
+
+    #[inline]
+    pub fn max_y(&self) -> T {
+        self.origin.y.clone() + self.size.height.clone()
+    }
+
+    /// Gets the minimal corner, same as origin.
+    #[inline]
+    pub fn min(&self) -> Point2D<T> {
+        self.origin.clone()
+    }
+
+    /// Gets the maximal corner.
+    #[inline]
+    pub fn max(&self) -> Point2D<T> {
+        Point2D::new(self.max_x(), self.max_y())
+    }
+}
+
+impl<T: Clone + Sub<T, Output = T>> Rect<T> {
+    /// Creates the rect spanning from minimal to maximal corner.
+    pub fn from_corners(min: Point2D<T>, max: Point2D<T>) -> Rect<T> {
+        let size = Size2D::new(max.x - min.x.clone(), max.y - min.y.clone());
+        Rect::new(min, size)
+    }
+}
+
+/// Overflow-checked arithmetic, returns None when any edge overflows.
+impl<T: Clone + CheckedAdd> Rect<T> {
+    /// Gets the maximal corner, or None when it overflows.
+    pub fn checked_max(&self) -> Option<Point2D<T>> {
+        match (self.origin.x.checked_add(&self.size.width), self.origin.y.checked_add(&self.size.height)) {
+            (Some(x), Some(y)) => Some(Point2D::new(x, y)),
+            _ => None,
+        }
+    }
+
+    /// Moves the rect by an offset, checking both corners so that `max()` stays valid.
+    pub fn checked_translate(&self, offset: &Point2D<T>) -> Option<Rect<T>> {
+        match (self.origin.x.checked_add(&offset.x), self.origin.y.checked_add(&offset.y)) {
+            (Some(x), Some(y)) => {
+                let moved = Rect::new(Point2D::new(x, y), self.size.clone());
+                moved.checked_max().map(|_| moved)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T: Clone + CheckedAdd + CheckedMul> Rect<T> {
+    /// Scales origin and size, checking both corners so that `max()` stays valid.
+    pub fn checked_mul(&self, scale: T) -> Option<Rect<T>> {
+        match (self.origin.checked_mul(scale.clone()), self.size.checked_mul(scale)) {
+            (Some(origin), Some(size)) => {
+                let scaled = Rect::new(origin, size);
+                scaled.checked_max().map(|_| scaled)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Saturating arithmetic, clamps origin and size to the numeric bounds.
+impl<T: Clone + SaturatingAdd> Rect<T> {
+    pub fn saturating_translate(&self, offset: &Point2D<T>) -> Rect<T> {
+        let origin = Point2D::new(self.origin.x.saturating_add(&offset.x), self.origin.y.saturating_add(&offset.y));
+        Rect::new(origin, self.size.clone())
+    }
+}
+
+impl<T: Clone + SaturatingMul> Rect<T> {
+    pub fn saturating_mul(&self, scale: T) -> Rect<T> {
+        Rect::new(self.origin.saturating_mul(scale.clone()), self.size.saturating_mul(scale))
+    }
+}
+
+/// Wrapping arithmetic, wraps origin and size around the numeric bounds.
+impl<T: Clone + WrappingAdd> Rect<T> {
+    pub fn wrapping_translate(&self, offset: &Point2D<T>) -> Rect<T> {
+        let origin = Point2D::new(self.origin.x.wrapping_add(&offset.x), self.origin.y.wrapping_add(&offset.y));
+        Rect::new(origin, self.size.clone())
+    }
+}
+
+impl<T: Clone + WrappingMul> Rect<T> {
+    pub fn wrapping_mul(&self, scale: T) -> Rect<T> {
+        Rect::new(self.origin.wrapping_mul(scale.clone()), self.size.wrapping_mul(scale))
+    }
+}
+
+/// Scales by a scalar, or moves a typed rect to another space by `ScaleFactor<Src, Dst, T>`.
+impl<S: Clone, T: Mul<S, Output = R>, R: Clone> Mul<S> for Rect<T> {
+    type Output = Rect<R>;
+
+    #[inline]
+    fn mul(self, scale: S) -> Rect<R> {
+        Rect::new(self.origin * scale.clone(), self.size * scale)
+    }
+}
+
+/// Descales by a scalar, or moves a typed rect back to the source space of a `ScaleFactor`.
+impl<S: Clone, T: Div<S, Output = R>, R: Clone> Div<S> for Rect<T> {
+    type Output = Rect<R>;
+
+    #[inline]
+    fn div(self, scale: S) -> Rect<R> {
+        Rect::new(self.origin / scale.clone(), self.size / scale)
+    }
+}
+
+#[inline]
+fn at_least<T: Clone + PartialOrd>(value: T, bound: &T) -> T {
+    if value < *bound {
+        bound.clone()
+    } else {
+        value
+    }
+}
+
+#[inline]
+fn round_point<T: Clone + Round>(point: &Point2D<T>, rounding: Rounding) -> Point2D<T> {
+    Point2D::new(rounding.apply(point.x.clone()), rounding.apply(point.y.clone()))
+}
+
+/// Rounds edges rather than origin and size, so rects sharing an edge keep sharing it.
+impl<T: Clone + Round + Add<T, Output = T> + Sub<T, Output = T>> Rect<T> {
+    /// Rounds each edge to the nearest whole value.
+    pub fn round(&self) -> Rect<T> {
+        Rect::from_corners(self.min().round(), self.max().round())
+    }
+
+    /// Rounds to the largest whole rect contained within. It comes out empty at the
+    /// rounded up origin when no whole pixel fits, rather than with a negative size.
+    pub fn round_in(&self) -> Rect<T>
+        where T: PartialOrd
+    {
+        let (min, max) = (self.min().ceil(), self.max().floor());
+        let max = Point2D::new(at_least(max.x, &min.x), at_least(max.y, &min.y));
+        Rect::from_corners(min, max)
+    }
+
+    /// Rounds to the smallest whole rect containing this one.
+    pub fn round_out(&self) -> Rect<T> {
+        Rect::from_corners(self.min().floor(), self.max().ceil())
+    }
+
+    /// Rounds each edge with the given mode.
+    pub fn round_with(&self, rounding: Rounding) -> Rect<T> {
+        Rect::from_corners(round_point(&self.min(), rounding), round_point(&self.max(), rounding))
+    }
+}
+
+// @section:begin Convenient aliases for Rect with typed units.
+
+pub type TypedRect<U, T> = Rect<Length<U, T>>;
+
+impl<U, T: Clone> Rect<Length<U, T>> {
+    /// Add measurement unit to type numeric values.
+    pub fn typed(x: T, y: T, width: T, height: T) -> TypedRect<U, T> {
+        Rect::new(Point2D::typed(x, y), Size2D::typed(width, height))
+    }
+
+    /// Drop measurement unit and preserve only numeric values.
+    pub fn to_untyped(&self) -> Rect<T> {
+        Rect::new(Point2D::new(self.origin.x.get(), self.origin.y.get()), self.size.untyped())
+    }
+
+    /// Creates the typed rect from untyped one.
+    pub fn from_untyped(rect: &Rect<T>) -> TypedRect<U, T> {
+        Rect::new(Point2D::from_untyped(&rect.origin), Size2D::from_untyped(&rect.size))
+    }
+}
+
+// @section:end
+
+impl<U, T: Clone + NumCast> Rect<Length<U, T>> {
+    pub fn cast<R: Clone + NumCast>(&self) -> Option<Rect<Length<U, R>>> {
+        match (self.origin.cast(), self.size.cast()) {
+            (Some(origin), Some(size)) => Some(Rect::new(origin, size)),
+            _ => None,
+        }
+    }
+}
+
+/// Casts with explicit rounding of the edges, where `cast` would truncate.
+impl<U, T: Clone + NumCast + Round + Add<T, Output = T> + Sub<T, Output = T>> Rect<Length<U, T>> {
+    pub fn cast_round<R: Clone + NumCast>(&self, rounding: Rounding) -> Option<Rect<Length<U, R>>> {
+        self.round_with(rounding).cast()
+    }
+
+    pub fn cast_round_in<R: Clone + NumCast>(&self) -> Option<Rect<Length<U, R>>>
+        where T: PartialOrd
+    {
+        self.round_in().cast()
+    }
+
+    pub fn cast_round_out<R: Clone + NumCast>(&self) -> Option<Rect<Length<U, R>>> {
+        self.round_out().cast()
+    }
+}
+
+#[rustfmt::skip]
+impl<S, T> Rect<Length<S, T>>
+    where T: Clone + NumCast + Round + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T>
+{
+    /// Scales the rect to device space and rounds its edges to the nearest device pixel.
+    ///
+    /// Edges are snapped instead of origin and size, so adjacent rects never open a seam.
+    pub fn snap_to_device<D>(&self, scale: ScaleFactor<S, D, T>) -> Option<TypedRect<D, i32>> {
+        let (min, max) = (self.min(), self.max());
+        let rect: TypedRect<D, T> = Rect::from_corners(min * scale.clone(), max * scale);
+
+        rect.cast_round(Rounding::Nearest)
+    }
+}
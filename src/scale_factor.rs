@@ -1,36 +1,39 @@
-#[cfg(feature = "plugins")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::ops::{Add, Mul, Sub, Div};
-use std::marker::PhantomData;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign, Div, DivAssign};
+use core::marker::PhantomData;
 
 use num_lib::NumCast;
 
 use num::One;
 
-#[derive(Copy, RustcDecodable, RustcEncodable, Debug)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf))]
+#[derive(Copy, Debug)]
+#[repr(transparent)]
 pub struct ScaleFactor<Src, Dst, T>(pub T, PhantomData<(Src, Dst)>);
 
-#[cfg(feature = "plugins")]
-impl<S, D, T> Deserialize for ScaleFactor<S, D, T> where T: Deserialize
+/// Deserializes scale factor from its bare scalar value.
+#[cfg(feature = "serde")]
+impl<'de, S, D, T> Deserialize<'de> for ScaleFactor<S, D, T> where T: Deserialize<'de>
 {
-    fn deserialize(deserializer: &mut D) -> Result<ScaleFactor<S, D, T>, D::Error> {
-        Ok(ScaleFactor(try!(Deserialize::deserialize(deserializer)), PhantomData))
+    #[rustfmt::skip]
+    fn deserialize<R>(deserializer: R) -> Result<ScaleFactor<S, D, T>, R::Error> where R: Deserializer<'de> {
+        Ok(ScaleFactor(Deserialize::deserialize(deserializer)?, PhantomData))
     }
 }
 
-#[cfg(feature = "plugins")]
+/// Serializes scale factor as its bare scalar value.
+#[cfg(feature = "serde")]
 impl<S, D, T> Serialize for ScaleFactor<S, D, T> where T: Serialize
 {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
-    fn serialize(&self, serializer: &mut S) -> Result<(), S::Error> where S: Serialize {
+    #[rustfmt::skip]
+    fn serialize<R>(&self, serializer: R) -> Result<R::Ok, R::Error> where R: Serializer {
         self.0.serialize(serializer)
     }
 }
 
 /// Creates an instance of new ScaleFactor.
 impl<S, D, T> ScaleFactor<S, D, T> {
-    pub fn new(x: T) -> ScaleFactor<S, D, T> {
+    pub const fn new(x: T) -> ScaleFactor<S, D, T> {
         ScaleFactor(x, PhantomData)
     }
 }
@@ -98,3 +101,31 @@ impl<S, D, T: Clone + Sub<T, Output = T>> Sub for ScaleFactor<S, D, T> {
         ScaleFactor::new(self.get() - other.get())
     }
 }
+
+/// Overloads "+=" increment assignment operator.
+impl<S, D, T: AddAssign<T>> AddAssign for ScaleFactor<S, D, T> {
+    fn add_assign(&mut self, other: ScaleFactor<S, D, T>) {
+        self.0 += other.0;
+    }
+}
+
+/// Overloads "-=" decrement assignment operator.
+impl<S, D, T: SubAssign<T>> SubAssign for ScaleFactor<S, D, T> {
+    fn sub_assign(&mut self, other: ScaleFactor<S, D, T>) {
+        self.0 -= other.0;
+    }
+}
+
+/// Overloads "*=" operator for scaling the factor by scalar.
+impl<S, D, T: MulAssign<T>> MulAssign<T> for ScaleFactor<S, D, T> {
+    fn mul_assign(&mut self, scale: T) {
+        self.0 *= scale;
+    }
+}
+
+/// Overloads "/=" operator for descaling the factor by scalar.
+impl<S, D, T: DivAssign<T>> DivAssign<T> for ScaleFactor<S, D, T> {
+    fn div_assign(&mut self, scale: T) {
+        self.0 /= scale;
+    }
+}
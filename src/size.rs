@@ -1,12 +1,18 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use length::Length;
-use num::Zero;
+use num::{Round, Rounding, Zero};
+use scale_factor::ScaleFactor;
 
 use num_lib::NumCast;
-use std::fmt::{Debug, Display, Formatter, Result};
-use std::ops::{Mul, Div};
+use num_lib::{CheckedAdd, CheckedMul, CheckedSub, SaturatingAdd, SaturatingMul, SaturatingSub};
+use num_lib::{WrappingAdd, WrappingMul, WrappingSub};
+use core::fmt::{Debug, Display, Formatter, Result};
+use core::ops::{AddAssign, Div, DivAssign, Mul, MulAssign, SubAssign};
 
-#[derive(Clone, Copy, RustcDecodable, RustcEncodable, PartialEq)]
-#[cfg_attr(feature = "plugins", derive(HeapSizeOf, Deserialize, Serialize))]
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Size2D<T> {
     pub width: T,
     pub height: T,
@@ -63,23 +69,145 @@ impl<T: Copy + Clone + Mul<T, Output = U>, U> Size2D<T> {
     }
 }
 
-/// Scales area of size.
-impl<S: Copy, T: Mul<S, Output = R>, R: Clone> Mul<S> for Size2D<T> {
+/// Calculates the area of size, returns None on overflow.
+impl<T: CheckedMul> Size2D<T> {
+    pub fn checked_area(&self) -> Option<T> {
+        self.width.checked_mul(&self.height)
+    }
+}
+
+/// Overflow-checked arithmetic, returns None when any component overflows.
+impl<T: Clone + CheckedAdd + CheckedSub> Size2D<T> {
+    pub fn checked_add(&self, other: &Size2D<T>) -> Option<Size2D<T>> {
+        match (self.width.checked_add(&other.width), self.height.checked_add(&other.height)) {
+            (Some(width), Some(height)) => Some(Size2D::new(width, height)),
+            _ => None,
+        }
+    }
+
+    pub fn checked_sub(&self, other: &Size2D<T>) -> Option<Size2D<T>> {
+        match (self.width.checked_sub(&other.width), self.height.checked_sub(&other.height)) {
+            (Some(width), Some(height)) => Some(Size2D::new(width, height)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Clone + CheckedMul> Size2D<T> {
+    pub fn checked_mul(&self, scale: T) -> Option<Size2D<T>> {
+        match (self.width.checked_mul(&scale), self.height.checked_mul(&scale)) {
+            (Some(width), Some(height)) => Some(Size2D::new(width, height)),
+            _ => None,
+        }
+    }
+}
+
+/// Saturating arithmetic, clamps each component to the numeric bounds.
+impl<T: Clone + SaturatingAdd + SaturatingSub> Size2D<T> {
+    pub fn saturating_add(&self, other: &Size2D<T>) -> Size2D<T> {
+        Size2D::new(self.width.saturating_add(&other.width), self.height.saturating_add(&other.height))
+    }
+
+    pub fn saturating_sub(&self, other: &Size2D<T>) -> Size2D<T> {
+        Size2D::new(self.width.saturating_sub(&other.width), self.height.saturating_sub(&other.height))
+    }
+}
+
+impl<T: Clone + SaturatingMul> Size2D<T> {
+    pub fn saturating_mul(&self, scale: T) -> Size2D<T> {
+        Size2D::new(self.width.saturating_mul(&scale), self.height.saturating_mul(&scale))
+    }
+}
+
+/// Wrapping arithmetic, wraps each component around the numeric bounds.
+impl<T: Clone + WrappingAdd + WrappingSub> Size2D<T> {
+    pub fn wrapping_add(&self, other: &Size2D<T>) -> Size2D<T> {
+        Size2D::new(self.width.wrapping_add(&other.width), self.height.wrapping_add(&other.height))
+    }
+
+    pub fn wrapping_sub(&self, other: &Size2D<T>) -> Size2D<T> {
+        Size2D::new(self.width.wrapping_sub(&other.width), self.height.wrapping_sub(&other.height))
+    }
+}
+
+impl<T: Clone + WrappingMul> Size2D<T> {
+    pub fn wrapping_mul(&self, scale: T) -> Size2D<T> {
+        Size2D::new(self.width.wrapping_mul(&scale), self.height.wrapping_mul(&scale))
+    }
+}
+
+/// Rounds dimensions to whole values, keeping the type.
+impl<T: Clone + Round> Size2D<T> {
+    pub fn round(&self) -> Size2D<T> {
+        Size2D::new(self.width.round(), self.height.round())
+    }
+
+    pub fn floor(&self) -> Size2D<T> {
+        Size2D::new(self.width.floor(), self.height.floor())
+    }
+
+    pub fn ceil(&self) -> Size2D<T> {
+        Size2D::new(self.width.ceil(), self.height.ceil())
+    }
+
+    pub fn trunc(&self) -> Size2D<T> {
+        Size2D::new(self.width.trunc(), self.height.trunc())
+    }
+}
+
+/// Scales area of size, typed sizes change space when scaled by `ScaleFactor`.
+impl<S: Clone, T: Mul<S, Output = R>, R: Clone> Mul<S> for Size2D<T> {
     type Output = Size2D<R>;
 
     #[inline]
     fn mul(self, scale: S) -> Size2D<R> {
-        Size2D::new(self.width * scale, self.height * scale)
+        Size2D::new(self.width * scale.clone(), self.height * scale)
+    }
+}
+
+/// Scales area of size in place.
+impl<S: Copy, T: MulAssign<S>> MulAssign<S> for Size2D<T> {
+    #[inline]
+    fn mul_assign(&mut self, scale: S) {
+        self.width *= scale;
+        self.height *= scale;
     }
 }
 
-/// Descales area of size.
-impl<S: Copy, T: Div<S, Output = R>, R: Clone> Div<S> for Size2D<T> {
+/// Descales area of size, typed sizes change space when divided by `ScaleFactor`.
+impl<S: Clone, T: Div<S, Output = R>, R: Clone> Div<S> for Size2D<T> {
     type Output = Size2D<R>;
 
     #[inline]
     fn div(self, scale: S) -> Size2D<R> {
-        Size2D::new(self.width / scale, self.height / scale)
+        Size2D::new(self.width / scale.clone(), self.height / scale)
+    }
+}
+
+/// Descales area of size in place.
+impl<S: Copy, T: DivAssign<S>> DivAssign<S> for Size2D<T> {
+    #[inline]
+    fn div_assign(&mut self, scale: S) {
+        self.width /= scale;
+        self.height /= scale;
+    }
+}
+
+/// Grows size in place.
+impl<T: AddAssign<T>> AddAssign for Size2D<T> {
+    #[inline]
+    fn add_assign(&mut self, other: Size2D<T>) {
+        self.width += other.width;
+        self.height += other.height;
+    }
+}
+
+/// Shrinks size in place.
+impl<T: SubAssign<T>> SubAssign for Size2D<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: Size2D<T>) {
+        self.width -= other.width;
+        self.height -= other.height;
     }
 }
 // @section:begin
@@ -117,6 +245,24 @@ impl<U, T: NumCast + Clone> Size2D<Length<U, T>> {
     }
 }
 
+impl<U, T: NumCast + Clone + Round> Size2D<Length<U, T>> {
+    /// Casts with explicit rounding, where `cast` would truncate.
+    pub fn cast_round<R: NumCast + Clone>(&self, rounding: Rounding) -> Option<Size2D<Length<U, R>>> {
+        match (self.width.cast_round(rounding), self.height.cast_round(rounding)) {
+            (Some(width), Some(height)) => Some(Size2D::new(width, height)),
+            _ => None,
+        }
+    }
+}
+
+impl<S, T: NumCast + Clone + Round + Mul<T, Output = T>> Size2D<Length<S, T>> {
+    /// Scales the size to device space and rounds it to the nearest device pixel.
+    pub fn snap_to_device<D>(&self, scale: ScaleFactor<S, D, T>) -> Option<TypedSize2D<D, i32>> {
+        let size: TypedSize2D<D, T> = Size2D::new(self.width.clone() * scale.clone(), self.height.clone() * scale);
+        size.cast_round(Rounding::Nearest)
+    }
+}
+
 /// TODO: replace on macros to create cast operation for most used numeric types.
 impl<U, T: NumCast + Clone> Size2D<Length<U, T>> {
     // to f32;
@@ -129,3 +275,51 @@ impl<U, T: NumCast + Clone> Size2D<Length<U, T>> {
         self.cast().unwrap()
     }
 }
+
+/// Extent along the three axes, e.g. of a box or a grid cell.
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Size3D<T> {
+    pub width: T,
+    pub height: T,
+    pub depth: T,
+}
+
+/// Implements type-safe formatting for debug operations.
+impl<T: Debug> Debug for Size3D<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        write!(formatter, "{:?}x{:?}x{:?}", self.width, self.height, self.depth)
+    }
+}
+
+/// Implements console-friendly format printing.
+impl<T: Display> Display for Size3D<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        write!(formatter, "({}x{}x{})", self.width, self.height, self.depth)
+    }
+}
+
+/// Create an instance of Size3D.
+impl<T> Size3D<T> {
+    pub fn new(width: T, height: T, depth: T) -> Size3D<T> {
+        Size3D {
+            width: width,
+            height: height,
+            depth: depth,
+        }
+    }
+}
+
+impl<T: Zero> Zero for Size3D<T> {
+    fn zero() -> Size3D<T> {
+        Size3D::new(Zero::zero(), Zero::zero(), Zero::zero())
+    }
+}
+
+/// Calculates the volume of size.
+impl<T: Copy + Mul<T, Output = T>> Size3D<T> {
+    pub fn volume(&self) -> T {
+        self.width * self.height * self.depth
+    }
+}
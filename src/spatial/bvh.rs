@@ -0,0 +1,411 @@
+use box3d::Box3D;
+use point::Point3D;
+
+use num_lib::Float;
+
+/// Number of buckets the centroids are sorted into when evaluating split candidates.
+const BINS: usize = 16;
+
+/// Half-line starting at the origin, points along it are `origin + direction * t` for `t >= 0`.
+///
+/// The direction needn't be normalized, hit distances are measured in multiples of it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray<T> {
+    pub origin: Point3D<T>,
+    pub direction: Point3D<T>,
+}
+
+impl<T: Float> Ray<T> {
+    pub fn new(origin: Point3D<T>, direction: Point3D<T>) -> Ray<T> {
+        Ray {
+            origin: origin,
+            direction: direction,
+        }
+    }
+
+    pub fn at(&self, t: T) -> Point3D<T> {
+        self.origin + self.direction * t
+    }
+
+    /// Distance at which the ray enters the box, zero when it starts inside.
+    ///
+    /// None when it misses the box or the box lies behind the origin.
+    pub fn intersect_box(&self, bounds: &Box3D<T>) -> Option<T> {
+        let (mut near, mut far) = (T::zero(), T::infinity());
+        let axes = [
+            (self.origin.x, self.direction.x, bounds.min.x, bounds.max.x),
+            (self.origin.y, self.direction.y, bounds.min.y, bounds.max.y),
+            (self.origin.z, self.direction.z, bounds.min.z, bounds.max.z),
+        ];
+
+        for &(origin, direction, min, max) in &axes {
+            if direction == T::zero() {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let (a, b) = ((min - origin) / direction, (max - origin) / direction);
+            near = near.max(a.min(b));
+            far = far.min(a.max(b));
+        }
+
+        if near <= far {
+            Some(near)
+        } else {
+            None
+        }
+    }
+}
+
+/// Closest intersection found by `Bvh::cast_ray`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit<T> {
+    /// Index of the item in the order the tree was built from.
+    pub item: usize,
+    pub distance: T,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    /// Range of `Bvh::order` holding the items.
+    Leaf(usize, usize),
+    Inner(usize, usize),
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    bounds: Box3D<T>,
+    kind: Kind,
+}
+
+/// Bounding volume hierarchy over items carrying an axis-aligned box and a payload.
+///
+/// The tree is built top-down, splitting every node where the surface area heuristic
+/// estimates the cheapest traversal. Items keep the index they had in the input, moving
+/// them only updates their boxes, `refit` then fixes the bounds of the nodes above while
+/// keeping the topology. A tree refitted after large movements answers queries correctly
+/// but slower than a rebuilt one.
+#[derive(Clone, Debug)]
+pub struct Bvh<T, V> {
+    items: Vec<(Box3D<T>, V)>,
+    /// Item indices, every leaf owning a contiguous range.
+    order: Vec<usize>,
+    /// Nodes in preorder, children always come after their parent.
+    nodes: Vec<Node<T>>,
+    leaf_size: usize,
+}
+
+impl<T: Float, V> Bvh<T, V> {
+    /// Builds the tree with leaves of up to 4 items.
+    pub fn new(items: Vec<(Box3D<T>, V)>) -> Bvh<T, V> {
+        Bvh::with_leaf_size(items, 4)
+    }
+
+    /// Builds the tree, leaves hold more than `leaf_size` items only when their boxes
+    /// can't be told apart by the centres.
+    pub fn with_leaf_size(items: Vec<(Box3D<T>, V)>, leaf_size: usize) -> Bvh<T, V> {
+        let mut bvh = Bvh {
+            order: (0..items.len()).collect(),
+            items: items,
+            nodes: Vec::new(),
+            leaf_size: leaf_size.max(1),
+        };
+
+        if !bvh.items.is_empty() {
+            bvh.build(0, bvh.items.len());
+        }
+        bvh
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Bounds of all the items, None when there are none.
+    pub fn bounds(&self) -> Option<Box3D<T>> {
+        self.nodes.first().map(|node| node.bounds)
+    }
+
+    pub fn get(&self, item: usize) -> Option<&V> {
+        self.items.get(item).map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, item: usize) -> Option<&mut V> {
+        self.items.get_mut(item).map(|(_, value)| value)
+    }
+
+    pub fn item_bounds(&self, item: usize) -> Option<Box3D<T>> {
+        self.items.get(item).map(|&(bounds, _)| bounds)
+    }
+
+    /// Moves the item, the tree stays stale until `refit` is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the item is out of range.
+    pub fn set_bounds(&mut self, item: usize, bounds: Box3D<T>) {
+        self.items[item].0 = bounds;
+    }
+
+    /// Recomputes the bounds of every node from the current boxes of the items.
+    pub fn refit(&mut self) {
+        for index in (0..self.nodes.len()).rev() {
+            let bounds = match self.nodes[index].kind {
+                Kind::Leaf(first, count) => self.leaf_bounds(first, count),
+                Kind::Inner(left, right) => self.nodes[left].bounds.union(&self.nodes[right].bounds),
+            };
+            self.nodes[index].bounds = bounds;
+        }
+    }
+
+    /// Items whose boxes overlap or touch the box, in no particular order.
+    pub fn query_box(&self, bounds: &Box3D<T>) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut stack = Vec::new();
+
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.intersects(bounds) {
+                continue;
+            }
+
+            match node.kind {
+                Kind::Leaf(first, count) => {
+                    let items = &self.order[first..first + count];
+                    found.extend(items.iter().filter(|&&item| self.items[item].0.intersects(bounds)));
+                }
+                Kind::Inner(left, right) => stack.extend(&[left, right]),
+            }
+        }
+        found
+    }
+
+    /// Nearest item whose box the ray hits.
+    pub fn cast_ray(&self, ray: &Ray<T>) -> Option<Hit<T>> {
+        self.cast_ray_with(ray, |_, bounds, _| ray.intersect_box(bounds))
+    }
+
+    /// Nearest item hit by the ray, as decided by `intersect`.
+    ///
+    /// The callback receives the index, box and payload of every item whose box the ray
+    /// enters closer than the best hit so far, and returns the distance of the hit along
+    /// the ray, if any. Hits must lie within the box of the item, e.g. on the triangle
+    /// it bounds, otherwise nearer hits may be missed.
+    pub fn cast_ray_with<F>(&self, ray: &Ray<T>, mut intersect: F) -> Option<Hit<T>>
+    where
+        F: FnMut(usize, &Box3D<T>, &V) -> Option<T>,
+    {
+        let mut best: Option<Hit<T>> = None;
+        let mut stack = Vec::new();
+
+        if let Some(distance) = self.nodes.first().and_then(|root| ray.intersect_box(&root.bounds)) {
+            stack.push((0, distance));
+        }
+
+        while let Some((index, distance)) = stack.pop() {
+            if best.is_some_and(|hit| hit.distance <= distance) {
+                continue;
+            }
+
+            match self.nodes[index].kind {
+                Kind::Leaf(first, count) => {
+                    for &item in &self.order[first..first + count] {
+                        let (ref bounds, ref value) = self.items[item];
+                        let closer = |distance: T| best.is_none_or(|hit| distance < hit.distance);
+
+                        if !ray.intersect_box(bounds).is_some_and(closer) {
+                            continue;
+                        }
+                        match intersect(item, bounds, value) {
+                            Some(distance) if distance >= T::zero() && closer(distance) => {
+                                best = Some(Hit {
+                                    item: item,
+                                    distance: distance,
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Kind::Inner(left, right) => {
+                    let left = ray.intersect_box(&self.nodes[left].bounds).map(|distance| (left, distance));
+                    let right = ray.intersect_box(&self.nodes[right].bounds).map(|distance| (right, distance));
+
+                    // The nearer child goes on top, so it is searched first.
+                    match (left, right) {
+                        (Some(left), Some(right)) if left.1 < right.1 => stack.extend(&[right, left]),
+                        (Some(left), Some(right)) => stack.extend(&[left, right]),
+                        (Some(child), None) | (None, Some(child)) => stack.push(child),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    fn leaf_bounds(&self, first: usize, count: usize) -> Box3D<T> {
+        let items = &self.order[first..first + count];
+        let bounds = self.items[items[0]].0;
+
+        items[1..]
+            .iter()
+            .fold(bounds, |bounds, &item| bounds.union(&self.items[item].0))
+    }
+
+    /// Builds the subtree over `order[first..first + count]` and returns its root.
+    fn build(&mut self, first: usize, count: usize) -> usize {
+        let index = self.nodes.len();
+        let bounds = self.leaf_bounds(first, count);
+
+        self.nodes.push(Node {
+            bounds: bounds,
+            kind: Kind::Leaf(first, count),
+        });
+
+        if count <= 1 {
+            return index;
+        }
+
+        let split = match self.find_split(first, count, &bounds) {
+            Some(split) => split,
+            None => return index,
+        };
+
+        let middle = self.partition(first, count, &split);
+        let left = self.build(first, middle - first);
+        let right = self.build(middle, first + count - middle);
+
+        self.nodes[index].kind = Kind::Inner(left, right);
+        index
+    }
+
+    /// Picks the bucket boundary with the lowest surface area heuristic, None when keeping
+    /// the items in one leaf is cheaper and allowed, or when their centres all coincide.
+    fn find_split(&self, first: usize, count: usize, bounds: &Box3D<T>) -> Option<Split<T>> {
+        let items = &self.order[first..first + count];
+        let centers = Box3D::from_points(items.iter().map(|&item| self.items[item].0.center()))?;
+
+        let mut best: Option<(T, Split<T>)> = None;
+        for axis in 0..3 {
+            let (min, max) = (coordinate(&centers.min, axis), coordinate(&centers.max, axis));
+            if min >= max {
+                continue;
+            }
+
+            let split = Split {
+                axis: axis,
+                min: min,
+                scale: T::from(BINS).unwrap() / (max - min),
+                bin: 0,
+            };
+
+            let mut bins: [Option<(Box3D<T>, usize)>; BINS] = [None; BINS];
+            for &item in items {
+                let bounds = self.items[item].0;
+                let bin = &mut bins[split.bin_of(&bounds.center())];
+
+                *bin = Some(match *bin {
+                    Some((union, count)) => (union.union(&bounds), count + 1),
+                    None => (bounds, 1),
+                });
+            }
+
+            // Sweeps from the right first, so the left sweep can price every boundary.
+            let mut right_costs = [T::zero(); BINS];
+            let mut accumulated: Option<(Box3D<T>, usize)> = None;
+            for bin in (1..BINS).rev() {
+                accumulated = merge(accumulated, bins[bin]);
+                right_costs[bin] = accumulated.map_or(T::zero(), |(union, count)| cost(&union, count));
+            }
+
+            accumulated = None;
+            for bin in 1..BINS {
+                accumulated = merge(accumulated, bins[bin - 1]);
+                let left_cost = accumulated.map_or(T::zero(), |(union, count)| cost(&union, count));
+                let cost = left_cost + right_costs[bin];
+
+                if best.as_ref().is_none_or(|&(best, _)| cost < best) {
+                    best = Some((cost, Split { bin: bin, ..split }));
+                }
+            }
+        }
+
+        // Weighted by the area of the node, a leaf costs one intersection per item and a
+        // split one traversal step plus the intersections of its children.
+        let (cost, split) = best?;
+        let area = bounds.surface_area();
+        if count <= self.leaf_size && area + cost >= area * T::from(count).unwrap() {
+            return None;
+        }
+        Some(split)
+    }
+
+    /// Moves the items left of the split to the front of the range, returns where the rest start.
+    fn partition(&mut self, first: usize, count: usize, split: &Split<T>) -> usize {
+        let mut middle = first;
+
+        for index in first..first + count {
+            let item = self.order[index];
+            if split.bin_of(&self.items[item].0.center()) < split.bin {
+                self.order.swap(index, middle);
+                middle += 1;
+            }
+        }
+
+        // Buckets that all end on one side can't happen, as the centres span the whole axis,
+        // but a half way split keeps the tree balanced should rounding ever cause it.
+        if middle == first || middle == first + count {
+            return first + count / 2;
+        }
+        middle
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Split<T> {
+    axis: usize,
+    min: T,
+    scale: T,
+    /// First bucket of the right side.
+    bin: usize,
+}
+
+impl<T: Float> Split<T> {
+    fn bin_of(&self, center: &Point3D<T>) -> usize {
+        let bin = ((coordinate(center, self.axis) - self.min) * self.scale)
+            .to_usize()
+            .unwrap_or(0);
+        bin.min(BINS - 1)
+    }
+}
+
+fn coordinate<T: Copy>(point: &Point3D<T>, axis: usize) -> T {
+    match axis {
+        0 => point.x,
+        1 => point.y,
+        _ => point.z,
+    }
+}
+
+fn merge<T: Float>(a: Option<(Box3D<T>, usize)>, b: Option<(Box3D<T>, usize)>) -> Option<(Box3D<T>, usize)> {
+    match (a, b) {
+        (Some((a, m)), Some((b, n))) => Some((a.union(&b), m + n)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn cost<T: Float>(bounds: &Box3D<T>, count: usize) -> T {
+    bounds.surface_area() * T::from(count).unwrap()
+}
@@ -0,0 +1,338 @@
+use point::{Point2D, Point3D};
+use size::{Size2D, Size3D};
+use spatial::kdtree::{KdPoint, Metric, SquaredEuclidean};
+
+use num_lib::{Float, Zero};
+
+/// Marks the end of a bucket list.
+const NONE: usize = usize::MAX;
+
+/// Point the grid can sort into cells of its size type.
+pub trait GridPoint: KdPoint {
+    type Size: Copy;
+
+    /// Extent of the cell along the axis, 0 being x.
+    fn extent(size: &Self::Size, axis: usize) -> Self::Scalar;
+}
+
+impl<T: Float> GridPoint for Point2D<T> {
+    type Size = Size2D<T>;
+
+    fn extent(size: &Size2D<T>, axis: usize) -> T {
+        if axis == 0 {
+            size.width
+        } else {
+            size.height
+        }
+    }
+}
+
+impl<T: Float> GridPoint for Point3D<T> {
+    type Size = Size3D<T>;
+
+    fn extent(size: &Size3D<T>, axis: usize) -> T {
+        match axis {
+            0 => size.width,
+            1 => size.height,
+            _ => size.depth,
+        }
+    }
+}
+
+/// Integer coordinates of a cell, unused axes stay zero.
+type Cell = [i64; 3];
+
+#[derive(Clone, Debug)]
+struct Entry<P> {
+    point: P,
+    cell: Cell,
+    live: bool,
+    previous: usize,
+    next: usize,
+}
+
+/// Uniform grid of equally sized cells hashing points into a fixed table of buckets.
+///
+/// Every bucket holds a doubly linked list threaded through the items, so moving an item
+/// to another cell relinks it in constant time. `update`, `remove` and the `for_each`
+/// queries never allocate, only `insert` grows the storage, and doubles the table when the
+/// items outnumber its buckets twice. Cells colliding in a bucket are told apart by their coordinates.
+///
+/// Radius queries scan the cells overlapping the query, or all items when those cells
+/// outnumber them, so they work for any radius but are cheapest with cells about the size
+/// of the typical radius. Coordinates have to be finite, cells are addressed by 64 bit integers.
+#[derive(Clone, Debug)]
+pub struct HashGrid<P: GridPoint> {
+    cell_size: P::Size,
+    buckets: Vec<usize>,
+    entries: Vec<Entry<P>>,
+    /// First removed item, the removed items are chained through `next`.
+    free: usize,
+    len: usize,
+}
+
+impl<P: GridPoint> HashGrid<P> {
+    /// Creates an empty grid with a table of 1024 buckets.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the cell size isn't positive and finite along every axis.
+    pub fn new(cell_size: P::Size) -> HashGrid<P> {
+        HashGrid::with_buckets(cell_size, 1024)
+    }
+
+    /// Creates an empty grid with a table of at least the given number of buckets.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the cell size isn't positive and finite along every axis.
+    pub fn with_buckets(cell_size: P::Size, buckets: usize) -> HashGrid<P> {
+        for axis in 0..P::DIMENSIONS {
+            let extent = P::extent(&cell_size, axis);
+            assert!(
+                extent > Zero::zero() && extent.is_finite(),
+                "cell size along axis {} isn't positive and finite",
+                axis
+            );
+        }
+
+        HashGrid {
+            cell_size: cell_size,
+            buckets: vec![NONE; buckets.max(1).next_power_of_two()],
+            entries: Vec::new(),
+            free: NONE,
+            len: 0,
+        }
+    }
+
+    pub fn cell_size(&self) -> P::Size {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all items, keeping the allocated storage.
+    pub fn clear(&mut self) {
+        for head in &mut self.buckets {
+            *head = NONE;
+        }
+        self.entries.clear();
+        self.free = NONE;
+        self.len = 0;
+    }
+
+    /// Adds the point and returns its item, removed items are handed out again.
+    pub fn insert(&mut self, point: P) -> usize {
+        if self.len >= 2 * self.buckets.len() {
+            self.rehash(2 * self.buckets.len());
+        }
+
+        let entry = Entry {
+            point: point,
+            cell: self.cell_of(&point),
+            live: true,
+            previous: NONE,
+            next: NONE,
+        };
+
+        let item = if self.free != NONE {
+            let item = self.free;
+            self.free = self.entries[item].next;
+            self.entries[item] = entry;
+            item
+        } else {
+            self.entries.push(entry);
+            self.entries.len() - 1
+        };
+
+        self.link(item);
+        self.len += 1;
+        item
+    }
+
+    /// Moves the item, relinking it only when it changes cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the item isn't in the grid.
+    pub fn update(&mut self, item: usize, point: P) {
+        assert!(self.contains(item), "item {} isn't in the grid", item);
+        let cell = self.cell_of(&point);
+
+        self.entries[item].point = point;
+        if self.entries[item].cell != cell {
+            self.unlink(item);
+            self.entries[item].cell = cell;
+            self.link(item);
+        }
+    }
+
+    /// Removes the item and returns its point, None when it isn't in the grid.
+    pub fn remove(&mut self, item: usize) -> Option<P> {
+        if !self.contains(item) {
+            return None;
+        }
+
+        self.unlink(item);
+        self.entries[item].live = false;
+        self.entries[item].next = self.free;
+        self.free = item;
+        self.len -= 1;
+
+        Some(self.entries[item].point)
+    }
+
+    pub fn contains(&self, item: usize) -> bool {
+        self.entries.get(item).is_some_and(|entry| entry.live)
+    }
+
+    pub fn get(&self, item: usize) -> Option<P> {
+        self.entries.get(item).filter(|entry| entry.live).map(|entry| entry.point)
+    }
+
+    /// Calls `visit` with every item within the Euclidean radius of the point, boundary
+    /// included, along with its distance.
+    pub fn for_each_within<F: FnMut(usize, P::Scalar)>(&self, point: &P, radius: P::Scalar, mut visit: F) {
+        let square_radius = radius * radius;
+
+        self.for_each_candidate(point, radius, |item, entry| {
+            let square_distance = SquaredEuclidean.distance(point, &entry.point);
+            if square_distance <= square_radius {
+                visit(item, square_distance.sqrt());
+            }
+        });
+    }
+
+    /// Items within the radius of the point, in no particular order.
+    pub fn within_radius(&self, point: &P, radius: P::Scalar) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.for_each_within(point, radius, |item, _| found.push(item));
+        found
+    }
+
+    /// Calls `visit` once for every pair of items at most the radius apart, with the
+    /// smaller item first. The pairs come in no particular order.
+    pub fn for_each_pair<F: FnMut(usize, usize)>(&self, radius: P::Scalar, mut visit: F) {
+        let square_radius = radius * radius;
+
+        for (item, entry) in self.entries.iter().enumerate().filter(|&(_, entry)| entry.live) {
+            self.for_each_candidate(&entry.point, radius, |other, candidate| {
+                if item < other && SquaredEuclidean.distance(&entry.point, &candidate.point) <= square_radius {
+                    visit(item, other);
+                }
+            });
+        }
+    }
+
+    /// Pairs of items at most the radius apart, in no particular order, see `for_each_pair`.
+    pub fn pairs(&self, radius: P::Scalar) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        self.for_each_pair(radius, |a, b| pairs.push((a, b)));
+        pairs
+    }
+
+    /// Visits the items in the cells overlapping the box around the point, or every item
+    /// when there are fewer of them than cells to scan.
+    fn for_each_candidate<F: FnMut(usize, &Entry<P>)>(&self, point: &P, radius: P::Scalar, mut visit: F) {
+        let (mut low, mut high) = ([0; 3], [0; 3]);
+        let mut cells: u128 = 1;
+
+        for axis in 0..P::DIMENSIONS {
+            let extent = P::extent(&self.cell_size, axis);
+            low[axis] = cell_index(point.coordinate(axis) - radius, extent);
+            high[axis] = cell_index(point.coordinate(axis) + radius, extent);
+
+            let span = (high[axis] as i128 - low[axis] as i128 + 1).max(0) as u128;
+            cells = cells.saturating_mul(span);
+        }
+
+        if cells > self.len as u128 {
+            for (item, entry) in self.entries.iter().enumerate().filter(|&(_, entry)| entry.live) {
+                visit(item, entry);
+            }
+            return;
+        }
+
+        for x in low[0]..=high[0] {
+            for y in low[1]..=high[1] {
+                for z in low[2]..=high[2] {
+                    let cell = [x, y, z];
+                    let mut item = self.buckets[self.bucket_of(&cell)];
+
+                    while item != NONE {
+                        let entry = &self.entries[item];
+                        if entry.cell == cell {
+                            visit(item, entry);
+                        }
+                        item = entry.next;
+                    }
+                }
+            }
+        }
+    }
+
+    fn cell_of(&self, point: &P) -> Cell {
+        let mut cell = [0; 3];
+
+        for (axis, index) in cell.iter_mut().enumerate().take(P::DIMENSIONS) {
+            *index = cell_index(point.coordinate(axis), P::extent(&self.cell_size, axis));
+        }
+        cell
+    }
+
+    /// Spreads the cells over the table with the primes of Teschner et al.
+    fn bucket_of(&self, cell: &Cell) -> usize {
+        let hash = (cell[0].wrapping_mul(73_856_093)) ^ (cell[1].wrapping_mul(19_349_663)) ^ (cell[2].wrapping_mul(83_492_791));
+        (hash as u64 as usize) & (self.buckets.len() - 1)
+    }
+
+    fn link(&mut self, item: usize) {
+        let bucket = self.bucket_of(&self.entries[item].cell);
+        let head = self.buckets[bucket];
+
+        self.entries[item].previous = NONE;
+        self.entries[item].next = head;
+        if head != NONE {
+            self.entries[head].previous = item;
+        }
+        self.buckets[bucket] = item;
+    }
+
+    fn unlink(&mut self, item: usize) {
+        let (previous, next) = (self.entries[item].previous, self.entries[item].next);
+
+        if previous != NONE {
+            self.entries[previous].next = next;
+        } else {
+            let bucket = self.bucket_of(&self.entries[item].cell);
+            self.buckets[bucket] = next;
+        }
+
+        if next != NONE {
+            self.entries[next].previous = previous;
+        }
+    }
+
+    fn rehash(&mut self, buckets: usize) {
+        self.buckets = vec![NONE; buckets];
+
+        for item in 0..self.entries.len() {
+            if self.entries[item].live {
+                self.link(item);
+            }
+        }
+    }
+}
+
+/// Index of the cell holding the coordinate, saturating beyond the range of `i64`.
+fn cell_index<T: Float>(coordinate: T, extent: T) -> i64 {
+    let index = (coordinate / extent).floor();
+
+    index.to_i64().unwrap_or(if index > T::zero() { i64::MAX } else { i64::MIN })
+}
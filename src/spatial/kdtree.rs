@@ -0,0 +1,294 @@
+use point::{Point2D, Point3D};
+
+use num_lib::{Float, Zero};
+use std::cmp::Ordering;
+
+/// Point with a fixed number of coordinates the tree can split along.
+pub trait KdPoint: Copy {
+    type Scalar: Float;
+
+    const DIMENSIONS: usize;
+
+    /// Coordinate along the axis, 0 being x.
+    fn coordinate(&self, axis: usize) -> Self::Scalar;
+}
+
+impl<T: Float> KdPoint for Point2D<T> {
+    type Scalar = T;
+
+    const DIMENSIONS: usize = 2;
+
+    fn coordinate(&self, axis: usize) -> T {
+        if axis == 0 {
+            self.x
+        } else {
+            self.y
+        }
+    }
+}
+
+impl<T: Float> KdPoint for Point3D<T> {
+    type Scalar = T;
+
+    const DIMENSIONS: usize = 3;
+
+    fn coordinate(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+}
+
+/// Distance function the searches rank points by.
+///
+/// The tree skips the far side of a split plane when the plane is further away than the
+/// current candidates, as measured by `axis_distance`. Implementations must therefore
+/// never report two points closer than the difference of any of their coordinates.
+pub trait Metric<P: KdPoint> {
+    fn distance(&self, a: &P, b: &P) -> P::Scalar;
+
+    /// Distance between points differing by `delta` along one axis only.
+    fn axis_distance(&self, delta: P::Scalar) -> P::Scalar;
+}
+
+/// Straight line distance.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Euclidean;
+
+/// Squared straight line distance, ranks like `Euclidean` without taking the square root.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SquaredEuclidean;
+
+/// Sum of the absolute coordinate differences.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Manhattan;
+
+/// Largest absolute coordinate difference.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Chebyshev;
+
+fn differences<P: KdPoint>(a: &P, b: &P) -> impl Iterator<Item = P::Scalar> {
+    let (a, b) = (*a, *b);
+    (0..P::DIMENSIONS).map(move |axis| a.coordinate(axis) - b.coordinate(axis))
+}
+
+impl<P: KdPoint> Metric<P> for Euclidean {
+    fn distance(&self, a: &P, b: &P) -> P::Scalar {
+        SquaredEuclidean.distance(a, b).sqrt()
+    }
+
+    fn axis_distance(&self, delta: P::Scalar) -> P::Scalar {
+        delta.abs()
+    }
+}
+
+impl<P: KdPoint> Metric<P> for SquaredEuclidean {
+    fn distance(&self, a: &P, b: &P) -> P::Scalar {
+        differences(a, b).fold(P::Scalar::zero(), |sum, delta| sum + delta * delta)
+    }
+
+    fn axis_distance(&self, delta: P::Scalar) -> P::Scalar {
+        delta * delta
+    }
+}
+
+impl<P: KdPoint> Metric<P> for Manhattan {
+    fn distance(&self, a: &P, b: &P) -> P::Scalar {
+        differences(a, b).fold(P::Scalar::zero(), |sum, delta| sum + delta.abs())
+    }
+
+    fn axis_distance(&self, delta: P::Scalar) -> P::Scalar {
+        delta.abs()
+    }
+}
+
+impl<P: KdPoint> Metric<P> for Chebyshev {
+    fn distance(&self, a: &P, b: &P) -> P::Scalar {
+        differences(a, b).fold(P::Scalar::zero(), |max, delta| max.max(delta.abs()))
+    }
+
+    fn axis_distance(&self, delta: P::Scalar) -> P::Scalar {
+        delta.abs()
+    }
+}
+
+/// Point found by a `KdTree` search.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Neighbour<T> {
+    /// Index of the point in the slice the tree was built from.
+    pub index: usize,
+    pub distance: T,
+}
+
+/// Static k-d tree over 2D or 3D points.
+///
+/// The tree is implicit, the points are reordered so that every range holds the median
+/// along its split axis in the middle, with the smaller coordinates before it. Ranges split
+/// along the axis their points spread the most on. Building selects the medians in linear
+/// time per level, so it takes O(n log n) overall.
+///
+/// Searches take the metric as an argument, so one tree serves every metric. Points with
+/// NaN coordinates end up in arbitrary places and make the results unspecified.
+#[derive(Clone, Debug)]
+pub struct KdTree<P> {
+    points: Vec<P>,
+    indices: Vec<usize>,
+    axes: Vec<u8>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+    pub fn new(points: &[P]) -> KdTree<P> {
+        let mut entries: Vec<(P, usize)> = points.iter().cloned().zip(0..).collect();
+        let mut axes = vec![0; points.len()];
+
+        build(&mut entries, &mut axes);
+
+        KdTree {
+            points: entries.iter().map(|&(point, _)| point).collect(),
+            indices: entries.iter().map(|&(_, index)| index).collect(),
+            axes: axes,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Closest point to the query, None when the tree is empty.
+    pub fn nearest<M: Metric<P>>(&self, query: &P, metric: &M) -> Option<Neighbour<P::Scalar>> {
+        self.nearest_k(query, 1, metric).pop()
+    }
+
+    /// Up to `count` points closest to the query, nearest first, equally distant points
+    /// ordered by their indices.
+    pub fn nearest_k<M: Metric<P>>(&self, query: &P, count: usize, metric: &M) -> Vec<Neighbour<P::Scalar>> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let mut collector = Collector::Nearest(Vec::with_capacity(count.min(self.len()) + 1), count);
+        self.search(0, self.len(), query, metric, &mut collector);
+        collector.into_found()
+    }
+
+    /// Points within the radius of the query, boundary included, in no particular order.
+    pub fn within_radius<M: Metric<P>>(&self, query: &P, radius: P::Scalar, metric: &M) -> Vec<Neighbour<P::Scalar>> {
+        let mut collector = Collector::Within(Vec::new(), radius);
+        self.search(0, self.len(), query, metric, &mut collector);
+        collector.into_found()
+    }
+
+    /// Offers the points of `start..end` to the collector, skipping the far side of a split
+    /// once the plane lies beyond the bound of the collector.
+    fn search<M: Metric<P>>(&self, start: usize, end: usize, query: &P, metric: &M, collector: &mut Collector<P::Scalar>) {
+        if start >= end {
+            return;
+        }
+
+        let middle = start + (end - start) / 2;
+        let (point, axis) = (&self.points[middle], self.axes[middle] as usize);
+
+        collector.offer(Neighbour {
+            index: self.indices[middle],
+            distance: metric.distance(query, point),
+        });
+
+        let delta = query.coordinate(axis) - point.coordinate(axis);
+        let (near, far) = if delta < P::Scalar::zero() {
+            ((start, middle), (middle + 1, end))
+        } else {
+            ((middle + 1, end), (start, middle))
+        };
+
+        self.search(near.0, near.1, query, metric, collector);
+        if collector.bound().is_none_or(|bound| metric.axis_distance(delta) <= bound) {
+            self.search(far.0, far.1, query, metric, collector);
+        }
+    }
+}
+
+/// Results of a search in progress.
+enum Collector<T> {
+    /// Keeps the given number of closest points, sorted by `rank`.
+    Nearest(Vec<Neighbour<T>>, usize),
+    /// Keeps the points within the radius.
+    Within(Vec<Neighbour<T>>, T),
+}
+
+impl<T: Float> Collector<T> {
+    fn offer(&mut self, neighbour: Neighbour<T>) {
+        match *self {
+            Collector::Nearest(ref mut found, count) => {
+                let position = found.partition_point(|other| rank(other, &neighbour) == Ordering::Less);
+                if position < count {
+                    found.insert(position, neighbour);
+                    found.truncate(count);
+                }
+            }
+            Collector::Within(ref mut found, radius) => {
+                if neighbour.distance <= radius {
+                    found.push(neighbour);
+                }
+            }
+        }
+    }
+
+    /// Distance beyond which no point is collected any more, None while it is unbounded.
+    fn bound(&self) -> Option<T> {
+        match *self {
+            Collector::Nearest(ref found, count) if found.len() == count => found.last().map(|worst| worst.distance),
+            Collector::Nearest(..) => None,
+            Collector::Within(_, radius) => Some(radius),
+        }
+    }
+
+    fn into_found(self) -> Vec<Neighbour<T>> {
+        match self {
+            Collector::Nearest(found, _) | Collector::Within(found, _) => found,
+        }
+    }
+}
+
+fn rank<T: PartialOrd>(a: &Neighbour<T>, b: &Neighbour<T>) -> Ordering {
+    a.distance
+        .partial_cmp(&b.distance)
+        .unwrap_or(Ordering::Equal)
+        .then(a.index.cmp(&b.index))
+}
+
+/// Arranges the entries into an implicit tree, recording the split axis of every median.
+fn build<P: KdPoint>(entries: &mut [(P, usize)], axes: &mut [u8]) {
+    if entries.len() <= 1 {
+        return;
+    }
+
+    let spread = |axis: usize| {
+        let coordinates = entries.iter().map(|entry| entry.0.coordinate(axis));
+        let (min, max) = coordinates.fold((P::Scalar::infinity(), P::Scalar::neg_infinity()), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+        max - min
+    };
+
+    let axis = (1..P::DIMENSIONS).fold(0, |best, axis| if spread(axis) > spread(best) { axis } else { best });
+    let middle = entries.len() / 2;
+
+    entries.select_nth_unstable_by(middle, |a, b| {
+        a.0.coordinate(axis)
+            .partial_cmp(&b.0.coordinate(axis))
+            .unwrap_or(Ordering::Equal)
+    });
+    axes[middle] = axis as u8;
+
+    let (entries_left, entries_right) = entries.split_at_mut(middle);
+    let (axes_left, axes_right) = axes.split_at_mut(middle);
+
+    build(entries_left, axes_left);
+    build(&mut entries_right[1..], &mut axes_right[1..]);
+}
@@ -0,0 +1,45 @@
+//! Spatial indices answering range and nearest-neighbour queries.
+
+pub mod bvh;
+pub mod hash_grid;
+pub mod kdtree;
+pub mod quadtree;
+
+pub use self::bvh::{Bvh, Hit, Ray};
+pub use self::hash_grid::{GridPoint, HashGrid};
+pub use self::kdtree::{Chebyshev, Euclidean, KdPoint, KdTree, Manhattan, Metric, Neighbour, SquaredEuclidean};
+pub use self::quadtree::{ItemId, QuadTree};
+
+use std::cmp::Ordering;
+
+/// Heap entry ranked by distance, the closest one on top of a `BinaryHeap`.
+///
+/// Ties go to the smaller key, so queries return equally distant items in a stable order.
+struct Ranked<T, K> {
+    distance: T,
+    key: K,
+}
+
+impl<T: PartialOrd, K: Ord> Ord for Ranked<T, K> {
+    fn cmp(&self, other: &Ranked<T, K>) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.key.cmp(&self.key))
+    }
+}
+
+impl<T: PartialOrd, K: Ord> PartialOrd for Ranked<T, K> {
+    fn partial_cmp(&self, other: &Ranked<T, K>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd, K: Ord> PartialEq for Ranked<T, K> {
+    fn eq(&self, other: &Ranked<T, K>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd, K: Ord> Eq for Ranked<T, K> {}
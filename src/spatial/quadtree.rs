@@ -0,0 +1,385 @@
+use point::Point2D;
+use rect::Rect;
+use size::Size2D;
+use spatial::Ranked;
+
+use num_lib::Float;
+use std::collections::BinaryHeap;
+use std::mem;
+
+/// Handle of an item stored in a `QuadTree`.
+///
+/// Stays valid until the item is removed, after which the tree may hand it out again.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ItemId(usize);
+
+#[derive(Clone, Debug)]
+struct Entry<T, V> {
+    bounds: Rect<T>,
+    value: V,
+    node: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    bounds: Rect<T>,
+    depth: usize,
+    parent: Option<usize>,
+    /// Index of the first of the four consecutive child nodes.
+    children: Option<usize>,
+    items: Vec<usize>,
+}
+
+/// Candidates of the nearest neighbour search, nodes rank before items at equal distance.
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+enum Candidate {
+    Node(usize),
+    Item(usize),
+}
+
+/// Region quadtree over items keyed by their bounding rect, a point being a rect of zero size.
+///
+/// Every item lives in the deepest node that fully contains its bounds, so items straddling
+/// the split lines of a node stay in the node itself. Items outside the bounds of the tree
+/// are kept in the root, they are still found by every query, just not as fast.
+///
+/// A leaf holding more than `capacity` items is split into quadrants, unless it is already
+/// at `max_depth`. Removing items merges the quadrants back once they fit into one node.
+/// All queries treat bounds as closed, items touching the query region are included.
+#[derive(Clone, Debug)]
+pub struct QuadTree<T, V> {
+    nodes: Vec<Node<T>>,
+    free_nodes: Vec<usize>,
+    entries: Vec<Option<Entry<T, V>>>,
+    free_entries: Vec<usize>,
+    len: usize,
+    max_depth: usize,
+    capacity: usize,
+}
+
+impl<T: Float, V> QuadTree<T, V> {
+    /// Creates an empty tree covering the bounds, limited to a depth of 8 and splitting
+    /// nodes that hold more than 8 items.
+    pub fn new(bounds: Rect<T>) -> QuadTree<T, V> {
+        QuadTree::with_limits(bounds, 8, 8)
+    }
+
+    /// Creates an empty tree with the maximal depth of its nodes, the root being at depth 0,
+    /// and the number of items a node holds before it splits.
+    pub fn with_limits(bounds: Rect<T>, max_depth: usize, capacity: usize) -> QuadTree<T, V> {
+        QuadTree {
+            nodes: vec![Node {
+                bounds: bounds,
+                depth: 0,
+                parent: None,
+                children: None,
+                items: Vec::new(),
+            }],
+            free_nodes: Vec::new(),
+            entries: Vec::new(),
+            free_entries: Vec::new(),
+            len: 0,
+            max_depth: max_depth,
+            capacity: capacity,
+        }
+    }
+
+    pub fn bounds(&self) -> Rect<T> {
+        self.nodes[0].bounds
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Depth of the deepest node in use, 0 while the root hasn't been split.
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut stack = vec![0];
+
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            depth = depth.max(node.depth);
+
+            if let Some(first) = node.children {
+                stack.extend(first..first + 4);
+            }
+        }
+        depth
+    }
+
+    /// Removes all items, keeping the bounds and limits.
+    pub fn clear(&mut self) {
+        *self = QuadTree::with_limits(self.bounds(), self.max_depth, self.capacity);
+    }
+
+    pub fn insert(&mut self, bounds: Rect<T>, value: V) -> ItemId {
+        let entry = Some(Entry {
+            bounds: bounds,
+            value: value,
+            node: 0,
+        });
+
+        let item = match self.free_entries.pop() {
+            Some(item) => {
+                self.entries[item] = entry;
+                item
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+
+        self.place(0, item);
+        self.len += 1;
+        ItemId(item)
+    }
+
+    /// Same as `insert` with a rect of zero size at the point.
+    pub fn insert_point(&mut self, point: Point2D<T>, value: V) -> ItemId {
+        self.insert(Rect::new(point, Size2D::zero()), value)
+    }
+
+    /// Removes the item and returns its value, None when the id isn't in use.
+    pub fn remove(&mut self, id: ItemId) -> Option<V> {
+        let entry = self.entries.get_mut(id.0)?.take()?;
+        let items = &mut self.nodes[entry.node].items;
+
+        if let Some(position) = items.iter().position(|&item| item == id.0) {
+            items.swap_remove(position);
+        }
+
+        self.free_entries.push(id.0);
+        self.len -= 1;
+        self.merge(entry.node);
+
+        Some(entry.value)
+    }
+
+    pub fn get(&self, id: ItemId) -> Option<&V> {
+        self.entries.get(id.0)?.as_ref().map(|entry| &entry.value)
+    }
+
+    pub fn get_mut(&mut self, id: ItemId) -> Option<&mut V> {
+        self.entries.get_mut(id.0)?.as_mut().map(|entry| &mut entry.value)
+    }
+
+    /// Bounds the item was inserted with.
+    pub fn item_bounds(&self, id: ItemId) -> Option<Rect<T>> {
+        self.entries.get(id.0)?.as_ref().map(|entry| entry.bounds)
+    }
+
+    /// Items whose bounds overlap or touch the rect, in no particular order.
+    pub fn query_rect(&self, rect: &Rect<T>) -> Vec<ItemId> {
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+
+            for &item in &node.items {
+                if overlaps(&self.entry(item).bounds, rect) {
+                    found.push(ItemId(item));
+                }
+            }
+
+            if let Some(first) = node.children {
+                stack.extend((first..first + 4).filter(|&child| overlaps(&self.nodes[child].bounds, rect)));
+            }
+        }
+        found
+    }
+
+    /// Items whose bounds contain the point, in no particular order.
+    pub fn query_point(&self, point: &Point2D<T>) -> Vec<ItemId> {
+        self.query_rect(&Rect::new(*point, Size2D::zero()))
+    }
+
+    /// Up to `count` items closest to the point, nearest first.
+    ///
+    /// The distance to an item is the one to the closest point of its bounds, zero when they
+    /// contain the point. Equally distant items are ordered by their ids.
+    pub fn nearest(&self, point: &Point2D<T>, count: usize) -> Vec<ItemId> {
+        let mut found = Vec::with_capacity(count.min(self.len));
+        let mut heap = BinaryHeap::new();
+
+        // The root holds the items outside the bounds as well, so its distance is unknown.
+        heap.push(Ranked {
+            distance: T::zero(),
+            key: Candidate::Node(0),
+        });
+
+        while found.len() < count {
+            let node = match heap.pop() {
+                Some(Ranked {
+                    key: Candidate::Item(item),
+                    ..
+                }) => {
+                    found.push(ItemId(item));
+                    continue;
+                }
+                Some(Ranked {
+                    key: Candidate::Node(node),
+                    ..
+                }) => &self.nodes[node],
+                None => break,
+            };
+
+            for &item in &node.items {
+                heap.push(Ranked {
+                    distance: square_distance(&self.entry(item).bounds, point),
+                    key: Candidate::Item(item),
+                });
+            }
+
+            if let Some(first) = node.children {
+                for child in first..first + 4 {
+                    heap.push(Ranked {
+                        distance: square_distance(&self.nodes[child].bounds, point),
+                        key: Candidate::Node(child),
+                    });
+                }
+            }
+        }
+        found
+    }
+
+    fn entry(&self, item: usize) -> &Entry<T, V> {
+        self.entries[item].as_ref().expect("nodes only refer to live items")
+    }
+
+    /// Stores the item in the deepest node below `node` that contains it, splitting the
+    /// node it ends up in when it overflows.
+    fn place(&mut self, mut node: usize, item: usize) {
+        let bounds = self.entry(item).bounds;
+
+        while let Some(first) = self.nodes[node].children {
+            match (first..first + 4).find(|&child| contains(&self.nodes[child].bounds, &bounds)) {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+
+        self.nodes[node].items.push(item);
+        if let Some(entry) = self.entries[item].as_mut() {
+            entry.node = node;
+        }
+
+        let leaf = &self.nodes[node];
+        if leaf.children.is_none() && leaf.items.len() > self.capacity && leaf.depth < self.max_depth {
+            self.split(node);
+        }
+    }
+
+    fn split(&mut self, node: usize) {
+        let (min, max, depth) = (
+            self.nodes[node].bounds.min(),
+            self.nodes[node].bounds.max(),
+            self.nodes[node].depth,
+        );
+        let two = T::one() + T::one();
+        let center = Point2D::new(min.x + (max.x - min.x) / two, min.y + (max.y - min.y) / two);
+
+        let quadrants = [
+            Rect::from_corners(min, center),
+            Rect::from_corners(Point2D::new(center.x, min.y), Point2D::new(max.x, center.y)),
+            Rect::from_corners(Point2D::new(min.x, center.y), Point2D::new(center.x, max.y)),
+            Rect::from_corners(center, max),
+        ];
+
+        let children = quadrants.iter().map(|&bounds| Node {
+            bounds: bounds,
+            depth: depth + 1,
+            parent: Some(node),
+            children: None,
+            items: Vec::new(),
+        });
+
+        let first = match self.free_nodes.pop() {
+            Some(first) => {
+                for (slot, child) in self.nodes[first..first + 4].iter_mut().zip(children) {
+                    *slot = child;
+                }
+                first
+            }
+            None => {
+                self.nodes.extend(children);
+                self.nodes.len() - 4
+            }
+        };
+
+        self.nodes[node].children = Some(first);
+        for item in mem::take(&mut self.nodes[node].items) {
+            self.place(node, item);
+        }
+    }
+
+    /// Folds the quadrants of the node and its ancestors back into them while they are
+    /// leaves holding no more than `capacity` items together.
+    fn merge(&mut self, mut node: usize) {
+        loop {
+            if let Some(first) = self.nodes[node].children {
+                let children = first..first + 4;
+
+                if children.clone().any(|child| self.nodes[child].children.is_some()) {
+                    return;
+                }
+                let count: usize = children.clone().map(|child| self.nodes[child].items.len()).sum();
+                if self.nodes[node].items.len() + count > self.capacity {
+                    return;
+                }
+
+                for child in children {
+                    for item in mem::take(&mut self.nodes[child].items) {
+                        if let Some(entry) = self.entries[item].as_mut() {
+                            entry.node = node;
+                        }
+                        self.nodes[node].items.push(item);
+                    }
+                }
+
+                self.nodes[node].children = None;
+                self.free_nodes.push(first);
+            }
+
+            match self.nodes[node].parent {
+                Some(parent) => node = parent,
+                None => return,
+            }
+        }
+    }
+}
+
+fn contains<T: Float>(outer: &Rect<T>, inner: &Rect<T>) -> bool {
+    let (outer_min, outer_max, inner_min, inner_max) = (outer.min(), outer.max(), inner.min(), inner.max());
+
+    outer_min.x <= inner_min.x && outer_min.y <= inner_min.y && inner_max.x <= outer_max.x && inner_max.y <= outer_max.y
+}
+
+fn overlaps<T: Float>(a: &Rect<T>, b: &Rect<T>) -> bool {
+    let (a_min, a_max, b_min, b_max) = (a.min(), a.max(), b.min(), b.max());
+
+    a_min.x <= b_max.x && b_min.x <= a_max.x && a_min.y <= b_max.y && b_min.y <= a_max.y
+}
+
+/// Squared distance from the point to the closest point of the rect.
+fn square_distance<T: Float>(rect: &Rect<T>, point: &Point2D<T>) -> T {
+    let (min, max) = (rect.min(), rect.max());
+    let dx = (min.x - point.x).max(point.x - max.x).max(T::zero());
+    let dy = (min.y - point.y).max(point.y - max.y).max(T::zero());
+
+    dx * dx + dy * dy
+}
@@ -0,0 +1,304 @@
+use matrix4d::{self, Matrix4D};
+
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+/// Matrix in double precision given as its rows, laid out like `Matrix4D::to_row_arrays`.
+pub type Rows = [[f64; 4]; 4];
+
+/// Single function of a transform list, angles are in radians.
+///
+/// Values are kept in double precision, so that large coordinates such as survey data can
+/// go through `Transform::to_rows` without losing digits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    Translate(f64, f64, f64),
+    Scale(f64, f64, f64),
+    /// Rotation around the axis `(x, y, z)` by the last value.
+    Rotate(f64, f64, f64, f64),
+    Skew(f64, f64),
+    /// Perspective projection with the viewer at the given positive distance along z.
+    Perspective(f64),
+    Matrix(Rows),
+}
+
+impl Operation {
+    /// Whether the operation keeps the plane on itself, see `Matrix4D::is_2d`.
+    pub fn is_2d(&self) -> bool {
+        self.to_matrix().is_2d()
+    }
+
+    pub fn to_matrix(&self) -> Matrix4D {
+        to_matrix(&self.to_rows())
+    }
+
+    /// Same matrix as `to_matrix` in double precision, see `Matrix4D` for the conventions.
+    #[rustfmt::skip]
+    pub fn to_rows(&self) -> Rows {
+        match *self {
+            Operation::Translate(x, y, z) => [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [x, y, z, 1.0],
+            ],
+            Operation::Scale(x, y, z) => [
+                [x, 0.0, 0.0, 0.0],
+                [0.0, y, 0.0, 0.0],
+                [0.0, 0.0, z, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            Operation::Rotate(x, y, z, angle) => matrix4d::rotation_rows(x, y, z, angle).unwrap_or(IDENTITY),
+            Operation::Skew(x, y) => [
+                [1.0, y.tan(), 0.0, 0.0],
+                [x.tan(), 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            Operation::Perspective(distance) => [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, -1.0 / distance],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            Operation::Matrix(rows) => rows,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The list isn't a sequence of `name(arguments)`, holds the offending text.
+    Syntax(String),
+    UnknownFunction(String),
+    /// The function got the wrong number of arguments, holds the function and the count.
+    ArgumentCount(String, usize),
+    InvalidNumber(String),
+    /// The angle has no unit or an unknown one, only zero may go without.
+    InvalidAngle(String),
+    /// The perspective distance isn't positive, which would divide by zero or flip the scene.
+    InvalidDistance(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Syntax(ref text) => write!(formatter, "expected 'function(arguments)' at '{}'", text),
+            ParseError::UnknownFunction(ref name) => write!(formatter, "unknown transform function '{}'", name),
+            ParseError::ArgumentCount(ref name, count) => {
+                write!(formatter, "'{}' doesn't take {} argument(s)", name, count)
+            }
+            ParseError::InvalidNumber(ref text) => write!(formatter, "invalid number '{}'", text),
+            ParseError::InvalidAngle(ref text) => {
+                write!(formatter, "invalid angle '{}', expected a unit of deg, rad, grad or turn", text)
+            }
+            ParseError::InvalidDistance(ref text) => {
+                write!(formatter, "invalid perspective distance '{}', expected a positive length", text)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Transform list in CSS syntax, e.g. `translate(10, 20) rotate(30deg) scale(2)`.
+///
+/// As in CSS the functions apply from right to left, so the example scales a point first
+/// and translates it last. Lengths may carry a `px` suffix, angles need one of the units
+/// `deg`, `rad`, `grad` or `turn`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transform {
+    pub operations: Vec<Operation>,
+}
+
+impl Transform {
+    pub fn parse(source: &str) -> Result<Transform, ParseError> {
+        let mut operations = Vec::new();
+        let mut rest = source.trim_start();
+
+        while !rest.is_empty() {
+            let open = rest.find('(').ok_or_else(|| ParseError::Syntax(rest.to_owned()))?;
+            let close = rest.find(')').ok_or_else(|| ParseError::Syntax(rest.to_owned()))?;
+            let name = rest[..open].trim();
+
+            if close < open || name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(ParseError::Syntax(rest.to_owned()));
+            }
+
+            let arguments: Vec<&str> = rest[open + 1..close]
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|argument| !argument.is_empty())
+                .collect();
+
+            operations.push(parse_function(name, &arguments)?);
+            rest = rest[close + 1..].trim_start();
+        }
+
+        Ok(Transform { operations: operations })
+    }
+
+    /// Whether every operation keeps the plane on itself.
+    pub fn is_2d(&self) -> bool {
+        self.operations.iter().all(Operation::is_2d)
+    }
+
+    /// Composes the operations into one matrix, see `Transform` for the order.
+    pub fn to_matrix(&self) -> Matrix4D {
+        to_matrix(&self.to_rows())
+    }
+
+    /// Composes the operations in double precision, see `to_matrix`.
+    pub fn to_rows(&self) -> Rows {
+        self.operations.iter().fold(IDENTITY, |rows, operation| mul(&rows, &operation.to_rows()))
+    }
+}
+
+impl FromStr for Transform {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Transform, ParseError> {
+        Transform::parse(source)
+    }
+}
+
+#[rustfmt::skip]
+const FUNCTIONS: [&str; 21] = [
+    "translate", "translatex", "translatey", "translatez", "translate3d",
+    "scale", "scalex", "scaley", "scalez", "scale3d",
+    "rotate", "rotatex", "rotatey", "rotatez", "rotate3d",
+    "skew", "skewx", "skewy", "perspective", "matrix", "matrix3d",
+];
+
+fn parse_function(name: &str, arguments: &[&str]) -> Result<Operation, ParseError> {
+    let count_error = || ParseError::ArgumentCount(name.to_owned(), arguments.len());
+    let lowercase = name.to_ascii_lowercase();
+
+    let operation = match (lowercase.as_str(), arguments.len()) {
+        ("translate", 1) => Operation::Translate(length(arguments[0])?, 0.0, 0.0),
+        ("translate", 2) => Operation::Translate(length(arguments[0])?, length(arguments[1])?, 0.0),
+        ("translatex", 1) => Operation::Translate(length(arguments[0])?, 0.0, 0.0),
+        ("translatey", 1) => Operation::Translate(0.0, length(arguments[0])?, 0.0),
+        ("translatez", 1) => Operation::Translate(0.0, 0.0, length(arguments[0])?),
+        ("translate3d", 3) => {
+            Operation::Translate(length(arguments[0])?, length(arguments[1])?, length(arguments[2])?)
+        }
+        ("scale", 1) => {
+            let scale = number(arguments[0])?;
+            Operation::Scale(scale, scale, 1.0)
+        }
+        ("scale", 2) => Operation::Scale(number(arguments[0])?, number(arguments[1])?, 1.0),
+        ("scalex", 1) => Operation::Scale(number(arguments[0])?, 1.0, 1.0),
+        ("scaley", 1) => Operation::Scale(1.0, number(arguments[0])?, 1.0),
+        ("scalez", 1) => Operation::Scale(1.0, 1.0, number(arguments[0])?),
+        ("scale3d", 3) => Operation::Scale(number(arguments[0])?, number(arguments[1])?, number(arguments[2])?),
+        ("rotate", 1) | ("rotatez", 1) => Operation::Rotate(0.0, 0.0, 1.0, angle(arguments[0])?),
+        ("rotatex", 1) => Operation::Rotate(1.0, 0.0, 0.0, angle(arguments[0])?),
+        ("rotatey", 1) => Operation::Rotate(0.0, 1.0, 0.0, angle(arguments[0])?),
+        ("rotate3d", 4) => Operation::Rotate(
+            number(arguments[0])?,
+            number(arguments[1])?,
+            number(arguments[2])?,
+            angle(arguments[3])?,
+        ),
+        ("skew", 1) => Operation::Skew(angle(arguments[0])?, 0.0),
+        ("skew", 2) => Operation::Skew(angle(arguments[0])?, angle(arguments[1])?),
+        ("skewx", 1) => Operation::Skew(angle(arguments[0])?, 0.0),
+        ("skewy", 1) => Operation::Skew(0.0, angle(arguments[0])?),
+        ("perspective", 1) => Operation::Perspective(distance(arguments[0])?),
+        // Both CSS matrices list the column-vector matrix column by column,
+        // which is the row-major order of this crate's row-vector matrices.
+        ("matrix", 6) => {
+            let values = numbers(arguments)?;
+            Operation::Matrix([
+                [values[0], values[1], 0.0, 0.0],
+                [values[2], values[3], 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [values[4], values[5], 0.0, 1.0],
+            ])
+        }
+        ("matrix3d", 16) => {
+            let values = numbers(arguments)?;
+            let mut rows = [[0.0; 4]; 4];
+
+            for (row, values) in rows.iter_mut().zip(values.chunks(4)) {
+                row.copy_from_slice(values);
+            }
+            Operation::Matrix(rows)
+        }
+        (name, _) if FUNCTIONS.contains(&name) => return Err(count_error()),
+        _ => return Err(ParseError::UnknownFunction(name.to_owned())),
+    };
+
+    Ok(operation)
+}
+
+fn number(text: &str) -> Result<f64, ParseError> {
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(ParseError::InvalidNumber(text.to_owned())),
+    }
+}
+
+fn numbers(texts: &[&str]) -> Result<Vec<f64>, ParseError> {
+    texts.iter().map(|text| number(text)).collect()
+}
+
+fn length(text: &str) -> Result<f64, ParseError> {
+    number(text.strip_suffix("px").unwrap_or(text))
+}
+
+fn distance(text: &str) -> Result<f64, ParseError> {
+    match length(text)? {
+        value if value > 0.0 => Ok(value),
+        _ => Err(ParseError::InvalidDistance(text.to_owned())),
+    }
+}
+
+fn angle(text: &str) -> Result<f64, ParseError> {
+    let units: [(&str, f64); 4] = [("deg", PI / 180.0), ("grad", PI / 200.0), ("rad", 1.0), ("turn", 2.0 * PI)];
+
+    // `grad` has to be tried before `rad`, which it ends with.
+    for &(unit, factor) in &units {
+        if let Some(value) = text.strip_suffix(unit) {
+            return number(value).map(|value| value * factor);
+        }
+    }
+
+    if number(text)? != 0.0 {
+        return Err(ParseError::InvalidAngle(text.to_owned()));
+    }
+    Ok(0.0)
+}
+
+#[rustfmt::skip]
+const IDENTITY: Rows = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// Same product as `Matrix4D::mul`, `other` applies first.
+fn mul(rows: &Rows, other: &Rows) -> Rows {
+    let mut product = [[0.0; 4]; 4];
+
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..4).map(|k| other[i][k] * rows[k][j]).sum();
+        }
+    }
+    product
+}
+
+fn to_matrix(rows: &Rows) -> Matrix4D {
+    let mut narrowed = [[0.0; 4]; 4];
+
+    for (narrowed, row) in narrowed.iter_mut().zip(rows) {
+        for (value, element) in narrowed.iter_mut().zip(row) {
+            *value = *element as f32;
+        }
+    }
+    Matrix4D::from_row_arrays(narrowed)
+}
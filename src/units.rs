@@ -0,0 +1,131 @@
+#[cfg(feature = "std")]
+use app_unit::AU_PER_PX;
+#[cfg(feature = "std")]
+use conversion::{ConversionError, UnitGraph};
+use length::Length;
+use point::TypedPoint2D;
+use rect::TypedRect;
+use scale_factor::ScaleFactor;
+use size::TypedSize2D;
+
+use num_lib::Float;
+
+// @section:begin Unit markers.
+
+/// CSS reference pixel, fixed at 96 per inch.
+#[derive(Clone, Copy, Debug)]
+pub enum CssPx {}
+
+/// Physical pixel of an output device, its size depends on the device resolution.
+#[derive(Clone, Copy, Debug)]
+pub enum DevicePx {}
+
+/// Typographic point, 72 per inch.
+#[derive(Clone, Copy, Debug)]
+pub enum Pt {}
+
+/// Pica, 12 points or 6 per inch.
+#[derive(Clone, Copy, Debug)]
+pub enum Pica {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Mm {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Cm {}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Inch {}
+
+// @section:end
+
+/// Unit with a fixed physical size.
+pub trait PhysicalUnit {
+    /// Number of units in one inch.
+    const PER_INCH: f64;
+}
+
+impl PhysicalUnit for CssPx {
+    const PER_INCH: f64 = 96.0;
+}
+
+impl PhysicalUnit for Pt {
+    const PER_INCH: f64 = 72.0;
+}
+
+impl PhysicalUnit for Pica {
+    const PER_INCH: f64 = 6.0;
+}
+
+impl PhysicalUnit for Mm {
+    const PER_INCH: f64 = 25.4;
+}
+
+impl PhysicalUnit for Cm {
+    const PER_INCH: f64 = 2.54;
+}
+
+impl PhysicalUnit for Inch {
+    const PER_INCH: f64 = 1.0;
+}
+
+// @section:begin Standard scale factors.
+
+pub const PX_PER_INCH: ScaleFactor<Inch, CssPx, f32> = ScaleFactor::new(96.0);
+pub const PT_PER_INCH: ScaleFactor<Inch, Pt, f32> = ScaleFactor::new(72.0);
+pub const PICA_PER_INCH: ScaleFactor<Inch, Pica, f32> = ScaleFactor::new(6.0);
+pub const MM_PER_INCH: ScaleFactor<Inch, Mm, f32> = ScaleFactor::new(25.4);
+pub const CM_PER_INCH: ScaleFactor<Inch, Cm, f32> = ScaleFactor::new(2.54);
+pub const PX_PER_PT: ScaleFactor<Pt, CssPx, f32> = ScaleFactor::new(4.0 / 3.0);
+pub const PT_PER_PICA: ScaleFactor<Pica, Pt, f32> = ScaleFactor::new(12.0);
+pub const MM_PER_CM: ScaleFactor<Cm, Mm, f32> = ScaleFactor::new(10.0);
+
+/// Scale factor between any two physical units, e.g. `factor::<Pt, Mm, f64>()`.
+pub fn factor<S: PhysicalUnit, D: PhysicalUnit, T: Float>() -> ScaleFactor<S, D, T> {
+    ScaleFactor::new(T::from(D::PER_INCH / S::PER_INCH).unwrap())
+}
+
+/// Scale factor from a physical unit to device pixels at the given resolution in dots per inch.
+pub fn device_factor<S: PhysicalUnit, T: Float>(dpi: T) -> ScaleFactor<S, DevicePx, T> {
+    ScaleFactor::new(dpi / T::from(S::PER_INCH).unwrap())
+}
+
+/// Scale factor from CSS to device pixels, as `window.devicePixelRatio` reports it.
+pub const fn device_pixel_ratio<T>(ratio: T) -> ScaleFactor<CssPx, DevicePx, T> {
+    ScaleFactor::new(ratio)
+}
+
+/// Graph of the standard units by their CSS names `in`, `px`, `pt`, `pc`, `mm` and `cm`,
+/// with `au` for app units and `dpx` for device pixels at the given dots per inch.
+///
+/// Fails only when the resolution is zero or not finite.
+#[cfg(feature = "std")]
+pub fn unit_graph<T: Float>(dpi: T) -> Result<UnitGraph<T>, ConversionError> {
+    let mut graph = UnitGraph::new();
+
+    graph.add("in", "px", factor::<Inch, CssPx, T>())?;
+    graph.add("in", "pt", factor::<Inch, Pt, T>())?;
+    graph.add("in", "pc", factor::<Inch, Pica, T>())?;
+    graph.add("in", "mm", factor::<Inch, Mm, T>())?;
+    graph.add("in", "cm", factor::<Inch, Cm, T>())?;
+    graph.add("px", "au", ScaleFactor::<CssPx, CssPx, T>::new(T::from(AU_PER_PX).unwrap()))?;
+    graph.add("in", "dpx", device_factor::<Inch, T>(dpi))?;
+
+    Ok(graph)
+}
+
+// @section:end
+
+// @section:begin Convenient aliases.
+
+pub type CssLength<T> = Length<CssPx, T>;
+pub type CssPoint2D<T> = TypedPoint2D<CssPx, T>;
+pub type CssSize2D<T> = TypedSize2D<CssPx, T>;
+pub type CssRect<T> = TypedRect<CssPx, T>;
+
+pub type DeviceLength<T> = Length<DevicePx, T>;
+pub type DevicePoint2D<T> = TypedPoint2D<DevicePx, T>;
+pub type DeviceSize2D<T> = TypedSize2D<DevicePx, T>;
+pub type DeviceRect<T> = TypedRect<DevicePx, T>;
+
+// @section:end
@@ -0,0 +1,165 @@
+extern crate matrix;
+extern crate num_traits;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::app_unit::{Au, Rounding, AU_PER_PX};
+    use matrix::length::Length;
+    use matrix::matrix2d::Matrix2D;
+    use matrix::point::{Point2D, TypedPoint2D};
+    use matrix::size::Size2D;
+    use matrix::num::Round;
+    use num_traits::{NumCast, One, Zero};
+
+    use std::panic;
+
+    #[derive(Debug, Copy, Clone)]
+    enum Px {}
+
+    #[test]
+    fn test_app_unit_conversions() {
+        assert_eq!(Au::from_px(2), Au(2 * AU_PER_PX));
+        assert_eq!(Au::from_f32_px(1.5, Rounding::Nearest), Some(Au(90)));
+        assert_eq!(Au(90).to_f32_px(), 1.5);
+        assert_eq!(Au(90).to_f64_px(), 1.5);
+        assert_eq!(Au::from_f64_px(f64::NAN, Rounding::Nearest), None);
+        assert_eq!(Au::from_f64_px(1.0e10, Rounding::Nearest), None);
+    }
+
+    #[test]
+    fn test_app_unit_rounding() {
+        let px = 0.0125; // 0.75au;
+
+        // do test;
+        assert_eq!(Au::from_f64_px(px, Rounding::Nearest), Some(Au(1)));
+        assert_eq!(Au::from_f64_px(px, Rounding::Floor), Some(Au(0)));
+        assert_eq!(Au::from_f64_px(px, Rounding::Ceil), Some(Au(1)));
+        assert_eq!(Au::from_f64_px(px, Rounding::Trunc), Some(Au(0)));
+        assert_eq!(Au::from_f64_px(-px, Rounding::Floor), Some(Au(-1)));
+        assert_eq!(Au::from_f64_px(-px, Rounding::Trunc), Some(Au(0)));
+
+        assert_eq!(Au(90).to_px(Rounding::Floor), 1);
+        assert_eq!(Au(90).to_px(Rounding::Ceil), 2);
+        assert_eq!(Au(-90).to_px(Rounding::Nearest), -2);
+
+        assert_eq!(Au(-90).round(), Au::from_px(-2));
+        assert_eq!(Au(-90).floor(), Au::from_px(-2));
+        assert_eq!(Au(-90).ceil(), Au::from_px(-1));
+        assert_eq!(Au(-90).trunc(), Au::from_px(-1));
+        assert_eq!(Au(120).ceil(), Au(120));
+    }
+
+    #[test]
+    fn test_app_unit_arithmetic() {
+        let a = Au::from_px(3);
+        let b = Au(30);
+
+        // do test;
+        assert_eq!(a + b, Au(210));
+        assert_eq!(a - b, Au(150));
+        assert_eq!(-b, Au(-30));
+        assert_eq!(a * Au::from_px(2), Au::from_px(6));
+        assert_eq!(a * b, Au(90));
+        assert_eq!(a / Au::from_px(2), Au(90));
+        assert_eq!(a * 2, Au::from_px(6));
+        assert_eq!(a / 4, Au(45));
+        assert!(b < a);
+    }
+
+    #[test]
+    fn test_app_unit_overflow() {
+        let large = Au(i32::MAX / 2);
+
+        // do test;
+        assert_eq!(Au::checked_from_px(i32::MAX / AU_PER_PX), Some(Au(i32::MAX / AU_PER_PX * AU_PER_PX)));
+        assert_eq!(Au::checked_from_px(i32::MAX / AU_PER_PX + 1), None);
+        assert_eq!(Au::checked_from_px(i32::MIN), None);
+        assert!(panic::catch_unwind(|| Au::from_px(i32::MAX)).is_err());
+        assert!(panic::catch_unwind(|| large * Au::from_px(3)).is_err());
+        assert!(panic::catch_unwind(|| large / Au(1)).is_err());
+        assert!(panic::catch_unwind(|| large * 3).is_err());
+        assert_eq!(large * Au::from_px(1), large);
+    }
+
+    #[test]
+    fn test_app_unit_limits() {
+        let (min, max) = (Au(i32::MIN), Au(i32::MAX));
+
+        // do test;
+        assert_eq!(max - Au(1) + Au(1), max);
+        assert_eq!(min + Au(1) - Au(1), min);
+        assert_eq!(-max, Au(-i32::MAX));
+        assert_eq!(min % Au(7), Au(i32::MIN % 7));
+        assert_eq!(min.ceil(), Au(-35_791_394 * AU_PER_PX));
+        assert_eq!(max.floor(), Au(35_791_394 * AU_PER_PX));
+        assert!(panic::catch_unwind(|| max + Au(1)).is_err());
+        assert!(panic::catch_unwind(|| min - Au(1)).is_err());
+        assert!(panic::catch_unwind(|| -min).is_err());
+        assert!(panic::catch_unwind(|| min % Au(-1)).is_err());
+        assert!(panic::catch_unwind(|| min / -1).is_err());
+        // The whole pixels beyond either end don't fit any more.
+        assert!(panic::catch_unwind(|| max.ceil()).is_err());
+        assert!(panic::catch_unwind(|| min.floor()).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_app_unit_division_by_zero() {
+        let _ = Au::from_px(1) / Au(0);
+    }
+
+    #[test]
+    fn test_app_unit_num_traits() {
+        assert_eq!(<Au as Zero>::zero(), Au(0));
+        assert_eq!(<Au as One>::one(), Au::from_px(1));
+        assert_eq!(<Au as NumCast>::from(2.5f32), Some(Au(150)));
+        assert_eq!(<f32 as NumCast>::from(Au(150)), Some(2.5));
+        assert_eq!(<i32 as NumCast>::from(Au(150)), Some(2));
+        assert_eq!(<u32 as NumCast>::from(Au(-150)), None);
+    }
+
+    #[test]
+    fn test_app_unit_length() {
+        let width: Length<Px, Au> = Length::new(Au::from_px(10));
+
+        // do test;
+        let half = Length::new(Au(300));
+        assert_eq!((width - half).get(), Au(300));
+        assert_eq!(width.cast::<f32>().unwrap().get(), 10.0);
+
+        let length: Length<Px, f64> = Length::new(0.5);
+        assert_eq!(length.cast::<Au>().unwrap().get(), Au(30));
+    }
+
+    #[test]
+    fn test_app_unit_point_and_size() {
+        let point = Point2D::new(Au(10), Au(20)) + Point2D::new(Au(5), Au(5));
+        let size = Size2D::new(Au::from_px(2), Au::from_px(3));
+
+        // do test;
+        assert_eq!(point, Point2D::new(Au(15), Au(25)));
+        assert_eq!(point.dot(Point2D::new(Au::from_px(1), Au::from_px(1))), Au(40));
+        assert_eq!(size.area(), Au::from_px(6));
+        assert_eq!(size * 2, Size2D::new(Au::from_px(4), Au::from_px(6)));
+
+        let typed: TypedPoint2D<Px, Au> = TypedPoint2D::typed(Au(60), Au(90));
+        assert_eq!(typed.cast::<f32>().unwrap(), TypedPoint2D::typed(1.0, 1.5));
+    }
+
+    #[test]
+    fn test_app_unit_matrix() {
+        let matrix: Matrix2D<Au> = Matrix2D::indentity()
+            .translate(Au(30), Au(-30))
+            .scale(Au::from_px(2), Au::from_px(3));
+
+        // do test;
+        assert_eq!(matrix.to_array(),
+                   [Au::from_px(2), Au(0), Au(0), Au::from_px(3), Au(30), Au(-30)]);
+        assert_eq!(matrix.transform_point(&Point2D::new(Au::from_px(1), Au::from_px(1))),
+                   Point2D::new(Au(150), Au(150)));
+        // Products are truncated to whole app units.
+        let half = Matrix2D::indentity().scale(Au(30), Au(30));
+        assert_eq!(half.transform_point(&Point2D::new(Au(7), Au(-7))), Point2D::new(Au(3), Au(-3)));
+    }
+}
@@ -0,0 +1,301 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::geometry::{boolean, clip_convex, signed_area, FillRule, Operation};
+    use matrix::point::Point2D;
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    use std::f64::consts::PI;
+
+    const OPERATIONS: [Operation; 4] = [
+        Operation::Union,
+        Operation::Intersection,
+        Operation::Difference,
+        Operation::Xor,
+    ];
+
+    fn polygon(coordinates: &[(f64, f64)]) -> Vec<Point2D<f64>> {
+        coordinates.iter().map(|&(x, y)| Point2D::new(x, y)).collect()
+    }
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Vec<Point2D<f64>> {
+        polygon(&[(x, y), (x + width, y), (x + width, y + height), (x, y + height)])
+    }
+
+    fn reversed(mut points: Vec<Point2D<f64>>) -> Vec<Point2D<f64>> {
+        points.reverse();
+        points
+    }
+
+    fn area(contours: &[Vec<Point2D<f64>>]) -> f64 {
+        contours.iter().map(|contour| signed_area(contour)).sum()
+    }
+
+    fn winding(contours: &[Vec<Point2D<f64>>], point: Point2D<f64>) -> i32 {
+        let mut winding = 0;
+        for contour in contours {
+            for (i, &a) in contour.iter().enumerate() {
+                let b = contour[(i + 1) % contour.len()];
+                let side = (b - a).cross(point - a);
+                if a.y <= point.y && b.y > point.y && side > 0.0 {
+                    winding += 1;
+                } else if b.y <= point.y && a.y > point.y && side < 0.0 {
+                    winding -= 1;
+                }
+            }
+        }
+        winding
+    }
+
+    fn covers(fill_rule: FillRule, winding: i32) -> bool {
+        match fill_rule {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+
+    /// Checks the result covers the right points once, sampling the bounding box.
+    fn check(
+        rng: &mut StdRng,
+        subject: &[Vec<Point2D<f64>>],
+        clip: &[Vec<Point2D<f64>>],
+        operation: Operation,
+        fill_rule: FillRule,
+    ) -> Vec<Vec<Point2D<f64>>> {
+        let result = boolean(subject, clip, operation, fill_rule);
+        let points = subject.iter().chain(clip).flat_map(|contour| contour.iter().cloned());
+        let (min, max) = points.fold((Point2D::new(1e9, 1e9), Point2D::new(-1e9, -1e9)), |(min, max), point| {
+            (min.min(point), max.max(point))
+        });
+
+        for _ in 0..500 {
+            let point = Point2D::new(
+                rng.gen_range(min.x - 1.0, max.x + 1.0),
+                rng.gen_range(min.y - 1.0, max.y + 1.0),
+            );
+            let inside = match operation {
+                Operation::Union => covers(fill_rule, winding(subject, point)) || covers(fill_rule, winding(clip, point)),
+                Operation::Intersection => covers(fill_rule, winding(subject, point)) && covers(fill_rule, winding(clip, point)),
+                Operation::Difference => covers(fill_rule, winding(subject, point)) && !covers(fill_rule, winding(clip, point)),
+                Operation::Xor => covers(fill_rule, winding(subject, point)) != covers(fill_rule, winding(clip, point)),
+            };
+            assert_eq!(winding(&result, point), inside as i32, "{:?} at {:?}", operation, point);
+        }
+        result
+    }
+
+    fn star(rng: &mut StdRng, center: Point2D<f64>, vertices: usize, min: f64, max: f64) -> Vec<Point2D<f64>> {
+        (0..vertices)
+            .map(|i| {
+                let angle = i as f64 * 2.0 * PI / vertices as f64;
+                center + Point2D::new(angle.cos(), angle.sin()) * rng.gen_range(min, max)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_convex_clipping() {
+        let square = vec![rect(0.0, 0.0, 2.0, 2.0)];
+        let diamond = polygon(&[(1.0, -0.5), (2.5, 1.0), (1.0, 2.5), (-0.5, 1.0)]);
+        let comb = polygon(&[
+            (0.0, 0.0),
+            (3.0, 0.0),
+            (3.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+        ]);
+
+        // do test;
+        let result = boolean(&square, &[reversed(diamond)], Operation::Intersection, FillRule::NonZero);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 8);
+        assert_eq!(area(&result), 3.5);
+
+        // The prongs of the comb stay joined along the top of the clip rect.
+        let clipped = clip_convex(&comb, &rect(-1.0, 1.5, 5.0, 5.0));
+        assert_eq!(signed_area(&clipped), 1.0);
+        assert_eq!(clip_convex(&square[0], &rect(3.0, 3.0, 1.0, 1.0)), vec![]);
+
+        // Clip polygons without three distinct vertices enclose nothing.
+        let (origin, corner) = (Point2D::new(0.0, 0.0), Point2D::new(2.0, 2.0));
+        assert_eq!(clip_convex(&square[0], &[]), vec![]);
+        assert_eq!(clip_convex(&square[0], &[origin, origin, origin]), vec![]);
+        assert_eq!(clip_convex(&square[0], &[origin, corner, origin, corner]), vec![]);
+    }
+
+    #[test]
+    fn test_shared_edges_and_vertices() {
+        let square = vec![rect(0.0, 0.0, 1.0, 1.0)];
+        let mut rng: StdRng = SeedableRng::from_seed(&[54][..]);
+
+        // do test;
+        let next = vec![rect(1.0, 0.0, 1.0, 1.0)];
+        let union = check(&mut rng, &square, &next, Operation::Union, FillRule::NonZero);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0], rotated_to(&union[0], rect(0.0, 0.0, 2.0, 1.0)));
+        assert!(check(&mut rng, &square, &next, Operation::Intersection, FillRule::NonZero).is_empty());
+        assert_eq!(area(&check(&mut rng, &square, &next, Operation::Xor, FillRule::NonZero)), 2.0);
+
+        for &operation in &OPERATIONS {
+            let result = check(&mut rng, &square, &square, operation, FillRule::NonZero);
+            let expected = if operation == Operation::Union || operation == Operation::Intersection {
+                1.0
+            } else {
+                0.0
+            };
+            assert_eq!(area(&result), expected);
+        }
+
+        // Touching at a corner, and along part of an edge.
+        let corner = check(
+            &mut rng,
+            &square,
+            &[rect(1.0, 1.0, 1.0, 1.0)],
+            Operation::Union,
+            FillRule::NonZero,
+        );
+        assert_eq!(corner.len(), 2);
+        let step = check(
+            &mut rng,
+            &[rect(0.0, 0.0, 2.0, 2.0)],
+            &[rect(2.0, 1.0, 1.0, 3.0)],
+            Operation::Union,
+            FillRule::NonZero,
+        );
+        assert_eq!(step.len(), 1);
+        assert_eq!(step[0].len(), 8);
+        assert_eq!(area(&step), 7.0);
+
+        // A square cut out of the middle of a side, and a diagonal cut through vertices.
+        let notch = check(
+            &mut rng,
+            &[rect(0.0, 0.0, 3.0, 3.0)],
+            &[rect(1.0, 2.0, 1.0, 1.0)],
+            Operation::Difference,
+            FillRule::NonZero,
+        );
+        assert_eq!(notch[0].len(), 8);
+        let triangle = polygon(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]);
+        let rest = check(&mut rng, &square, &[triangle], Operation::Difference, FillRule::NonZero);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].len(), 3);
+        assert_eq!(area(&rest), 0.5);
+    }
+
+    /// The expected contour starting where the actual one does.
+    fn rotated_to(actual: &[Point2D<f64>], mut expected: Vec<Point2D<f64>>) -> Vec<Point2D<f64>> {
+        if let Some(start) = expected.iter().position(|&point| point == actual[0]) {
+            expected.rotate_left(start);
+        }
+        expected
+    }
+
+    #[test]
+    fn test_holes_and_fill_rules() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[55][..]);
+        let frame = vec![rect(0.0, 0.0, 10.0, 10.0), reversed(rect(2.0, 2.0, 6.0, 6.0))];
+        let overlapping = vec![rect(0.0, 0.0, 2.0, 2.0), rect(1.0, 1.0, 2.0, 2.0)];
+        let pentagram = vec![(0..5)
+            .map(|i| {
+                let angle = i as f64 * 4.0 * PI / 5.0;
+                Point2D::new(angle.cos(), angle.sin()) * 5.0
+            })
+            .collect()];
+
+        // do test;
+        let result = check(
+            &mut rng,
+            &frame,
+            &[rect(4.0, 4.0, 2.0, 2.0)],
+            Operation::Union,
+            FillRule::NonZero,
+        );
+        assert_eq!(result.len(), 3);
+        assert_eq!(area(&result), 68.0);
+        let result = check(
+            &mut rng,
+            &frame,
+            &[rect(1.0, 1.0, 8.0, 8.0)],
+            Operation::Difference,
+            FillRule::EvenOdd,
+        );
+        assert_eq!(area(&result), 100.0 - 64.0);
+        check(
+            &mut rng,
+            &frame,
+            &[rect(-1.0, 3.0, 12.0, 1.0)],
+            Operation::Xor,
+            FillRule::NonZero,
+        );
+
+        let empty: &[Vec<Point2D<f64>>] = &[];
+        assert_eq!(
+            area(&check(&mut rng, &overlapping, empty, Operation::Union, FillRule::NonZero)),
+            7.0
+        );
+        assert_eq!(
+            area(&check(&mut rng, &overlapping, empty, Operation::Union, FillRule::EvenOdd)),
+            6.0
+        );
+
+        let filled = check(&mut rng, &pentagram, empty, Operation::Union, FillRule::NonZero);
+        let hollow = check(&mut rng, &pentagram, empty, Operation::Union, FillRule::EvenOdd);
+        assert_eq!((filled.len(), hollow.len()), (1, 5));
+        assert!(area(&filled) > area(&hollow));
+    }
+
+    #[test]
+    fn test_random_polygons() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[56][..]);
+
+        // do test;
+        for round in 0..40 {
+            let fill_rule = if round % 2 == 0 {
+                FillRule::NonZero
+            } else {
+                FillRule::EvenOdd
+            };
+            let mut subject = vec![star(&mut rng, Point2D::new(0.0, 0.0), 20, 5.0, 20.0)];
+            subject.push(reversed(star(&mut rng, Point2D::new(1.0, 0.0), 6, 1.0, 3.0)));
+            let center = Point2D::new(rng.gen_range(-10.0, 10.0), 0.0);
+            let mut clip = vec![star(&mut rng, center, 12, 5.0, 20.0)];
+
+            // Every fourth round snaps to a coarse grid and clips with a shifted copy, so that
+            // edges overlap and vertices coincide everywhere.
+            if round % 4 >= 2 {
+                for contour in &mut subject {
+                    for point in contour.iter_mut() {
+                        *point = Point2D::new(point.x.round(), point.y.round());
+                    }
+                }
+                let shift = Point2D::new(rng.gen_range(-3, 4) as f64, rng.gen_range(-3, 4) as f64);
+                clip = subject
+                    .iter()
+                    .map(|contour| contour.iter().map(|&point| point + shift).collect())
+                    .collect();
+            }
+
+            let areas: Vec<f64> = OPERATIONS
+                .iter()
+                .map(|&operation| area(&check(&mut rng, &subject, &clip, operation, fill_rule)))
+                .collect();
+            let empty: &[Vec<Point2D<f64>>] = &[];
+            let subject_area = area(&boolean(&subject, empty, Operation::Union, fill_rule));
+            let clip_area = area(&boolean(&clip, empty, Operation::Union, fill_rule));
+
+            let tolerance = 1e-9 * (subject_area + clip_area);
+            assert!((areas[0] + areas[1] - subject_area - clip_area).abs() < tolerance);
+            assert!((areas[2] - subject_area + areas[1]).abs() < tolerance);
+            assert!((areas[3] - areas[0] + areas[1]).abs() < tolerance);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+extern crate matrix;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::box3d::Box3D;
+    use matrix::point::Point3D;
+
+    #[test]
+    fn test_from_points() {
+        let points = vec![
+            Point3D::new(1.0, 5.0, -1.0),
+            Point3D::new(-2.0, 3.0, 4.0),
+            Point3D::new(0.0, 7.0, 0.0),
+        ];
+
+        // do test;
+        let bounds = Box3D::from_points(points).unwrap();
+        assert_eq!(bounds, Box3D::new(Point3D::new(-2.0, 3.0, -1.0), Point3D::new(1.0, 7.0, 4.0)));
+        assert_eq!(Box3D::<f32>::from_points(vec![]), None);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let bounds = Box3D::new(Point3D::new(0.0, 0.0, 0.0), Point3D::new(1.0, 1.0, 1.0));
+        let flat = Box3D::new(Point3D::new(0.0, 0.0, 0.0), Point3D::new(1.0, 1.0, 0.0));
+
+        // do test;
+        assert!(!bounds.is_empty());
+        assert!(flat.is_empty());
+        assert_eq!(format!("{:?}", bounds), "Box3D((0.0, 0.0, 0.0) to (1.0, 1.0, 1.0))");
+    }
+
+    #[test]
+    fn test_union_and_intersects() {
+        let a = Box3D::new(Point3D::new(0.0, 0.0, 0.0), Point3D::new(2.0, 2.0, 2.0));
+        let b = Box3D::new(Point3D::new(2.0, 1.0, -1.0), Point3D::new(3.0, 4.0, 1.0));
+        let c = Box3D::new(Point3D::new(2.5, 0.0, 0.0), Point3D::new(3.0, 0.5, 0.5));
+
+        // do test;
+        assert_eq!(a.union(&b), Box3D::new(Point3D::new(0.0, 0.0, -1.0), Point3D::new(3.0, 4.0, 2.0)));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&c));
+        assert!(a.contains(&Point3D::new(2.0, 0.0, 1.0)));
+        assert!(!a.contains(&Point3D::new(2.0, 0.0, 2.5)));
+    }
+
+    #[test]
+    fn test_center_and_surface_area() {
+        let bounds = Box3D::new(Point3D::new(0.0, 0.0, 0.0), Point3D::new(1.0, 2.0, 3.0));
+        let inverted = Box3D::new(Point3D::new(1.0, 0.0, 0.0), Point3D::new(0.0, 1.0, 1.0));
+
+        // do test;
+        assert_eq!(bounds.center(), Point3D::new(0.5, 1.0, 1.5));
+        assert_eq!(bounds.surface_area(), 22.0);
+        assert_eq!(inverted.surface_area(), 2.0);
+    }
+}
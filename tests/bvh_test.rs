@@ -0,0 +1,206 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::box3d::Box3D;
+    use matrix::point::Point3D;
+    use matrix::spatial::{Bvh, Hit, Ray};
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    fn cube(x: f64, y: f64, z: f64, size: f64) -> Box3D<f64> {
+        Box3D::new(Point3D::new(x, y, z), Point3D::new(x + size, y + size, z + size))
+    }
+
+    fn random_point(rng: &mut StdRng, range: f64) -> Point3D<f64> {
+        Point3D::new(
+            rng.gen_range(-range, range),
+            rng.gen_range(-range, range),
+            rng.gen_range(-range, range),
+        )
+    }
+
+    fn random_box(rng: &mut StdRng) -> Box3D<f64> {
+        let min = random_point(rng, 50.0);
+        let size = Point3D::new(rng.gen_range(0.0, 5.0), rng.gen_range(0.0, 5.0), rng.gen_range(0.0, 5.0));
+        Box3D::new(min, min + size)
+    }
+
+    fn random_ray(rng: &mut StdRng) -> Ray<f64> {
+        Ray::new(random_point(rng, 60.0), random_point(rng, 1.0))
+    }
+
+    fn nearest_box(boxes: &[Box3D<f64>], ray: &Ray<f64>) -> Option<f64> {
+        boxes
+            .iter()
+            .filter_map(|bounds| ray.intersect_box(bounds))
+            .fold(None, |best, distance| match best {
+                Some(best) if best <= distance => Some(best),
+                _ => Some(distance),
+            })
+    }
+
+    /// Distance along the ray to the sphere around `center`, None when it misses.
+    fn intersect_sphere(ray: &Ray<f64>, center: Point3D<f64>, radius: f64) -> Option<f64> {
+        let offset = ray.origin - center;
+        let (a, b, c) = (
+            ray.direction.dot(ray.direction),
+            offset.dot(ray.direction),
+            offset.dot(offset) - radius * radius,
+        );
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let (near, far) = ((-b - discriminant.sqrt()) / a, (-b + discriminant.sqrt()) / a);
+        if far < 0.0 {
+            return None;
+        }
+        Some(near.max(0.0))
+    }
+
+    fn check_queries(bvh: &Bvh<f64, usize>, boxes: &[Box3D<f64>], rng: &mut StdRng) {
+        for _ in 0..100 {
+            let ray = random_ray(rng);
+            assert_eq!(bvh.cast_ray(&ray).map(|hit| hit.distance), nearest_box(boxes, &ray));
+
+            if let Some(hit) = bvh.cast_ray(&ray) {
+                assert_eq!(ray.intersect_box(&boxes[hit.item]), Some(hit.distance));
+            }
+
+            let query = random_box(rng);
+            let mut found = bvh.query_box(&query);
+            found.sort();
+
+            let expected: Vec<usize> = (0..boxes.len()).filter(|&i| boxes[i].intersects(&query)).collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_intersect_box() {
+        let bounds = cube(0.0, 0.0, 0.0, 2.0);
+
+        // do test;
+        let ray = Ray::new(Point3D::new(-2.0, 1.0, 1.0), Point3D::new(2.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_box(&bounds), Some(1.0));
+        assert_eq!(ray.at(1.0), Point3D::new(0.0, 1.0, 1.0));
+
+        let inside = Ray::new(Point3D::new(1.0, 1.0, 1.0), Point3D::new(0.0, -1.0, 0.0));
+        assert_eq!(inside.intersect_box(&bounds), Some(0.0));
+
+        let behind = Ray::new(Point3D::new(4.0, 1.0, 1.0), Point3D::new(1.0, 0.0, 0.0));
+        assert_eq!(behind.intersect_box(&bounds), None);
+
+        let beside = Ray::new(Point3D::new(-2.0, 3.0, 1.0), Point3D::new(1.0, 0.0, 0.0));
+        assert_eq!(beside.intersect_box(&bounds), None);
+    }
+
+    #[test]
+    fn test_cast_ray() {
+        let items = vec![
+            (cube(0.0, 0.0, 0.0, 1.0), "near"),
+            (cube(5.0, 0.0, 0.0, 1.0), "far"),
+            (cube(0.0, 5.0, 0.0, 1.0), "aside"),
+        ];
+        let bvh = Bvh::with_leaf_size(items, 1);
+        let ray = Ray::new(Point3D::new(-1.0, 0.5, 0.5), Point3D::new(1.0, 0.0, 0.0));
+
+        // do test;
+        assert_eq!(bvh.len(), 3);
+        assert_eq!(
+            bvh.bounds(),
+            Some(Box3D::new(Point3D::new(0.0, 0.0, 0.0), Point3D::new(6.0, 6.0, 1.0)))
+        );
+        assert_eq!(bvh.cast_ray(&ray), Some(Hit { item: 0, distance: 1.0 }));
+        assert_eq!(bvh.get(0), Some(&"near"));
+
+        let skipping = bvh.cast_ray_with(
+            &ray,
+            |_, bounds, &name| if name == "near" { None } else { ray.intersect_box(bounds) },
+        );
+        assert_eq!(skipping, Some(Hit { item: 1, distance: 6.0 }));
+
+        let empty: Bvh<f64, ()> = Bvh::new(vec![]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.bounds(), None);
+        assert_eq!(empty.cast_ray(&ray), None);
+        assert!(empty.query_box(&cube(0.0, 0.0, 0.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_coincident_boxes() {
+        let items = (0..20).map(|i| (cube(0.0, 0.0, 0.0, 1.0), i)).collect();
+        let bvh = Bvh::with_leaf_size(items, 2);
+
+        // do test;
+        assert_eq!(bvh.query_box(&cube(0.5, 0.5, 0.5, 0.1)).len(), 20);
+        assert!(bvh.query_box(&cube(2.0, 0.0, 0.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[45][..]);
+        let boxes: Vec<Box3D<f64>> = (0..500).map(|_| random_box(&mut rng)).collect();
+        let bvh = Bvh::new(boxes.iter().cloned().zip(0..).collect());
+
+        // do test;
+        check_queries(&bvh, &boxes, &mut rng);
+    }
+
+    #[test]
+    fn test_refit() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[46][..]);
+        let mut boxes: Vec<Box3D<f64>> = (0..300).map(|_| random_box(&mut rng)).collect();
+        let mut bvh = Bvh::new(boxes.iter().cloned().zip(0..).collect());
+
+        // do test;
+        for _ in 0..3 {
+            for (item, bounds) in boxes.iter_mut().enumerate() {
+                let offset = random_point(&mut rng, 10.0);
+                *bounds = Box3D::new(bounds.min + offset, bounds.max + offset);
+                bvh.set_bounds(item, *bounds);
+            }
+
+            bvh.refit();
+            assert_eq!(bvh.item_bounds(7), Some(boxes[7]));
+            check_queries(&bvh, &boxes, &mut rng);
+        }
+    }
+
+    #[test]
+    fn test_cast_ray_with_spheres() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[47][..]);
+        let spheres: Vec<(Point3D<f64>, f64)> = (0..300)
+            .map(|_| (random_point(&mut rng, 50.0), rng.gen_range(0.1, 3.0)))
+            .collect();
+
+        let items = spheres
+            .iter()
+            .map(|&(center, radius)| {
+                let extent = Point3D::new(radius, radius, radius);
+                (Box3D::new(center - extent, center + extent), (center, radius))
+            })
+            .collect();
+        let bvh = Bvh::new(items);
+
+        // do test;
+        for _ in 0..200 {
+            let ray = random_ray(&mut rng);
+            let hit = bvh.cast_ray_with(&ray, |_, _, &(center, radius)| intersect_sphere(&ray, center, radius));
+
+            let expected = spheres
+                .iter()
+                .filter_map(|&(center, radius)| intersect_sphere(&ray, center, radius))
+                .fold(None, |best: Option<f64>, distance| {
+                    Some(best.map_or(distance, |best| best.min(distance)))
+                });
+            assert_eq!(hit.map(|hit| hit.distance), expected);
+        }
+    }
+}
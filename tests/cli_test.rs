@@ -0,0 +1,182 @@
+#![cfg(feature = "std")]
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{ErrorKind, Write};
+    use std::process::{Command, Output, Stdio};
+    use std::thread;
+
+    fn run<I: AsRef<[u8]>>(arguments: &[&str], input: I) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_matrix"))
+            .args(arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // Feeds the input from another thread so a command that stops reading early, or fills its
+        // output pipes first, can't block or break the test.
+        let mut stdin = child.stdin.take().unwrap();
+        let input = input.as_ref().to_vec();
+        let writer = thread::spawn(move || match stdin.write_all(&input) {
+            Err(ref error) if error.kind() == ErrorKind::BrokenPipe => Ok(()),
+            result => result,
+        });
+
+        let output = child.wait_with_output().unwrap();
+        writer.join().unwrap().unwrap();
+        output
+    }
+
+    fn stdout(output: &Output) -> String {
+        String::from_utf8(output.stdout.clone()).unwrap()
+    }
+
+    fn stderr(output: &Output) -> String {
+        String::from_utf8(output.stderr.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_compose_matrix() {
+        let output = run(&["compose", "translate(10, 20) scale(2)"], "");
+
+        // do test;
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), " 2  0\n 0  2\n10 20\n");
+
+        let output = run(&["compose", "translate(10, 20) scale(2)", "--json", "--3d"], "");
+        assert_eq!(stdout(&output),
+                   "{\"dimensions\": 3, \"rows\": [[2, 0, 0, 0], [0, 2, 0, 0], [0, 0, 1, 0], [10, 20, 0, 1]]}\n");
+    }
+
+    #[test]
+    fn test_compose_points() {
+        let output = run(&["compose", "translate(10, 20) rotate(90deg) scale(2)", "1,0", "0,1,5", "-"], "1 1\n\n");
+
+        // do test;
+        assert_eq!(stdout(&output), "10 22\n8 20 5\n8 22\n");
+
+        let output = run(&["compose", "translate(10, 20) rotate(90deg) scale(2)", "--invert", "--json", "10,22"], "");
+        assert_eq!(stdout(&output), "[[1, 0]]\n");
+    }
+
+    #[test]
+    fn test_compose_errors() {
+        let output = run(&["compose", "rotate(30)"], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("invalid angle '30'"));
+
+        let output = run(&["compose", "scale(0)", "--invert"], "");
+        assert_eq!(output.status.code(), Some(1));
+        let output = run(&["compose", "scale(1e30) scale(1e30) rotate(45deg)", "--invert"], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("not invertible"));
+        assert_eq!(stdout(&run(&["compose", "scale(1e-4)", "--invert", "1,1"], "")), "10000 10000\n");
+
+        let output = run(&["compose", "scale(1e30) scale(1e30)", "--json"], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("the transform overflows the range of numbers"));
+        assert!(stdout(&output).is_empty());
+
+        let output = run(&["compose", "scale(1e30)", "--json", "1,2", "4e9,1"], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("point '4000000000 1' maps beyond the range of numbers"));
+        assert!(stdout(&output).is_empty());
+
+        let output = run(&["compose", "perspective(0)", "1,2"], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("invalid perspective distance '0'"));
+
+        let output = run(&["compose", "scale(2)", "--bogus"], "");
+        assert_eq!(output.status.code(), Some(2));
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(stdout(&run(&["convert", "12pt", "--to", "dpx", "--scale", "2"], "")), "32\n");
+        assert_eq!(stdout(&run(&["convert", "1in", "--to", "dpx", "--dpi", "300"], "")), "300\n");
+        assert_eq!(stdout(&run(&["convert", "210mm", "1in", "--to", "px", "--round", "nearest", "--json"], "")),
+                   "[794, 96]\n");
+        assert_eq!(stdout(&run(&["convert", "-", "--from", "px", "--to", "au"], "10 20\n1.5\n")), "600\n1200\n90\n");
+
+        let output = run(&["convert", "1px", "1e308in", "--to", "au", "--json"], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("'1e308in' converts beyond the range of numbers"));
+        assert!(stdout(&output).is_empty());
+
+        let output = run(&["convert", "12em", "--to", "px"], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("unknown unit 'em'"));
+    }
+
+    #[test]
+    fn test_apply_keeps_extra_columns() {
+        let input = "x,y,name\n500000.125,4000000.5,\"pole, north\"\n\n# comment\n 1 , 2 ,a\n";
+        let output = run(&["apply", "--transform", "translate(-500000, -4000000)", "--header"], input);
+
+        // do test;
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), "x,y,name\n0.125,0.5,\"pole, north\"\n\n# comment\n -499999 , -3999998 ,a\n");
+
+        let output = run(&["apply", "--transform", "rotateX(90deg)", "--3d"], "1 2 3 extra  spaced\n4\t5\t6\n");
+        assert_eq!(stdout(&output), "1 -3 2 extra  spaced\n4\t-6\t5\n");
+    }
+
+    #[test]
+    fn test_apply_malformed_lines() {
+        let input = "1,2\nbad,3\n7\n3,4\n";
+
+        // do test;
+        let output = run(&["apply", "--transform", "scale(2)"], input);
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(stdout(&output), "2,4\n");
+        assert!(stderr(&output).contains("line 2: invalid number 'bad'"));
+
+        let output = run(&["apply", "--transform", "scale(2)", "--skip-invalid"], input);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), "2,4\n6,8\n");
+        assert!(stderr(&output).contains("line 3: expected at least 2 columns, found 1"));
+        assert!(stderr(&output).contains("skipped 2 malformed line(s)"));
+    }
+
+    #[test]
+    fn test_apply_keeps_double_precision() {
+        let survey = "500000.123,4500000.456,1\n";
+
+        // do test;
+        let output = run(&["apply", "--transform", "rotateX(1deg)", "--3d"], survey);
+        assert_eq!(stdout(&output), "500000.123,4499315.066682,78536.836774\n");
+
+        let output = run(&["apply", "--transform", "translate(-500000.123, -4500000.456)"], survey);
+        assert_eq!(stdout(&output), "0,0,1\n");
+        let output = run(&["apply", "--transform", "translate(-500000.123, 0)", "--invert"], "0.001,0\n");
+        assert_eq!(stdout(&output), "500000.124,0\n");
+
+        let output = run(&["apply", "--transform", "scale(1e300) scale(1e300)"], survey);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("overflows"));
+    }
+
+    #[test]
+    fn test_apply_quoted_fields_and_invalid_utf8() {
+        let input = "\"1.5\", \"2\",\"a, \"\"b\"\"\"\n\"1,5\",2\n\"3,4\n".to_owned();
+
+        // do test;
+        let output = run(&["apply", "--transform", "scale(2)", "--skip-invalid"], &input);
+        assert_eq!(stdout(&output), "\"3\", \"4\",\"a, \"\"b\"\"\"\n");
+        assert!(stderr(&output).contains("line 2: invalid number '1,5'"));
+        assert!(stderr(&output).contains("line 3: unterminated quote"));
+
+        let output = run(&["apply", "--transform", "scale(2)"], &b"# caf\xe9\n1 2\n\xff,3\n4,5\n"[..]);
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(output.stdout, b"# caf\xe9\n2 4\n");
+        assert!(stderr(&output).contains("line 3: invalid UTF-8"));
+
+        let output = run(&["apply", "--transform", "scale(2)", "--skip-invalid"], &b"\xff,3\n4,5\n"[..]);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), "8,10\n");
+        assert!(stderr(&output).contains("line 1: invalid UTF-8"));
+    }
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::approxeq::ApproxEq;
+    use matrix::conversion::{ConversionError, UnitGraph};
+    use matrix::length::Length;
+    use matrix::scale_factor::ScaleFactor;
+    use matrix::units::{self, CssPx, DevicePx, Inch, Mm};
+
+    fn screen(dpi: f64, zoom: f64) -> UnitGraph<f64> {
+        let mut graph = UnitGraph::new();
+
+        graph.add("in", "mm", units::factor::<Inch, Mm, f64>()).unwrap();
+        graph.add("in", "px", units::factor::<Inch, CssPx, f64>()).unwrap();
+        graph.add("in", "dev", units::device_factor::<Inch, f64>(dpi)).unwrap();
+        graph.add("px", "zoomed", ScaleFactor::<CssPx, CssPx, f64>::new(zoom)).unwrap();
+
+        graph
+    }
+
+    #[test]
+    fn test_direct_and_inverse_factors() {
+        let graph = screen(192.0, 1.0);
+
+        // do test;
+        assert_eq!(graph.factor("in", "mm").unwrap().get(), 25.4);
+        assert!(graph.factor("mm", "in").unwrap().get().approx_eq(&(1.0 / 25.4)));
+        assert_eq!(graph.factor("px", "px").unwrap().get(), 1.0);
+    }
+
+    #[test]
+    fn test_composed_factors() {
+        let graph = screen(192.0, 1.5);
+
+        // do test;
+        assert!(graph.factor("px", "dev").unwrap().get().approx_eq(&2.0));
+        assert!(graph.convert(25.4, "mm", "dev").unwrap().approx_eq(&192.0));
+        assert!(graph.convert(10.0, "zoomed", "px").unwrap().approx_eq(&(10.0 / 1.5)));
+
+        let length: Length<Mm, f64> = Length::new(25.4);
+        let device: Length<DevicePx, f64> = graph.convert_length(length, "mm", "dev").unwrap();
+        assert!(device.get().approx_eq(&192.0));
+    }
+
+    #[test]
+    fn test_consistent_cycle_is_accepted() {
+        let mut graph = screen(96.0, 1.0);
+
+        // do test;
+        assert_eq!(graph.add("px", "dev", ScaleFactor::<CssPx, DevicePx, f64>::new(1.0)), Ok(()));
+        assert_eq!(graph.add("mm", "px", units::factor::<Mm, CssPx, f64>()), Ok(()));
+    }
+
+    #[test]
+    fn test_inconsistent_cycle_is_rejected() {
+        let mut graph = screen(96.0, 1.0);
+
+        // do test;
+        let error = graph.add("px", "dev", ScaleFactor::<CssPx, DevicePx, f64>::new(2.0));
+        assert_eq!(error, Err(ConversionError::Inconsistent("px".to_owned(), "dev".to_owned())));
+        assert!(graph.factor("px", "dev").unwrap().get().approx_eq(&1.0));
+    }
+
+    #[test]
+    fn test_self_conversion_is_one() {
+        let mut graph: UnitGraph<f64> = UnitGraph::new();
+        let double = ScaleFactor::<CssPx, CssPx, f64>::new(2.0);
+
+        // do test;
+        let error = graph.add("pt", "pt", double);
+        assert_eq!(error, Err(ConversionError::Inconsistent("pt".to_owned(), "pt".to_owned())));
+        assert!(!graph.contains("pt"));
+        assert_eq!(graph.units().count(), 0);
+        assert_eq!(graph.add("pt", "pt", ScaleFactor::<CssPx, CssPx, f64>::new(1.0)), Ok(()));
+        assert!(graph.contains("pt"));
+        assert_eq!(graph.factor("pt", "pt").unwrap().get(), 1.0);
+
+        let mut graph = screen(96.0, 1.0);
+        let units: Vec<String> = graph.units().cloned().collect();
+        assert!(graph.add("px", "px", double).is_err());
+        assert!(graph.add("em", "em", double).is_err());
+        assert_eq!(graph.units().cloned().collect::<Vec<String>>(), units);
+        assert_eq!(graph.convert(3.0, "px", "px"), Ok(3.0));
+    }
+
+    #[test]
+    fn test_runtime_update() {
+        let mut graph = screen(96.0, 1.0);
+
+        // do test;
+        assert!(graph.remove("in", "dev"));
+        assert!(!graph.remove("in", "dev"));
+        assert_eq!(graph.factor("px", "dev"), Err(ConversionError::NoPath("px".to_owned(), "dev".to_owned())));
+
+        graph.add("px", "dev", ScaleFactor::<CssPx, DevicePx, f64>::new(3.0)).unwrap();
+        assert!(graph.factor("in", "dev").unwrap().get().approx_eq(&288.0));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut graph = screen(96.0, 1.0);
+        graph.add_unit("furlong");
+
+        // do test;
+        assert_eq!(graph.factor("px", "parsec"), Err(ConversionError::UnknownUnit("parsec".to_owned())));
+        assert_eq!(graph.factor("px", "furlong"),
+                   Err(ConversionError::NoPath("px".to_owned(), "furlong".to_owned())));
+        assert_eq!(graph.add("px", "furlong", ScaleFactor::<CssPx, CssPx, f64>::new(0.0)),
+                   Err(ConversionError::InvalidFactor("px".to_owned(), "furlong".to_owned())));
+        assert_eq!(format!("{}", graph.factor("px", "furlong").unwrap_err()),
+                   "no conversion from 'px' to 'furlong'");
+        assert_eq!(graph.units().count(), 6);
+    }
+}
@@ -0,0 +1,260 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::geometry::predicates::{incircle, orient2d};
+    use matrix::geometry::{signed_area, Delaunay};
+    use matrix::point::Point2D;
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    use std::collections::HashSet;
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point2D<f64>> {
+        coordinates.iter().map(|&(x, y)| Point2D::new(x, y)).collect()
+    }
+
+    fn edges(delaunay: &Delaunay) -> HashSet<(usize, usize)> {
+        delaunay
+            .triangles()
+            .iter()
+            .flat_map(|t| vec![(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .collect()
+    }
+
+    /// Checks orientation, coverage of the hull and the empty circumcircle property, skipping
+    /// circles across constrained edges.
+    fn check(delaunay: &Delaunay) {
+        let points = delaunay.points();
+        let hull: Vec<Point2D<f64>> = delaunay.hull().iter().map(|&i| points[i]).collect();
+        let mut area = 0.0;
+
+        for t in delaunay.triangles() {
+            let (a, b, c) = (points[t[0]], points[t[1]], points[t[2]]);
+            assert!(orient2d(a, b, c) > 0.0, "clockwise triangle {:?}", t);
+            area += signed_area(&[a, b, c]);
+        }
+        let expected = signed_area(&hull);
+        assert!(
+            (area - expected).abs() <= 1e-9 * expected.max(1.0),
+            "{} != {}",
+            area,
+            expected
+        );
+
+        // Every unconstrained edge inside the hull is locally Delaunay.
+        for t in delaunay.triangles() {
+            for i in 0..3 {
+                let (from, to) = (t[i], t[(i + 1) % 3]);
+                if delaunay.is_constrained(from, to) {
+                    continue;
+                }
+
+                let other = delaunay
+                    .triangles()
+                    .iter()
+                    .find(|o| (0..3).any(|j| o[j] == to && o[(j + 1) % 3] == from));
+                if let Some(other) = other {
+                    let far = *other.iter().find(|&&v| v != from && v != to).unwrap();
+                    let circle = incircle(points[t[0]], points[t[1]], points[t[2]], points[far]);
+                    assert!(circle <= 0.0, "edge ({}, {}) isn't locally Delaunay", from, to);
+                }
+            }
+        }
+    }
+
+    /// Checks no point lies strictly inside any circumcircle.
+    fn check_empty_circles(delaunay: &Delaunay) {
+        let points = delaunay.points();
+        for t in delaunay.triangles() {
+            for &point in points {
+                assert!(incircle(points[t[0]], points[t[1]], points[t[2]], point) <= 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_square_and_hull() {
+        let square = points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.5, 0.0)]);
+
+        // do test;
+        let delaunay = Delaunay::new(&square);
+        check(&delaunay);
+        assert_eq!(delaunay.triangles().len(), 3);
+
+        let mut hull = delaunay.hull();
+        let start = hull.iter().position(|&i| i == 0).unwrap();
+        hull.rotate_left(start);
+        assert_eq!(hull, vec![0, 4, 1, 2, 3]);
+
+        assert_eq!(delaunay.neighbours(4).len(), 4);
+    }
+
+    #[test]
+    fn test_cocircular_points() {
+        // Every integer point on the circle of radius 5, and its center.
+        let mut circle = points(&[
+            (5.0, 0.0),
+            (4.0, 3.0),
+            (3.0, 4.0),
+            (0.0, 5.0),
+            (-3.0, 4.0),
+            (-4.0, 3.0),
+            (-5.0, 0.0),
+            (-4.0, -3.0),
+            (-3.0, -4.0),
+            (0.0, -5.0),
+            (3.0, -4.0),
+            (4.0, -3.0),
+        ]);
+
+        // do test;
+        let delaunay = Delaunay::new(&circle);
+        check(&delaunay);
+        check_empty_circles(&delaunay);
+        assert_eq!(delaunay.triangles().len(), 10);
+
+        circle.push(Point2D::new(0.0, 0.0));
+        let delaunay = Delaunay::new(&circle);
+        check(&delaunay);
+        assert_eq!(delaunay.triangles().len(), 12);
+
+        let grid: Vec<Point2D<f64>> = (0..100).map(|i| Point2D::new((i % 10) as f64, (i / 10) as f64)).collect();
+        let delaunay = Delaunay::new(&grid);
+        check(&delaunay);
+        check_empty_circles(&delaunay);
+        assert_eq!(delaunay.triangles().len(), 2 * 81);
+    }
+
+    #[test]
+    fn test_nearly_cocircular_points() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[49][..]);
+
+        // do test;
+        for _ in 0..20 {
+            // Points on a circle off by a few units in the last place.
+            let points: Vec<Point2D<f64>> = (0..60)
+                .map(|_| {
+                    let angle = rng.gen_range(0.0f64, 2.0 * std::f64::consts::PI);
+                    let ulps = 1.0 + rng.gen_range(-4.0, 4.0) * f64::EPSILON;
+                    Point2D::new(1e3 + angle.cos() * ulps, 1e3 + angle.sin() * ulps)
+                })
+                .collect();
+
+            let delaunay = Delaunay::new(&points);
+            check(&delaunay);
+            check_empty_circles(&delaunay);
+        }
+    }
+
+    #[test]
+    fn test_random_points() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[50][..]);
+
+        // do test;
+        for round in 0..10 {
+            // Coarse coordinates so that collinear and cocircular points come up often.
+            let points: Vec<Point2D<f64>> = (0..50 + 50 * round)
+                .map(|_| Point2D::new(rng.gen_range(0, 20) as f64, rng.gen_range(0, 20) as f64))
+                .collect();
+
+            let delaunay = Delaunay::new(&points);
+            check(&delaunay);
+            check_empty_circles(&delaunay);
+
+            let distinct: HashSet<(u64, u64)> = points.iter().map(|p| (p.x.to_bits(), p.y.to_bits())).collect();
+            let hull = delaunay.hull().len();
+            assert_eq!(delaunay.triangles().len(), 2 * distinct.len() - hull - 2);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_input() {
+        let line = points(&[(0.0, 0.0), (2.0, 2.0), (1.0, 1.0), (3.0, 3.0), (1.0, 1.0)]);
+
+        // do test;
+        assert!(Delaunay::new(&[]).triangles().is_empty());
+
+        let delaunay = Delaunay::new(&line);
+        assert!(delaunay.triangles().is_empty());
+        assert_eq!(delaunay.hull(), vec![0, 2, 1, 3]);
+        assert_eq!(delaunay.neighbours(2), vec![0, 1]);
+        assert_eq!(delaunay.neighbours(4), vec![0, 1]);
+
+        let mut duplicated = line.clone();
+        duplicated.push(Point2D::new(0.0, 3.0));
+        duplicated.push(Point2D::new(0.0, 3.0));
+        let delaunay = Delaunay::new(&duplicated);
+        check(&delaunay);
+        assert_eq!(delaunay.triangles().len(), 3);
+        assert!(delaunay.triangles().iter().all(|t| !t.contains(&4) && !t.contains(&6)));
+    }
+
+    #[test]
+    fn test_constrained_edges() {
+        let mut grid: Vec<Point2D<f64>> = Vec::new();
+        for y in 0..5 {
+            for x in 0..9 {
+                // Nudges the rows so that the edge across isn't Delaunay by accident.
+                grid.push(Point2D::new(x as f64 + (y % 2) as f64 * 0.5, y as f64 * 0.9));
+            }
+        }
+
+        // do test;
+        let delaunay = Delaunay::with_constraints(&grid, &[(0, 43), (1, 2)]);
+        check(&delaunay);
+        assert!(delaunay.is_constrained(0, 43) && delaunay.is_constrained(43, 0));
+        assert!(delaunay.is_constrained(2, 1));
+        assert!(!delaunay.is_constrained(0, 1));
+        assert!(edges(&delaunay).contains(&(0, 43)) || edges(&delaunay).contains(&(43, 0)));
+        assert!(!Delaunay::new(&grid).is_constrained(0, 43));
+
+        // A constraint through a point gets split there.
+        let delaunay = Delaunay::with_constraints(&grid, &[(0, 4)]);
+        check(&delaunay);
+        assert!((0..4).all(|x| delaunay.is_constrained(x, x + 1)));
+    }
+
+    #[test]
+    fn test_random_constrained_edges() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[51][..]);
+
+        // do test;
+        for round in 0..20 {
+            // Odd rounds snap to a coarse grid, where constraints often run through points.
+            let points: Vec<Point2D<f64>> = (0..200)
+                .map(|_| {
+                    let point: Point2D<f64> = Point2D::new(rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 100.0));
+                    if round % 2 == 0 {
+                        point
+                    } else {
+                        Point2D::new((point.x / 4.0).floor(), (point.y / 4.0).floor())
+                    }
+                })
+                .collect();
+
+            // Spokes out of the first point never cross each other.
+            let constraints: Vec<(usize, usize)> = (1..10).map(|i| (0, i)).collect();
+            let delaunay = Delaunay::with_constraints(&points, &constraints);
+            check(&delaunay);
+            for &(a, b) in &constraints {
+                let points = delaunay.points();
+                let through = (0..200).any(|c| c != a && c != b && orient2d(points[a], points[b], points[c]) == 0.0);
+                assert!(through || delaunay.is_constrained(a, b));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cross")]
+    fn test_crossing_constraints() {
+        let square = points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+
+        // do test;
+        Delaunay::with_constraints(&square, &[(0, 2), (1, 3)]);
+    }
+}
@@ -0,0 +1,210 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts the allocations of the current thread, tests run in parallel threads.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::point::{Point2D, Point3D};
+    use matrix::size::{Size2D, Size3D};
+    use matrix::spatial::HashGrid;
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    fn random_point(rng: &mut StdRng) -> Point3D<f64> {
+        Point3D::new(
+            rng.gen_range(-50.0, 50.0),
+            rng.gen_range(-50.0, 50.0),
+            rng.gen_range(-20.0, 20.0),
+        )
+    }
+
+    fn sorted(mut pairs: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        pairs.sort();
+        pairs
+    }
+
+    fn brute_force_pairs(points: &[Option<Point3D<f64>>], radius: f64) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+
+        for (a, first) in points.iter().enumerate() {
+            for (b, second) in points.iter().enumerate().skip(a + 1) {
+                if let (Some(first), Some(second)) = (first, second) {
+                    if first.distance_to(*second) <= radius {
+                        pairs.push((a, b));
+                    }
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_insert_update_remove() {
+        let mut grid = HashGrid::new(Size2D::new(10.0, 10.0));
+
+        // do test;
+        let a = grid.insert(Point2D::new(1.0, 1.0));
+        let b = grid.insert(Point2D::new(4.0, 5.0));
+        let c = grid.insert(Point2D::new(-35.0, 12.0));
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.cell_size(), Size2D::new(10.0, 10.0));
+        assert_eq!(grid.pairs(5.0), vec![(a, b)]);
+
+        grid.update(c, Point2D::new(3.0, 3.0));
+        assert_eq!(grid.get(c), Some(Point2D::new(3.0, 3.0)));
+        assert_eq!(sorted(grid.pairs(5.0)), vec![(a, b), (a, c), (b, c)]);
+
+        assert_eq!(grid.remove(b), Some(Point2D::new(4.0, 5.0)));
+        assert_eq!(grid.remove(b), None);
+        assert!(!grid.contains(b));
+
+        let mut found = grid.within_radius(&Point2D::new(0.0, 0.0), 2.0);
+        found.sort();
+        assert_eq!(found, vec![a]);
+
+        assert_eq!(grid.insert(Point2D::new(100.0, 100.0)), b);
+        grid.clear();
+        assert!(grid.is_empty());
+        assert!(grid.pairs(1000.0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_update_removed_item() {
+        let mut grid = HashGrid::new(Size2D::new(1.0, 1.0));
+        let item = grid.insert(Point2D::new(0.0, 0.0));
+        grid.remove(item);
+
+        // do test;
+        grid.update(item, Point2D::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn test_large_radius_scans_items() {
+        let mut grid = HashGrid::new(Size3D::new(1.0, 1.0, 1.0));
+        let near = grid.insert(Point3D::new(0.5, 0.5, 0.5));
+        let far = grid.insert(Point3D::new(-2999.0, 10.0, 2000.0));
+        let mut flat = HashGrid::new(Size2D::new(1.0, 1.0));
+        flat.insert(Point2D::new(0.0, 0.0));
+
+        // do test;
+        // Visiting every cell in the box would take minutes.
+        let mut found = grid.within_radius(&Point3D::new(0.0, 0.0, 0.0), 1.0e6);
+        found.sort();
+        assert_eq!(found, vec![near, far]);
+        assert_eq!(grid.within_radius(&Point3D::new(0.0, 0.0, 0.0), 3000.0), vec![near]);
+        assert_eq!(grid.pairs(f64::INFINITY), vec![(near, far)]);
+        assert_eq!(flat.within_radius(&Point2D::new(0.0, 0.0), 3000.0).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't positive and finite")]
+    fn test_zero_cell_size() {
+        // do test;
+        HashGrid::<Point2D<f64>>::new(Size2D::new(1.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't positive and finite")]
+    fn test_non_finite_cell_size() {
+        // do test;
+        HashGrid::<Point3D<f64>>::new(Size3D::new(1.0, f64::NAN, 1.0));
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[47][..]);
+        let mut grid = HashGrid::with_buckets(Size3D::new(4.0, 4.0, 2.0), 8);
+        let mut points: Vec<Option<Point3D<f64>>> = Vec::new();
+
+        for _ in 0..400 {
+            let point = random_point(&mut rng);
+            assert_eq!(grid.insert(point), points.len());
+            points.push(Some(point));
+        }
+
+        // do test;
+        for round in 0..5 {
+            for (item, point) in points.iter_mut().enumerate() {
+                match *point {
+                    Some(_) if rng.gen_weighted_bool(10) => {
+                        grid.remove(item);
+                        *point = None;
+                    }
+                    Some(ref mut point) => {
+                        *point += Point3D::new(rng.gen_range(-3.0, 3.0), rng.gen_range(-3.0, 3.0), rng.gen_range(-1.0, 1.0));
+                        grid.update(item, *point);
+                    }
+                    None => {}
+                }
+            }
+
+            let radius = 2.0 + round as f64 * 1.5;
+            assert_eq!(sorted(grid.pairs(radius)), brute_force_pairs(&points, radius));
+
+            for _ in 0..20 {
+                let query = random_point(&mut rng);
+                let mut found = grid.within_radius(&query, radius);
+                found.sort();
+
+                let expected: Vec<usize> = (0..points.len())
+                    .filter(|&item| points[item].is_some_and(|point| point.distance_to(query) <= radius))
+                    .collect();
+                assert_eq!(found, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_does_not_allocate() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[48][..]);
+        let mut grid = HashGrid::new(Size3D::new(5.0, 5.0, 5.0));
+        let items: Vec<usize> = (0..1000).map(|_| grid.insert(random_point(&mut rng))).collect();
+        let targets: Vec<Point3D<f64>> = (0..1000).map(|_| random_point(&mut rng)).collect();
+
+        // do test;
+        let before = super::allocations();
+        let mut pairs = 0;
+
+        for (&item, &target) in items.iter().zip(&targets) {
+            grid.update(item, target);
+        }
+        grid.remove(items[0]);
+        grid.for_each_pair(5.0, |_, _| pairs += 1);
+        grid.for_each_within(&targets[1], 5.0, |_, _| pairs += 1);
+
+        assert_eq!(super::allocations(), before);
+        assert!(pairs > 0);
+    }
+}
@@ -0,0 +1,120 @@
+extern crate matrix;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::approxeq::ApproxEq;
+    use matrix::interpolate::{self, CubicBezier, Interpolate};
+    use matrix::length::Length;
+    use matrix::matrix2d::Matrix2D;
+    use matrix::matrix4d::Matrix4D;
+    use matrix::point::{Point2D, Point3D, Point4D, TypedPoint2D};
+    use matrix::scale_factor::ScaleFactor;
+    use matrix::size::Size2D;
+
+    #[derive(Debug, Copy, Clone)]
+    enum Mm {}
+
+    #[test]
+    fn test_lerp_scalars() {
+        let from: Length<Mm, f32> = Length::new(10.0);
+        let to: Length<Mm, f32> = Length::new(20.0);
+
+        // do test;
+        assert_eq!(from.lerp(&to, 0.0), from);
+        assert_eq!(from.lerp(&to, 1.0), to);
+        assert_eq!(from.lerp(&to, 0.25), Length::new(12.5));
+        assert_eq!(from.lerp(&to, 2.0), Length::new(30.0));
+
+        let scale: ScaleFactor<Mm, Mm, f64> = ScaleFactor::new(1.0);
+        assert_eq!(scale.lerp(&ScaleFactor::new(3.0), 0.5), ScaleFactor::new(2.0));
+    }
+
+    #[test]
+    fn test_lerp_points_and_sizes() {
+        assert_eq!(Point2D::new(0.0, 10.0).lerp(&Point2D::new(10.0, 0.0), 0.5),
+                   Point2D::new(5.0, 5.0));
+        assert_eq!(Point3D::new(0.0, 0.0, 0.0).lerp(&Point3D::new(4.0, 8.0, -4.0), 0.25),
+                   Point3D::new(1.0, 2.0, -1.0));
+        assert_eq!(Point4D::new(0.0, 0.0, 0.0, 1.0).lerp(&Point4D::new(2.0, 2.0, 2.0, 3.0), 0.5),
+                   Point4D::new(1.0, 1.0, 1.0, 2.0));
+        assert_eq!(Size2D::new(10.0, 10.0).lerp(&Size2D::new(20.0, 30.0), 0.5),
+                   Size2D::new(15.0, 20.0));
+
+        let typed: TypedPoint2D<Mm, f32> = TypedPoint2D::typed(0.0, 0.0);
+        assert_eq!(typed.lerp(&TypedPoint2D::typed(2.0, 4.0), 0.5), TypedPoint2D::typed(1.0, 2.0));
+
+        // The inherent point methods forward to the trait.
+        let (from, to) = (Point2D::new(0.1f64, 0.7), Point2D::new(-3.3, 9.9));
+        assert_eq!(from.lerp(&to, 0.3), Interpolate::lerp(&from, &to, 0.3));
+        let (from, to) = (Point3D::new(0.1f32, 0.7, 2.0), Point3D::new(-3.3, 9.9, 0.1));
+        assert_eq!(from.lerp(&to, 0.3), Interpolate::lerp(&from, &to, 0.3));
+    }
+
+    #[test]
+    fn test_lerp_matrices() {
+        let from = Matrix2D::indentity();
+        let to = Matrix2D::indentity().translate(10.0, 20.0);
+
+        // do test;
+        let middle = from.lerp(&to, 0.5);
+        assert_eq!(middle.to_array(), [1.0, 0.0, 0.0, 1.0, 5.0, 10.0]);
+
+        let from = Matrix4D::indentity();
+        let to = Matrix4D::indentity().scale(3.0, 3.0, 3.0);
+        assert!(from.lerp(&to, 0.5).approx_eq(&Matrix4D::indentity().scale(2.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn test_easing_curves() {
+        assert_eq!(interpolate::linear(0.3), 0.3);
+        assert_eq!(interpolate::ease_in(0.5), 0.25);
+        assert_eq!(interpolate::ease_out(0.5), 0.75);
+        assert_eq!(interpolate::ease_in_out(0.5), 0.5);
+        assert_eq!(interpolate::ease_in_out(0.0), 0.0);
+        assert_eq!(interpolate::ease_in_out(1.0), 1.0);
+
+        let from = Point2D::new(0.0, 0.0);
+        let to = Point2D::new(100.0, 100.0);
+        assert_eq!(from.ease(&to, 0.5, interpolate::ease_in), Point2D::new(25.0, 25.0));
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        let linear = CubicBezier::new(0.0, 0.0, 1.0, 1.0);
+        let ease = CubicBezier::new(0.25, 0.1, 0.25, 1.0);
+
+        // do test;
+        for i in 0..11 {
+            let t = i as f64 / 10.0;
+            assert!(linear.ease(t).approx_eq(&t));
+        }
+
+        assert!(ease.ease(0.0).approx_eq(&0.0));
+        assert!(ease.ease(1.0).approx_eq(&1.0));
+        assert!(ease.ease(0.5).approx_eq_eps(&0.8024033877399112, &1.0e-4));
+
+        let from: Length<Mm, f64> = Length::new(0.0);
+        let to: Length<Mm, f64> = Length::new(10.0);
+        let eased = from.ease(&to, 0.5, |t| ease.ease(t));
+        assert!(eased.get().approx_eq_eps(&8.024033877399112, &1.0e-3));
+    }
+
+    #[test]
+    fn test_cubic_bezier_steep_control_points() {
+        // Equal x and y control points trace the diagonal, however steep the parametrisation.
+        let diagonal = CubicBezier::new(1.0, 1.0, 0.0, 0.0);
+        let steep = CubicBezier::new(1.0, 0.0, 0.0, 1.0);
+
+        // do test;
+        let mut last = 0.0;
+        for i in 0..1001 {
+            let t = i as f64 / 1000.0;
+            assert!(diagonal.ease(t).approx_eq_eps(&t, &1.0e-6), "{} at {}", diagonal.ease(t), t);
+
+            let eased = steep.ease(t);
+            assert!(eased >= last && eased <= 1.0, "{} at {}", eased, t);
+            last = eased;
+        }
+    }
+}
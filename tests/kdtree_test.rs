@@ -0,0 +1,166 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::point::{Point2D, Point3D};
+    use matrix::spatial::{Chebyshev, Euclidean, KdPoint, KdTree, Manhattan, Metric, Neighbour, SquaredEuclidean};
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    /// Euclidean distance with the z axis stretched, e.g. for elevation in a point cloud.
+    struct Stretched(f64);
+
+    impl Metric<Point3D<f64>> for Stretched {
+        fn distance(&self, a: &Point3D<f64>, b: &Point3D<f64>) -> f64 {
+            let delta = *a - *b;
+            (delta.x * delta.x + delta.y * delta.y + delta.z * delta.z * self.0 * self.0).sqrt()
+        }
+
+        fn axis_distance(&self, delta: f64) -> f64 {
+            // The stretched axis only grows, so the plain difference stays a lower bound.
+            delta.abs()
+        }
+    }
+
+    fn random_points(rng: &mut StdRng, count: usize) -> Vec<Point3D<f64>> {
+        (0..count)
+            .map(|_| {
+                Point3D::new(
+                    rng.gen_range(-100.0, 100.0),
+                    rng.gen_range(-100.0, 100.0),
+                    rng.gen_range(-10.0, 10.0),
+                )
+            })
+            .collect()
+    }
+
+    fn brute_force<P: KdPoint, M: Metric<P>>(points: &[P], query: &P, metric: &M) -> Vec<Neighbour<P::Scalar>> {
+        let mut all: Vec<Neighbour<P::Scalar>> = points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| Neighbour {
+                index,
+                distance: metric.distance(query, point),
+            })
+            .collect();
+
+        all.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap().then(a.index.cmp(&b.index)));
+        all
+    }
+
+    fn check_metric<M: Metric<Point3D<f64>>>(tree: &KdTree<Point3D<f64>>, points: &[Point3D<f64>], metric: &M, radius: f64) {
+        let mut rng: StdRng = SeedableRng::from_seed(&[46][..]);
+
+        for _ in 0..50 {
+            let query = random_points(&mut rng, 1)[0];
+            let expected = brute_force(points, &query, metric);
+            let count = rng.gen_range(1, 20);
+
+            assert_eq!(tree.nearest(&query, metric), Some(expected[0]));
+            assert_eq!(tree.nearest_k(&query, count, metric), &expected[..count]);
+
+            let mut within = tree.within_radius(&query, radius, metric);
+            within.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap().then(a.index.cmp(&b.index)));
+
+            let expected: Vec<_> = expected
+                .into_iter()
+                .take_while(|neighbour| neighbour.distance <= radius)
+                .collect();
+            assert_eq!(within, expected);
+        }
+    }
+
+    #[test]
+    fn test_nearest() {
+        let points = [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(0.0, 10.0),
+            Point2D::new(3.0, 4.0),
+        ];
+        let tree = KdTree::new(&points);
+
+        // do test;
+        assert_eq!(tree.len(), 4);
+        assert_eq!(
+            tree.nearest(&Point2D::new(2.0, 2.0), &Euclidean),
+            Some(Neighbour {
+                index: 3,
+                distance: 5.0f64.sqrt()
+            })
+        );
+        assert_eq!(
+            tree.nearest(&Point2D::new(9.0, 1.0), &Manhattan).map(|found| found.index),
+            Some(1)
+        );
+
+        let found = tree.nearest_k(&Point2D::new(0.0, 0.0), 3, &SquaredEuclidean);
+        assert_eq!(
+            found.iter().map(|found| (found.index, found.distance)).collect::<Vec<_>>(),
+            vec![(0, 0.0), (3, 25.0), (1, 100.0)]
+        );
+
+        let mut within: Vec<usize> = tree
+            .within_radius(&Point2D::new(0.0, 0.0), 5.0, &Euclidean)
+            .iter()
+            .map(|found| found.index)
+            .collect();
+        within.sort();
+        assert_eq!(within, vec![0, 3]);
+    }
+
+    #[test]
+    fn test_empty_and_duplicates() {
+        let empty: KdTree<Point3D<f32>> = KdTree::new(&[]);
+        let duplicates = KdTree::new(&[Point3D::new(1.0f32, 1.0, 1.0); 10]);
+
+        // do test;
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(&Point3D::new(0.0, 0.0, 0.0), &Euclidean), None);
+        assert!(empty.nearest_k(&Point3D::new(0.0, 0.0, 0.0), 3, &Euclidean).is_empty());
+
+        let found = duplicates.nearest_k(&Point3D::new(0.0, 0.0, 0.0), 4, &Chebyshev);
+        assert_eq!(found.iter().map(|found| found.index).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(
+            duplicates.within_radius(&Point3D::new(1.0, 1.0, 2.0), 1.0, &Chebyshev).len(),
+            10
+        );
+        assert!(duplicates.nearest_k(&Point3D::new(0.0, 0.0, 0.0), 0, &Chebyshev).is_empty());
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[46][..]);
+        let points = random_points(&mut rng, 2000);
+        let tree = KdTree::new(&points);
+
+        // do test;
+        check_metric(&tree, &points, &Euclidean, 15.0);
+        check_metric(&tree, &points, &SquaredEuclidean, 225.0);
+        check_metric(&tree, &points, &Manhattan, 20.0);
+        check_metric(&tree, &points, &Chebyshev, 10.0);
+        check_metric(&tree, &points, &Stretched(4.0), 15.0);
+    }
+
+    #[test]
+    fn test_planar_points() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[47][..]);
+        let points: Vec<Point2D<f32>> = (0..500)
+            .map(|_| Point2D::new(rng.gen_range(0.0, 1.0), rng.gen_range(0.0, 1.0)))
+            .collect();
+        let tree = KdTree::new(&points);
+
+        // do test;
+        for _ in 0..100 {
+            let query = Point2D::new(rng.gen_range(-0.5, 1.5), rng.gen_range(-0.5, 1.5));
+            assert_eq!(
+                tree.nearest_k(&query, 5, &Euclidean),
+                &brute_force(&points, &query, &Euclidean)[..5]
+            );
+        }
+    }
+}
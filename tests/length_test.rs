@@ -1,9 +1,12 @@
+#![allow(clippy::clone_on_copy, clippy::neg_cmp_op_on_partial_ord)]
+
 extern crate matrix;
 
 #[cfg(test)]
 mod tests {
 
     use matrix::length::Length;
+    use matrix::num::{CheckedAdd, CheckedSub, Round, Rounding, SaturatingAdd, WrappingAdd};
     use matrix::scale_factor::ScaleFactor;
 
     // @section:aliases;
@@ -106,4 +109,52 @@ mod tests {
 
         assert_eq!(negative_zero.get(), 0.0);
     }
+
+    #[test]
+    fn test_overflow_aware_length_ops() {
+        let max: Length<Inch, i32> = Length::new(i32::MAX);
+        let one: Length<Inch, i32> = Length::new(1);
+
+        // do test;
+        assert_eq!(max.checked_add(&one), None);
+        assert_eq!(max.checked_sub(&one), Some(Length::new(i32::MAX - 1)));
+        assert_eq!(max.saturating_add(&one), max);
+        assert_eq!(max.wrapping_add(&one), Length::new(i32::MIN));
+        assert_eq!(one.checked_mul(5), Some(Length::new(5)));
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(max.saturating_mul(2), max);
+        assert_eq!(max.wrapping_mul(2), Length::new(-2));
+    }
+
+    #[test]
+    fn test_length_rounding() {
+        let length: Length<Inch, f32> = Length::new(-2.5);
+
+        // do test;
+        assert_eq!(length.round(), Length::new(-3.0));
+        assert_eq!(length.floor(), Length::new(-3.0));
+        assert_eq!(length.ceil(), Length::new(-2.0));
+        assert_eq!(length.trunc(), Length::new(-2.0));
+        assert_eq!(length.cast::<i32>().unwrap().get(), -2);
+        assert_eq!(length.cast_round::<i32>(Rounding::Nearest).unwrap().get(), -3);
+        assert_eq!(length.cast_round::<i32>(Rounding::Ceil).unwrap().get(), -2);
+    }
+
+    #[test]
+    fn test_length_compound_assignment() {
+        let mut length: Length<Inch, f32> = Length::new(12.0);
+
+        // do test;
+        length += Length::new(3.0);
+        assert_eq!(length.get(), 15.0);
+
+        length -= Length::new(5.0);
+        assert_eq!(length.get(), 10.0);
+
+        length *= 3.0;
+        assert_eq!(length.get(), 30.0);
+
+        length /= 2.0;
+        assert_eq!(length.get(), 15.0);
+    }
 }
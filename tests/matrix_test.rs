@@ -0,0 +1,381 @@
+extern crate matrix;
+
+#[cfg(test)]
+mod matrix2d_tests {
+
+    use matrix::matrix2d::Matrix2D;
+    use matrix::point::Point2D;
+
+    #[test]
+    fn test_mul_assign() {
+        let mut m = Matrix2D::indentity();
+
+        // do test;
+        m *= Matrix2D::indentity().translate(1.0, 2.0);
+        m *= Matrix2D::indentity().translate(3.0, 4.0);
+
+        assert_eq!(m.to_array(), Matrix2D::indentity().translate(4.0, 6.0).to_array());
+    }
+
+    #[test]
+    fn test_scale_before_transform() {
+        // A quarter turn, taking the x axis onto the y axis.
+        let rotation = Matrix2D::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0);
+
+        // do test;
+        let m = rotation.scale(2.0, 3.0);
+        assert_eq!(m.transform_point(&Point2D::new(1.0, 1.0)), Point2D::new(-3.0, 2.0));
+        assert_eq!(m.to_array(), rotation.mul(&Matrix2D::indentity().scale(2.0, 3.0)).to_array());
+
+        let m = Matrix2D::indentity().translate(10.0, 20.0).scale(2.0, 3.0);
+        assert_eq!(m.transform_point(&Point2D::new(1.0, 1.0)), Point2D::new(12.0, 23.0));
+    }
+
+    #[test]
+    fn test_row_major_convention() {
+        let m = Matrix2D::from_array([1, 2, 3, 4, 5, 6]);
+
+        // do test;
+        assert_eq!(m, Matrix2D::from_row_arrays([[1, 2], [3, 4], [5, 6]]));
+        assert_eq!(m, Matrix2D::from_column_arrays([[1, 3, 5], [2, 4, 6]]));
+        assert_eq!(m.to_row_major(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(m.to_column_major(), [1, 3, 5, 2, 4, 6]);
+        assert_eq!((m[(0, 1)], m[(1, 0)], m[(2, 1)]), (2, 3, 6));
+
+        assert_eq!(m.rows().collect::<Vec<_>>(), vec![[1, 2], [3, 4], [5, 6]]);
+        assert_eq!(m.columns().collect::<Vec<_>>(), vec![[1, 3, 5], [2, 4, 6]]);
+    }
+
+    #[test]
+    fn test_translation_in_last_row() {
+        let m = Matrix2D::indentity().translate(10, 20);
+
+        // do test;
+        assert_eq!((m[(2, 0)], m[(2, 1)]), (10, 20));
+        assert_eq!(m.transform_point(&Point2D::new(1, 2)), Point2D::new(11, 22));
+
+        let m = Matrix2D::from_row_arrays([[2, 1], [0, 3], [10, 20]]);
+        assert_eq!(m.transform_point(&Point2D::new(1, 2)), Point2D::new(12, 27));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_range() {
+        let m = Matrix2D::from_array([1, 2, 3, 4, 5, 6]);
+        let _ = m[(0, 2)];
+    }
+
+    #[test]
+    fn test_grid_format() {
+        let m = Matrix2D::from_row_arrays([[1.0, 0.0], [0.0, 1.0], [-10.5, 200.0]]);
+
+        // do test;
+        assert_eq!(format!("{}", m), "[    1,   0]\n[    0,   1]\n[-10.5, 200]");
+        assert_eq!(format!("{:?}", m), "Matrix2D\n[  1.0,   0.0]\n[  0.0,   1.0]\n[-10.5, 200.0]");
+        assert_eq!(format!("{:.1}", Matrix2D::from_array([1.0, 0.25, 0.0, 1.0, 0.0, 0.0])),
+                   "[1.0, 0.2]\n[0.0, 1.0]\n[0.0, 0.0]");
+    }
+}
+
+#[cfg(test)]
+mod matrix4d_tests {
+
+    use matrix::approxeq::ApproxEq;
+    use matrix::box3d::Box3D;
+    use matrix::matrix4d::Matrix4D;
+    use matrix::point::{Point2D, Point3D, Point4D};
+    use matrix::rect::Rect;
+    use matrix::size::Size2D;
+
+    /// Projects onto the plane at distance one, `t` takes the depth.
+    #[rustfmt::skip]
+    fn perspective() -> Matrix4D {
+        Matrix4D::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 1.0,
+            0.0, 0.0, 0.0, 0.0
+        )
+    }
+
+    /// A quarter turn around z, taking the x axis onto the y axis.
+    #[rustfmt::skip]
+    fn quarter_turn() -> Matrix4D {
+        Matrix4D::new(
+            0.0, 1.0, 0.0, 0.0,
+            -1.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0
+        )
+    }
+
+    #[test]
+    fn test_scale_before_transform() {
+        let rotation = quarter_turn();
+
+        // do test;
+        let m = rotation.scale(2.0, 3.0, 4.0);
+        assert_eq!(m.transform_point2d(&Point2D::new(1.0, 1.0)), Point2D::new(-3.0, 2.0));
+        assert_eq!(m, rotation.mul(&Matrix4D::indentity().scale(2.0, 3.0, 4.0)));
+
+        let m = Matrix4D::indentity().translate(10.0, 20.0, 30.0).scale(2.0, 3.0, 4.0);
+        let point = m.transform_point4d(&Point4D::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(point, Point4D::new(12.0, 23.0, 34.0, 1.0));
+    }
+
+    #[test]
+    fn test_mul_assign() {
+        let mut m = Matrix4D::indentity();
+        let translation = Matrix4D::indentity().translate(1.0, 2.0, 3.0);
+
+        // do test;
+        m *= translation;
+        assert_eq!(m, translation);
+
+        m *= Matrix4D::indentity().scale(2.0, 2.0, 2.0);
+        assert!(m.approx_eq(&translation.mul(&Matrix4D::indentity().scale(2.0, 2.0, 2.0))));
+    }
+
+    #[test]
+    fn test_transform_point4d() {
+        let m = Matrix4D::indentity().translate(1.0, 2.0, 3.0);
+
+        // do test;
+        assert_eq!(m.transform_point4d(&Point4D::new(1.0, 1.0, 1.0, 1.0)), Point4D::new(2.0, 3.0, 4.0, 1.0));
+        assert_eq!(m.transform_point4d(&Point4D::new(1.0, 1.0, 1.0, 0.0)), Point4D::new(1.0, 1.0, 1.0, 0.0));
+        assert_eq!(m.transform_point4d(&Point4D::new(1.0, 1.0, 1.0, 2.0)), Point4D::new(3.0, 5.0, 7.0, 2.0));
+    }
+
+    #[test]
+    fn test_transform_point3d() {
+        let m = Matrix4D::indentity().translate(1.0, 2.0, 3.0);
+        assert_eq!(m.transform_point3d(&Point3D::new(1.0, 1.0, 1.0)), Some(Point3D::new(2.0, 3.0, 4.0)));
+
+        let m = perspective();
+        assert_eq!(m.transform_point3d(&Point3D::new(2.0, 4.0, 2.0)), Some(Point3D::new(1.0, 2.0, 1.0)));
+
+        // points on and behind the camera plane;
+        assert_eq!(m.transform_point3d(&Point3D::new(2.0, 4.0, 0.0)), None);
+        assert_eq!(m.transform_point3d(&Point3D::new(2.0, 4.0, -2.0)), None);
+        // and points so close to it that the rect clipping would cut them off.
+        assert_eq!(m.transform_point3d(&Point3D::new(2.0, 4.0, 5.0e-7)), None);
+    }
+
+    // Rotates around the y axis by 60 degrees, then projects with the viewer at z = 50,
+    // so every point with x beyond 50 / sin(60deg), about 57.7, ends up behind the viewer.
+    #[rustfmt::skip]
+    fn perspective_rotation() -> Matrix4D {
+        let (sin, cos) = 60.0f32.to_radians().sin_cos();
+        let distance = 50.0;
+
+        Matrix4D::new(
+            cos, 0.0, sin, -sin / distance,
+            0.0, 1.0, 0.0, 0.0,
+            -sin, 0.0, cos, -cos / distance,
+            0.0, 0.0, 0.0, 1.0
+        )
+    }
+
+    #[test]
+    fn test_transform_rect() {
+        let rect = Rect::new(Point2D::new(10.0, 20.0), Size2D::new(30.0, 40.0));
+        let m = Matrix4D::indentity().translate(1.0, 2.0, 3.0).scale(2.0, 2.0, 1.0);
+
+        // do test;
+        let result = m.transform_rect(&rect).unwrap();
+        assert!(result.origin.x.approx_eq(&21.0) && result.origin.y.approx_eq(&42.0));
+        assert!(result.size.width.approx_eq(&60.0) && result.size.height.approx_eq(&80.0));
+    }
+
+    #[test]
+    fn test_transform_rect_clips_behind_viewer() {
+        let m = perspective_rotation();
+        let rect = Rect::new(Point2D::new(-100.0, -50.0), Size2D::new(200.0, 100.0));
+
+        // do test;
+        let result = m.transform_rect(&rect).unwrap();
+
+        // the left edge stays in front at depth 2.73;
+        let depth = 1.0 + 60.0f32.to_radians().sin() * 2.0;
+        assert!(result.min_x().approx_eq_eps(&(-50.0 / depth), &1.0e-3));
+
+        // the visible part reaches the clipping plane, so it grows without bound;
+        assert!(result.max_x() > 1.0e6);
+        assert!(result.min_y() < -1.0e6 && result.max_y() > 1.0e6);
+
+        // naive projection of the corners folds the right edge back onto the left side;
+        let naive = m.transform_point4d(&Point4D::new(100.0, 50.0, 0.0, 1.0)).to_point2d().unwrap();
+        assert!(naive.x < result.min_x());
+    }
+
+    #[test]
+    fn test_transform_rect_behind_viewer() {
+        let m = perspective_rotation();
+        let rect = Rect::new(Point2D::new(60.0, -50.0), Size2D::new(40.0, 100.0));
+
+        // do test;
+        assert_eq!(m.transform_rect(&rect), None);
+    }
+
+    #[test]
+    fn test_transform_box() {
+        let bounds = Box3D::new(Point3D::new(-1.0, -2.0, -3.0), Point3D::new(1.0, 2.0, 3.0));
+        let m = Matrix4D::indentity().translate(1.0, 2.0, 3.0);
+
+        // do test;
+        let result = m.transform_box(&bounds).unwrap();
+        assert_eq!(result, Box3D::new(Point3D::new(0.0, 0.0, 0.0), Point3D::new(2.0, 4.0, 6.0)));
+
+        let m = perspective_rotation();
+        let visible = m.transform_box(&Box3D::new(Point3D::new(-100.0, -50.0, -1.0), Point3D::new(100.0, 50.0, 1.0)));
+        assert!(visible.unwrap().max.x > 1.0e6);
+
+        let hidden = m.transform_box(&Box3D::new(Point3D::new(60.0, -50.0, -1.0), Point3D::new(100.0, 50.0, 1.0)));
+        assert_eq!(hidden, None);
+    }
+
+    #[rustfmt::skip]
+    fn sequence() -> Matrix4D {
+        Matrix4D::from_array([
+            1.0, 2.0, 3.0, 4.0,
+            5.0, 6.0, 7.0, 8.0,
+            9.0, 10.0, 11.0, 12.0,
+            13.0, 14.0, 15.0, 16.0,
+        ])
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_row_major_convention() {
+        let m = sequence();
+        let rows = [[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0], [9.0, 10.0, 11.0, 12.0], [13.0, 14.0, 15.0, 16.0]];
+        let columns = [[1.0, 5.0, 9.0, 13.0], [2.0, 6.0, 10.0, 14.0], [3.0, 7.0, 11.0, 15.0], [4.0, 8.0, 12.0, 16.0]];
+
+        // do test;
+        assert_eq!(m, Matrix4D::from_row_arrays(rows));
+        assert_eq!(m, Matrix4D::from_column_arrays(columns));
+        assert_eq!(m.to_row_major(), m.to_array());
+        assert_eq!(m.to_column_major(), [
+            1.0, 5.0, 9.0, 13.0,
+            2.0, 6.0, 10.0, 14.0,
+            3.0, 7.0, 11.0, 15.0,
+            4.0, 8.0, 12.0, 16.0,
+        ]);
+        assert_eq!((m[(0, 1)], m[(1, 0)], m[(3, 2)]), (2.0, 5.0, 15.0));
+
+        assert_eq!(m.rows().collect::<Vec<_>>(), rows.to_vec());
+        assert_eq!(m.columns().collect::<Vec<_>>(), columns.to_vec());
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = sequence();
+
+        // do test;
+        assert_eq!(m.transpose().to_row_arrays(), m.to_column_arrays());
+        assert_eq!(m.transpose().transpose(), m);
+        assert_eq!(m.transpose()[(2, 3)], m[(3, 2)]);
+    }
+
+    #[test]
+    fn test_translation_in_last_row() {
+        let m = Matrix4D::indentity().translate(1.0, 2.0, 3.0);
+
+        // do test;
+        assert_eq!(m.rows().last(), Some([1.0, 2.0, 3.0, 1.0]));
+        assert_eq!(m.columns().last(), Some([0.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_range() {
+        let _ = sequence()[(4, 0)];
+    }
+
+    #[test]
+    fn test_grid_format() {
+        let m = Matrix4D::indentity().translate(10.0, -2.5, 0.0);
+
+        // do test;
+        assert_eq!(format!("{}", m), "[ 1,    0, 0, 0]\n[ 0,    1, 0, 0]\n[ 0,    0, 1, 0]\n[10, -2.5, 0, 1]");
+        assert!(format!("{:?}", m).starts_with("Matrix4D\n[ 1.0,  0.0, 0.0, 0.0]\n"));
+    }
+
+    #[test]
+    fn test_scale_after_rotation() {
+        let m = Matrix4D::indentity().rotate(0.0, 0.0, 1.0, 90.0f32.to_radians()).scale(2.0, 3.0, 1.0);
+
+        // do test;
+        let point = m.transform_point3d(&Point3D::new(1.0, 1.0, 0.0)).unwrap();
+        assert!(point.x.approx_eq(&-3.0) && point.y.approx_eq(&2.0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let m = Matrix4D::indentity().rotate(0.0, 0.0, 5.0, 90.0f32.to_radians());
+        let point = m.transform_point3d(&Point3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(point.x.approx_eq(&0.0) && point.y.approx_eq(&1.0) && point.z.approx_eq(&0.0));
+
+        let m = Matrix4D::indentity().rotate(1.0, 0.0, 0.0, 90.0f32.to_radians());
+        let point = m.transform_point3d(&Point3D::new(0.0, 1.0, 0.0)).unwrap();
+        assert!(point.x.approx_eq(&0.0) && point.y.approx_eq(&0.0) && point.z.approx_eq(&1.0));
+
+        assert_eq!(Matrix4D::indentity().rotate(0.0, 0.0, 0.0, 1.0), Matrix4D::indentity());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = Matrix4D::indentity()
+            .translate(10.0, -5.0, 2.0)
+            .rotate(1.0, 2.0, 3.0, 0.7)
+            .scale(2.0, 4.0, 0.5)
+            .perspective(300.0);
+
+        // do test;
+        let inverse = m.inverse().unwrap();
+        assert!(m.mul(&inverse).approx_eq(&Matrix4D::indentity()));
+        assert!(inverse.mul(&m).approx_eq(&Matrix4D::indentity()));
+
+        assert_eq!(Matrix4D::indentity().scale(1.0, 0.0, 1.0).inverse(), None);
+    }
+
+    #[test]
+    fn test_inverse_of_small_and_non_finite_matrices() {
+        let small = Matrix4D::indentity().scale(1.0e-4, 1.0e-4, 1.0e-4);
+        let huge = Matrix4D::indentity().scale(1.0e30, 1.0e30, 1.0).scale(1.0e30, 1.0e30, 1.0);
+
+        // do test;
+        let inverse = small.inverse().unwrap();
+        assert!(inverse.mul(&small).approx_eq(&Matrix4D::indentity()));
+        let scale = inverse.to_row_arrays()[0][0];
+        assert!(scale > 9999.0 && scale < 10001.0);
+
+        // Equal rows scaled up still count as singular.
+        #[rustfmt::skip]
+        let rank_deficient = Matrix4D::new(
+            1000.0, 2000.0, 0.0, 0.0,
+            1000.0, 2000.0 + 1.0e-4, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0
+        );
+        assert_eq!(rank_deficient.inverse(), None);
+
+        assert_eq!(huge.inverse(), None);
+        let mut rows = Matrix4D::indentity().to_row_arrays();
+        rows[1][2] = f32::NAN;
+        assert_eq!(Matrix4D::from_row_arrays(rows).inverse(), None);
+        rows[1][2] = f32::INFINITY;
+        assert_eq!(Matrix4D::from_row_arrays(rows).inverse(), None);
+    }
+
+    #[test]
+    fn test_2d_conversion() {
+        let m = Matrix4D::indentity().translate(3.0, 4.0, 0.0).skew(0.3, 0.0);
+
+        // do test;
+        assert!(m.is_2d());
+        assert_eq!(Matrix4D::from_2d(&m.to_2d()), m);
+        assert!(!m.translate(0.0, 0.0, 1.0).is_2d());
+        assert!(!m.perspective(100.0).is_2d());
+    }
+}
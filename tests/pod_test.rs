@@ -0,0 +1,138 @@
+extern crate matrix;
+
+#[cfg(test)]
+mod layout_tests {
+
+    use matrix::length::Length;
+    use matrix::matrix2d::Matrix2D;
+    use matrix::matrix4d::Matrix4D;
+    use matrix::point::{Point2D, Point3D, Point4D, TypedPoint2D};
+    use matrix::pod::Std140Point3D;
+    use matrix::size::Size2D;
+    use std::mem::{align_of, size_of};
+
+    #[derive(Debug, Copy, Clone)]
+    enum Mm {}
+
+    #[test]
+    fn test_points_have_no_padding() {
+        assert_eq!(size_of::<Point2D<f32>>(), 2 * size_of::<f32>());
+        assert_eq!(size_of::<Point3D<f32>>(), 3 * size_of::<f32>());
+        assert_eq!(size_of::<Point4D<f32>>(), 4 * size_of::<f32>());
+        assert_eq!(size_of::<Point3D<u8>>(), 3);
+        assert_eq!(size_of::<Point3D<f64>>(), 3 * size_of::<f64>());
+        assert_eq!(align_of::<Point3D<f32>>(), align_of::<f32>());
+    }
+
+    #[test]
+    fn test_typed_geometry_has_no_padding() {
+        assert_eq!(size_of::<Length<Mm, f32>>(), size_of::<f32>());
+        assert_eq!(size_of::<TypedPoint2D<Mm, f32>>(), 2 * size_of::<f32>());
+        assert_eq!(align_of::<TypedPoint2D<Mm, f32>>(), align_of::<f32>());
+    }
+
+    #[test]
+    fn test_sizes_and_matrices_have_no_padding() {
+        assert_eq!(size_of::<Size2D<f32>>(), 2 * size_of::<f32>());
+        assert_eq!(size_of::<Matrix2D<f32>>(), 6 * size_of::<f32>());
+        assert_eq!(size_of::<Matrix4D>(), 16 * size_of::<f32>());
+    }
+
+    #[test]
+    fn test_std140_point_is_16_bytes() {
+        assert_eq!(size_of::<Std140Point3D>(), 16);
+        assert_eq!(align_of::<Std140Point3D>(), 16);
+    }
+}
+
+#[cfg(test)]
+mod pod_tests {
+
+    use matrix::matrix4d::Matrix4D;
+    use matrix::point::{Point2D, Point3D};
+    use matrix::pod::{self, Pod};
+
+    #[test]
+    fn test_point_as_bytes() {
+        let point = Point2D::new(1.0f32, 2.0);
+
+        // do test;
+        let bytes = point.as_bytes();
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(&bytes[..4], &1.0f32.to_ne_bytes());
+        assert_eq!(&bytes[4..], &2.0f32.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_point_from_bytes() {
+        let values = [3.0f32, 4.0];
+        let bytes = pod::slice_as_bytes(&values);
+
+        // do test;
+        assert_eq!(Point2D::<f32>::from_bytes(bytes), Some(&Point2D::new(3.0, 4.0)));
+        assert_eq!(Point2D::<f32>::from_bytes(&bytes[..4]), None);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_misaligned_data() {
+        let values = [0u32; 3];
+        let bytes = pod::slice_as_bytes(&values);
+
+        // do test;
+        assert!(Point2D::<u32>::from_bytes(&bytes[1..9]).is_none());
+        assert!(Point2D::<u32>::from_bytes(&bytes[4..12]).is_some());
+    }
+
+    #[test]
+    fn test_cast_points_to_components() {
+        let points = [Point3D::new(1.0f32, 2.0, 3.0), Point3D::new(4.0, 5.0, 6.0)];
+
+        // do test;
+        let components: &[f32] = pod::cast_slice(&points).unwrap();
+        assert_eq!(components, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let back: &[Point3D<f32>] = pod::cast_slice(components).unwrap();
+        assert_eq!(back, &points);
+    }
+
+    #[test]
+    fn test_cast_slice_rejects_partial_elements() {
+        let components = [1.0f32, 2.0, 3.0, 4.0];
+
+        // do test;
+        assert!(pod::cast_slice::<f32, Point3D<f32>>(&components).is_none());
+        assert!(pod::cast_slice::<f32, Point2D<f32>>(&components).is_some());
+    }
+
+    #[test]
+    fn test_cast_slice_mut_updates_in_place() {
+        let mut points = [Point2D::new(1.0f32, 2.0), Point2D::new(3.0, 4.0)];
+
+        // do test;
+        for value in pod::cast_slice_mut::<Point2D<f32>, f32>(&mut points).unwrap() {
+            *value *= 2.0;
+        }
+        assert_eq!(points, [Point2D::new(2.0, 4.0), Point2D::new(6.0, 8.0)]);
+    }
+
+    #[test]
+    fn test_matrix_views_match_to_array() {
+        let matrix = Matrix4D::indentity().translate(1.0, 2.0, 3.0);
+
+        // do test;
+        assert_eq!(matrix.as_array(), &matrix.to_array());
+        assert_eq!(pod::cast_slice::<Matrix4D, f32>(&[matrix]).unwrap(), &matrix.to_array()[..]);
+        assert_eq!(matrix.as_bytes().len(), 64);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_pad_points_for_std140() {
+        let points = [Point3D::new(1.0f32, 2.0, 3.0), Point3D::new(4.0, 5.0, 6.0)];
+
+        // do test;
+        let padded = pod::pad_points(&points);
+        let components: &[f32] = pod::cast_slice(&padded).unwrap();
+        assert_eq!(components, &[1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0, 0.0]);
+    }
+}
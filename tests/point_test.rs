@@ -77,6 +77,70 @@ mod typedpoint2d_tests {
 
         assert_eq!(p1 * cm_per_mm, Point2DCm::typed(0.1, 0.2));
     }
+
+    #[test]
+    pub fn test_scalar_div() {
+        let p1 = Point2DCm::typed(1.0, 2.0);
+        let cm_per_mm: ScaleFactor<Mm, Cm, f32> = ScaleFactor::new(0.5);
+
+        assert_eq!(p1 / cm_per_mm, Point2DMm::typed(2.0, 4.0));
+    }
+
+    #[test]
+    pub fn test_to_untyped() {
+        let p1 = Point2DMm::typed(1.0, 2.0);
+
+        assert_eq!(p1.to_untyped(), ::matrix::point::Point2D::new(1.0, 2.0));
+    }
+
+    #[test]
+    pub fn test_scale_without_copy_units() {
+        enum Pt {}
+        enum Px {}
+
+        let p1: TypedPoint2D<Pt, f64> = TypedPoint2D::typed(3.0, 6.0);
+        let px_per_pt: ScaleFactor<Pt, Px, f64> = ScaleFactor::new(4.0 / 3.0);
+
+        // do test;
+        let p2: TypedPoint2D<Px, f64> = p1.clone() * px_per_pt.clone();
+        assert_eq!(p2.to_untyped(), ::matrix::point::Point2D::new(4.0, 8.0));
+        assert_eq!((p2 / px_per_pt).to_untyped(), p1.to_untyped());
+    }
+}
+
+#[cfg(test)]
+mod typedpoint3d_tests {
+
+    use matrix::point::{Point3D, TypedPoint3D};
+    use matrix::scale_factor::ScaleFactor;
+
+    #[derive(Debug, Copy, Clone)]
+    enum Mm {}
+
+    #[derive(Debug, Copy, Clone)]
+    enum Cm {}
+
+    #[test]
+    fn test_scale() {
+        let p1: TypedPoint3D<Mm, f32> = TypedPoint3D::typed(10.0, 20.0, 30.0);
+        let cm_per_mm: ScaleFactor<Mm, Cm, f32> = ScaleFactor::new(0.1);
+
+        // do test;
+        let p2: TypedPoint3D<Cm, f32> = p1 * cm_per_mm;
+        assert_eq!(p2, TypedPoint3D::typed(1.0, 2.0, 3.0));
+        assert_eq!(p2 / cm_per_mm, p1);
+    }
+
+    #[test]
+    fn test_untyped() {
+        let p1 = Point3D::new(1, 2, 3);
+
+        // do test;
+        let typed: TypedPoint3D<Mm, i32> = TypedPoint3D::from_untyped(&p1);
+        assert_eq!(typed.to_untyped(), p1);
+        assert_eq!(p1 * 2, Point3D::new(2, 4, 6));
+        assert_eq!(p1 / 2, Point3D::new(0, 1, 1));
+    }
 }
 
 #[cfg(test)]
@@ -119,7 +183,7 @@ mod point3d_tests {
 
 #[cfg(test)]
 mod point4d_tests {
-    use matrix::point::Point4D;
+    use matrix::point::{Point2D, Point3D, Point4D};
 
     #[test]
     pub fn test_add() {
@@ -152,4 +216,293 @@ mod point4d_tests {
 
         assert_eq!(p1.max(p2), Point4D::new(2.0, 3.0, 5.0, 10.0));
     }
+
+    #[test]
+    pub fn test_scalar_mul_div() {
+        let p1 = Point4D::new(1.0, 2.0, 3.0, 4.0);
+
+        assert_eq!(p1 * 2.0, Point4D::new(2.0, 4.0, 6.0, 8.0));
+        assert_eq!(p1 / 2.0, Point4D::new(0.5, 1.0, 1.5, 2.0));
+    }
+
+    #[test]
+    pub fn test_dot() {
+        let p1 = Point4D::new(1, 2, 3, 4);
+        let p2 = Point4D::new(5, 6, 7, 8);
+
+        assert_eq!(p1.dot(p2), 70);
+    }
+
+    #[test]
+    pub fn test_perspective_divide() {
+        let p1 = Point4D::new(2.0, 4.0, 6.0, 2.0);
+
+        // do test;
+        assert_eq!(p1.to_point3d(), Some(Point3D::new(1.0, 2.0, 3.0)));
+        assert_eq!(p1.to_point2d(), Some(Point2D::new(1.0, 2.0)));
+        assert_eq!((p1 * 3.0).to_point3d(), p1.to_point3d());
+
+        // points at infinity have no projection;
+        let p2 = Point4D::new(1.0, 1.0, 1.0, 0.0);
+        assert_eq!(p2.to_point3d(), None);
+        assert_eq!(p2.to_point2d(), None);
+    }
+}
+
+#[cfg(test)]
+mod overflow_tests {
+
+    use matrix::num::CheckedAdd;
+    use matrix::point::{Point2D, Point3D, Point4D, TypedPoint2D};
+
+    #[derive(Debug, Copy, Clone)]
+    pub enum Px {}
+
+    #[test]
+    pub fn test_checked_ops() {
+        let p1 = Point2D::new(i32::MAX - 1, 0);
+        let p2 = Point2D::new(1, 1);
+
+        assert_eq!(p1.checked_add(&p2), Some(Point2D::new(i32::MAX, 1)));
+        assert_eq!(p1.checked_add(&p2).unwrap().checked_add(&p2), None);
+        assert_eq!(Point2D::new(0u32, 1).checked_sub(&Point2D::new(1, 1)), None);
+        assert_eq!(p2.checked_mul(7), Some(Point2D::new(7, 7)));
+        assert_eq!(p1.checked_mul(2), None);
+    }
+
+    #[test]
+    pub fn test_saturating_ops() {
+        let p1 = Point3D::new(i16::MAX, i16::MIN, 0);
+        let p2 = Point3D::new(1i16, -1, 1);
+
+        assert_eq!(p1.saturating_add(&p2), Point3D::new(i16::MAX, i16::MIN, 1));
+        assert_eq!(p1.saturating_sub(&p2), Point3D::new(i16::MAX - 1, i16::MIN + 1, -1));
+        assert_eq!(p1.saturating_mul(2), Point3D::new(i16::MAX, i16::MIN, 0));
+    }
+
+    #[test]
+    pub fn test_wrapping_ops() {
+        let p1 = Point4D::new(u8::MAX, 0, 1, 128);
+        let p2 = Point4D::new(1u8, 1, 1, 1);
+
+        assert_eq!(p1.wrapping_add(&p2), Point4D::new(0, 1, 2, 129));
+        assert_eq!(p1.wrapping_sub(&p2), Point4D::new(254, u8::MAX, 0, 127));
+        assert_eq!(p1.wrapping_mul(2), Point4D::new(254, 0, 2, 0));
+        assert_eq!(p1.checked_add(&p2), None);
+    }
+
+    #[test]
+    pub fn test_typed_checked_ops() {
+        let p1: TypedPoint2D<Px, i32> = TypedPoint2D::typed(i32::MAX, 0);
+        let p2: TypedPoint2D<Px, i32> = TypedPoint2D::typed(0, 1);
+
+        assert_eq!(p1.checked_add(&p2), Some(TypedPoint2D::typed(i32::MAX, 1)));
+        assert_eq!(p1.checked_add(&p1), None);
+        assert_eq!(p1.x.checked_add(&p2.y), None);
+    }
+}
+
+#[cfg(test)]
+mod rounding_tests {
+
+    use matrix::num::Rounding;
+    use matrix::point::{Point2D, Point3D, TypedPoint2D};
+    use matrix::scale_factor::ScaleFactor;
+
+    #[derive(Debug, Copy, Clone)]
+    pub enum Css {}
+
+    #[derive(Debug, Copy, Clone)]
+    pub enum Device {}
+
+    #[test]
+    pub fn test_round_floor_ceil_trunc() {
+        let p1 = Point2D::new(1.5, -1.5);
+
+        assert_eq!(p1.round(), Point2D::new(2.0, -2.0));
+        assert_eq!(p1.floor(), Point2D::new(1.0, -2.0));
+        assert_eq!(p1.ceil(), Point2D::new(2.0, -1.0));
+        assert_eq!(p1.trunc(), Point2D::new(1.0, -1.0));
+
+        let p2 = Point3D::new(0.4f32, 0.6, -0.6);
+        assert_eq!(p2.round(), Point3D::new(0.0, 1.0, -1.0));
+        assert_eq!(p2.floor(), Point3D::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    pub fn test_cast_round() {
+        let p1: TypedPoint2D<Css, f32> = TypedPoint2D::typed(2.7, -2.7);
+
+        assert_eq!(p1.cast::<i32>().unwrap(), TypedPoint2D::typed(2, -2));
+        assert_eq!(p1.cast_round::<i32>(Rounding::Nearest).unwrap(), TypedPoint2D::typed(3, -3));
+        assert_eq!(p1.cast_round::<i32>(Rounding::Floor).unwrap(), TypedPoint2D::typed(2, -3));
+        assert_eq!(p1.cast_round::<i32>(Rounding::Ceil).unwrap(), TypedPoint2D::typed(3, -2));
+    }
+
+    #[test]
+    pub fn test_snap_to_device() {
+        let p1: TypedPoint2D<Css, f32> = TypedPoint2D::typed(10.3, 10.4);
+        let scale: ScaleFactor<Css, Device, f32> = ScaleFactor::new(2.0);
+
+        let snapped: TypedPoint2D<Device, i32> = p1.snap_to_device(scale).unwrap();
+        assert_eq!(snapped, TypedPoint2D::typed(21, 21));
+    }
+}
+
+#[cfg(test)]
+mod assignment_tests {
+
+    use matrix::point::{Point2D, Point3D, Point4D, TypedPoint2D};
+
+    #[derive(Debug, Copy, Clone)]
+    pub enum Mm {}
+
+    #[test]
+    pub fn test_point2d_compound_assignment() {
+        let mut p1 = Point2D::new(1.0, 2.0);
+
+        p1 += Point2D::new(3.0, 4.0);
+        assert_eq!(p1, Point2D::new(4.0, 6.0));
+
+        p1 -= Point2D::new(1.0, 1.0);
+        assert_eq!(p1, Point2D::new(3.0, 5.0));
+
+        p1 *= 2.0;
+        assert_eq!(p1, Point2D::new(6.0, 10.0));
+
+        p1 /= 4.0;
+        assert_eq!(p1, Point2D::new(1.5, 2.5));
+    }
+
+    #[test]
+    pub fn test_point3d_and_point4d_compound_assignment() {
+        let mut p1 = Point3D::new(1, 2, 3);
+        let mut p2 = Point4D::new(1, 2, 3, 4);
+
+        p1 += Point3D::new(1, 1, 1);
+        p1 *= 3;
+        p1 -= Point3D::new(0, 3, 6);
+        p1 /= 2;
+        assert_eq!(p1, Point3D::new(3, 3, 3));
+
+        p2 += Point4D::new(1, 1, 1, 1);
+        p2 *= 2;
+        p2 -= Point4D::new(4, 6, 8, 10);
+        assert_eq!(p2, Point4D::new(0, 0, 0, 0));
+
+        p2 += Point4D::new(8, 8, 8, 8);
+        p2 /= 4;
+        assert_eq!(p2, Point4D::new(2, 2, 2, 2));
+    }
+
+    #[test]
+    pub fn test_typed_point_compound_assignment() {
+        let mut p1: TypedPoint2D<Mm, f32> = TypedPoint2D::typed(1.0, 2.0);
+
+        for _ in 0..3 {
+            p1 += TypedPoint2D::typed(1.0, 1.0);
+        }
+        p1 *= 2.0;
+
+        assert_eq!(p1, TypedPoint2D::typed(8.0, 10.0));
+    }
+}
+
+#[cfg(test)]
+mod vector_tests {
+
+    use matrix::approxeq::ApproxEq;
+    use matrix::point::{Point2D, Point3D};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn test_length_and_distance() {
+        let p1 = Point2D::new(3.0, 4.0);
+        let p2 = Point3D::new(2.0, 3.0, 6.0);
+
+        // do test;
+        assert_eq!(p1.square_length(), 25.0);
+        assert_eq!(p1.length(), 5.0);
+        assert_eq!(p2.square_length(), 49.0);
+        assert_eq!(p2.length(), 7.0);
+
+        assert_eq!(Point2D::new(1.0, 1.0).distance_to(Point2D::new(4.0, 5.0)), 5.0);
+        assert_eq!(Point3D::new(1.0, 1.0, 1.0).distance_to(Point3D::new(3.0, 4.0, 7.0)), 7.0);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(Point2D::new(3.0, 4.0).normalize(), Point2D::new(0.6, 0.8));
+        assert_eq!(Point3D::new(0.0, 0.0, -2.0).normalize(), Point3D::new(0.0, 0.0, -1.0));
+
+        // zero vectors stay zero;
+        assert_eq!(Point2D::new(0.0, 0.0).normalize(), Point2D::new(0.0, 0.0));
+        assert_eq!(Point3D::new(0.0, 0.0, 0.0).normalize(), Point3D::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_angle_to() {
+        let x = Point2D::new(1.0, 0.0);
+        let y = Point2D::new(0.0, 2.0);
+
+        // do test;
+        assert!(x.angle_to(y).approx_eq(&FRAC_PI_2));
+        assert!(y.angle_to(x).approx_eq(&-FRAC_PI_2));
+        assert!(x.angle_to(-x).approx_eq(&PI));
+        assert_eq!(x.angle_to(Point2D::new(0.0, 0.0)), 0.0);
+
+        let x = Point3D::new(1.0, 0.0, 0.0);
+        let z = Point3D::new(0.0, 0.0, 3.0);
+        assert!(x.angle_to(z).approx_eq(&FRAC_PI_2));
+        assert!(z.angle_to(x).approx_eq(&FRAC_PI_2));
+        assert_eq!(Point3D::new(0.0, 0.0, 0.0).angle_to(z), 0.0);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let p1 = Point2D::new(1.0, 0.0).rotate(FRAC_PI_2);
+        assert!(p1.x.approx_eq(&0.0) && p1.y.approx_eq(&1.0));
+
+        let p2 = Point3D::new(1.0, 0.0, 0.0).rotate(Point3D::new(0.0, 0.0, 5.0), FRAC_PI_2);
+        assert!(p2.x.approx_eq(&0.0) && p2.y.approx_eq(&1.0) && p2.z.approx_eq(&0.0));
+
+        // zero axis leaves the vector unchanged;
+        let p3 = Point3D::new(1.0, 2.0, 3.0);
+        assert_eq!(p3.rotate(Point3D::new(0.0, 0.0, 0.0), 1.0), p3);
+    }
+
+    #[test]
+    fn test_project_and_reflect() {
+        let p1 = Point2D::new(2.0, 3.0);
+
+        // do test;
+        assert_eq!(p1.project_onto(Point2D::new(5.0, 0.0)), Point2D::new(2.0, 0.0));
+        assert_eq!(p1.reflect(Point2D::new(0.0, 1.0)), Point2D::new(2.0, -3.0));
+
+        let p2 = Point3D::new(1.0, -1.0, 2.0);
+        assert_eq!(p2.project_onto(Point3D::new(0.0, 0.0, 2.0)), Point3D::new(0.0, 0.0, 2.0));
+        assert_eq!(p2.reflect(Point3D::new(0.0, 3.0, 0.0)), Point3D::new(1.0, 1.0, 2.0));
+
+        // zero vectors project to zero and reflect nothing;
+        assert_eq!(p1.project_onto(Point2D::new(0.0, 0.0)), Point2D::new(0.0, 0.0));
+        assert_eq!(p1.reflect(Point2D::new(0.0, 0.0)), p1);
+        assert_eq!(p2.project_onto(Point3D::new(0.0, 0.0, 0.0)), Point3D::new(0.0, 0.0, 0.0));
+        assert_eq!(p2.reflect(Point3D::new(0.0, 0.0, 0.0)), p2);
+    }
+
+    #[test]
+    fn test_abs_clamp_lerp() {
+        let p1 = Point2D::new(-2.0, 5.0);
+        let p2 = Point3D::new(-2.0, 5.0, 0.5);
+
+        // do test;
+        assert_eq!(p1.abs(), Point2D::new(2.0, 5.0));
+        assert_eq!(p1.clamp(Point2D::new(-1.0, -1.0), Point2D::new(1.0, 1.0)), Point2D::new(-1.0, 1.0));
+        assert_eq!(p1.lerp(&Point2D::new(2.0, 7.0), 0.5), Point2D::new(0.0, 6.0));
+
+        assert_eq!(p2.abs(), Point3D::new(2.0, 5.0, 0.5));
+        assert_eq!(p2.clamp(Point3D::new(-1.0, -1.0, 0.0), Point3D::new(1.0, 1.0, 1.0)),
+                   Point3D::new(-1.0, 1.0, 0.5));
+        assert_eq!(p2.lerp(&Point3D::new(2.0, 7.0, 1.5), 0.5), Point3D::new(0.0, 6.0, 1.0));
+    }
 }
@@ -0,0 +1,89 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::geometry::predicates::{incircle, orient2d};
+    use matrix::point::Point2D;
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    fn sign(value: f64) -> i128 {
+        if value > 0.0 {
+            1
+        } else if value < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }
+
+    fn point((x, y): (i128, i128)) -> Point2D<f64> {
+        Point2D::new(x as f64, y as f64)
+    }
+
+    #[test]
+    fn test_orient2d() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[52][..]);
+
+        // do test;
+        for _ in 0..10000 {
+            // Large coordinates whose products round, with c within a unit of the line.
+            let a = (
+                rng.gen_range(-1i64 << 40, 1 << 40) as i128,
+                rng.gen_range(-1i64 << 40, 1 << 40) as i128,
+            );
+            let b = (
+                rng.gen_range(-1i64 << 40, 1 << 40) as i128,
+                rng.gen_range(-1i64 << 40, 1 << 40) as i128,
+            );
+            let (step, steps) = (rng.gen_range(-16, 16) as i128, rng.gen_range(1, 16) as i128);
+            let c = (
+                a.0 + (b.0 - a.0) * step / steps + rng.gen_range(-1, 2) as i128,
+                a.1 + (b.1 - a.1) * step / steps + rng.gen_range(-1, 2) as i128,
+            );
+
+            let exact = (a.0 - c.0) * (b.1 - c.1) - (a.1 - c.1) * (b.0 - c.0);
+            assert_eq!(sign(orient2d(point(a), point(b), point(c))), exact.signum());
+            assert_eq!(sign(orient2d(point(b), point(a), point(c))), -exact.signum());
+        }
+
+        // A point a rounding error off a line far from the origin.
+        let (a, b) = (Point2D::new(1e8, 1e8), Point2D::new(1e8 + 3.0, 1e8 + 3.0));
+        let above = Point2D::new(1e8 + 1.0, 1e8 + 1.0 + 2.0f64.powi(-26));
+        assert_eq!(orient2d(a, b, Point2D::new(1e8 + 1.0, 1e8 + 1.0)), 0.0);
+        assert!(orient2d(a, b, above) > 0.0);
+    }
+
+    #[test]
+    fn test_incircle() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[53][..]);
+
+        // Integer points on a circle of radius 5^6 around a far away center.
+        let (radius, center) = (15625i128, (1i128 << 40, -(1i128 << 38)));
+        let circle: Vec<(i128, i128)> = (-radius..radius + 1)
+            .flat_map(|x| {
+                let y = ((radius * radius - x * x) as f64).sqrt() as i128;
+                vec![(x, y), (x, -y)]
+            })
+            .filter(|&(x, y)| x * x + y * y == radius * radius)
+            .map(|(x, y)| (center.0 + x, center.1 + y))
+            .collect();
+
+        // do test;
+        for _ in 0..10000 {
+            let mut pick = || circle[rng.gen_range(0, circle.len())];
+            let (a, b, c, mut d) = (pick(), pick(), pick(), pick());
+            d.0 += rng.gen_range(-1, 2) as i128;
+            d.1 += rng.gen_range(-1, 2) as i128;
+
+            let lift = |p: (i128, i128)| (p.0 - d.0) * (p.0 - d.0) + (p.1 - d.1) * (p.1 - d.1);
+            let cross = |p: (i128, i128), q: (i128, i128)| (p.0 - d.0) * (q.1 - d.1) - (p.1 - d.1) * (q.0 - d.0);
+            let exact = lift(a) * cross(b, c) + lift(b) * cross(c, a) + lift(c) * cross(a, b);
+            assert_eq!(sign(incircle(point(a), point(b), point(c), point(d))), exact.signum());
+        }
+    }
+}
@@ -0,0 +1,193 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::point::Point2D;
+    use matrix::rect::Rect;
+    use matrix::size::Size2D;
+    use matrix::spatial::{ItemId, QuadTree};
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect<f64> {
+        Rect::new(Point2D::new(x, y), Size2D::new(width, height))
+    }
+
+    fn overlaps(a: &Rect<f64>, b: &Rect<f64>) -> bool {
+        a.min_x() <= b.max_x() && b.min_x() <= a.max_x() && a.min_y() <= b.max_y() && b.min_y() <= a.max_y()
+    }
+
+    fn square_distance(rect: &Rect<f64>, point: &Point2D<f64>) -> f64 {
+        let dx = (rect.min_x() - point.x).max(point.x - rect.max_x()).max(0.0);
+        let dy = (rect.min_y() - point.y).max(point.y - rect.max_y()).max(0.0);
+        dx * dx + dy * dy
+    }
+
+    fn random_rect(rng: &mut StdRng) -> Rect<f64> {
+        // One in four items is a point, some reach outside the tree.
+        let (x, y) = (rng.gen_range(-10.0, 110.0), rng.gen_range(-10.0, 110.0));
+        if rng.gen_weighted_bool(4) {
+            return rect(x, y, 0.0, 0.0);
+        }
+        rect(x, y, rng.gen_range(0.0, 20.0), rng.gen_range(0.0, 20.0))
+    }
+
+    fn sorted(mut ids: Vec<ItemId>) -> Vec<ItemId> {
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut tree = QuadTree::new(rect(0.0, 0.0, 100.0, 100.0));
+
+        // do test;
+        let button = tree.insert(rect(10.0, 10.0, 30.0, 10.0), "button");
+        let cursor = tree.insert_point(Point2D::new(50.0, 50.0), "cursor");
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.get(button), Some(&"button"));
+        assert_eq!(tree.item_bounds(cursor), Some(rect(50.0, 50.0, 0.0, 0.0)));
+
+        *tree.get_mut(cursor).unwrap() = "pointer";
+        assert_eq!(tree.remove(cursor), Some("pointer"));
+        assert_eq!(tree.remove(cursor), None);
+        assert_eq!(tree.get(cursor), None);
+        assert_eq!(tree.len(), 1);
+
+        tree.clear();
+        assert!(tree.is_empty());
+        assert_eq!(tree.get(button), None);
+    }
+
+    #[test]
+    fn test_query_point() {
+        let mut tree = QuadTree::with_limits(rect(0.0, 0.0, 100.0, 100.0), 4, 1);
+        let window = tree.insert(rect(0.0, 0.0, 100.0, 100.0), ());
+        let panel = tree.insert(rect(10.0, 10.0, 40.0, 80.0), ());
+        let button = tree.insert(rect(20.0, 20.0, 10.0, 5.0), ());
+        let outside = tree.insert(rect(150.0, 0.0, 10.0, 10.0), ());
+
+        // do test;
+        assert_eq!(
+            sorted(tree.query_point(&Point2D::new(25.0, 22.0))),
+            vec![window, panel, button]
+        );
+        assert_eq!(sorted(tree.query_point(&Point2D::new(50.0, 90.0))), vec![window, panel]);
+        assert_eq!(sorted(tree.query_point(&Point2D::new(90.0, 90.0))), vec![window]);
+        assert_eq!(sorted(tree.query_point(&Point2D::new(155.0, 5.0))), vec![outside]);
+        assert!(tree.query_point(&Point2D::new(-1.0, 50.0)).is_empty());
+    }
+
+    #[test]
+    fn test_query_rect_includes_touching_items() {
+        let mut tree = QuadTree::new(rect(0.0, 0.0, 100.0, 100.0));
+        let left = tree.insert(rect(0.0, 0.0, 10.0, 10.0), ());
+        let corner = tree.insert_point(Point2D::new(20.0, 20.0), ());
+        tree.insert(rect(30.0, 30.0, 10.0, 10.0), ());
+
+        // do test;
+        assert_eq!(sorted(tree.query_rect(&rect(10.0, 5.0, 10.0, 15.0))), vec![left, corner]);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let mut tree = QuadTree::with_limits(rect(0.0, 0.0, 100.0, 100.0), 6, 2);
+        let ids: Vec<ItemId> = (0..10)
+            .map(|i| tree.insert_point(Point2D::new(i as f64 * 10.0, 0.0), i))
+            .collect();
+        let wide = tree.insert(rect(60.0, 40.0, 40.0, 10.0), 10);
+
+        // do test;
+        assert_eq!(tree.nearest(&Point2D::new(42.0, 1.0), 3), vec![ids[4], ids[5], ids[3]]);
+        assert_eq!(tree.nearest(&Point2D::new(80.0, 45.0), 1), vec![wide]);
+        assert_eq!(tree.nearest(&Point2D::new(0.0, 0.0), 50).len(), 11);
+        assert!(tree.nearest(&Point2D::new(0.0, 0.0), 0).is_empty());
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut shallow = QuadTree::with_limits(rect(0.0, 0.0, 64.0, 64.0), 2, 1);
+        let mut deep = QuadTree::with_limits(rect(0.0, 0.0, 64.0, 64.0), 10, 1);
+
+        // do test;
+        for i in 0..64 {
+            let point = Point2D::new(i as f64 + 0.5, i as f64 + 0.5);
+            shallow.insert_point(point, i);
+            deep.insert_point(point, i);
+        }
+
+        assert_eq!(shallow.max_depth(), 2);
+        assert_eq!(shallow.capacity(), 1);
+        assert_eq!(shallow.depth(), 2);
+        assert_eq!(deep.depth(), 6);
+    }
+
+    #[test]
+    fn test_remove_merges_nodes() {
+        let mut tree = QuadTree::with_limits(rect(0.0, 0.0, 100.0, 100.0), 8, 2);
+        let ids: Vec<ItemId> = (0..20)
+            .map(|i| tree.insert_point(Point2D::new(i as f64 * 5.0, 50.0), i))
+            .collect();
+        assert!(tree.depth() > 0);
+
+        // do test;
+        for (i, id) in ids.iter().enumerate() {
+            assert_eq!(tree.remove(*id), Some(i));
+        }
+        assert_eq!(tree.depth(), 0);
+
+        let id = tree.insert_point(Point2D::new(1.0, 1.0), 20);
+        assert_eq!(tree.nearest(&Point2D::new(0.0, 0.0), 5), vec![id]);
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[44][..]);
+        let mut tree = QuadTree::with_limits(rect(0.0, 0.0, 100.0, 100.0), 6, 4);
+        let mut items: Vec<(ItemId, Rect<f64>)> = Vec::new();
+
+        // do test;
+        for round in 0..400 {
+            if !items.is_empty() && rng.gen_weighted_bool(3) {
+                let (id, _) = items.swap_remove(rng.gen_range(0, items.len()));
+                assert!(tree.remove(id).is_some());
+            } else {
+                let bounds = random_rect(&mut rng);
+                items.push((tree.insert(bounds, round), bounds));
+            }
+            assert_eq!(tree.len(), items.len());
+
+            let query = random_rect(&mut rng);
+            let expected = items
+                .iter()
+                .filter(|&&(_, bounds)| overlaps(&bounds, &query))
+                .map(|&(id, _)| id)
+                .collect();
+            assert_eq!(sorted(tree.query_rect(&query)), sorted(expected));
+
+            let point = query.origin;
+            let expected = items
+                .iter()
+                .filter(|&&(_, bounds)| overlaps(&bounds, &rect(point.x, point.y, 0.0, 0.0)));
+            assert_eq!(
+                sorted(tree.query_point(&point)),
+                sorted(expected.map(|&(id, _)| id).collect())
+            );
+
+            let mut expected: Vec<(f64, ItemId)> = items
+                .iter()
+                .map(|&(id, bounds)| (square_distance(&bounds, &point), id))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let count = rng.gen_range(0, 12);
+            let expected: Vec<ItemId> = expected.iter().take(count).map(|&(_, id)| id).collect();
+            assert_eq!(tree.nearest(&point, count), expected);
+        }
+    }
+}
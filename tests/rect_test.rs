@@ -0,0 +1,133 @@
+extern crate matrix;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::num::Rounding;
+    use matrix::point::Point2D;
+    use matrix::rect::{Rect, TypedRect};
+    use matrix::scale_factor::ScaleFactor;
+    use matrix::size::Size2D;
+
+    #[derive(Debug, Copy, Clone)]
+    enum Css {}
+
+    #[derive(Debug, Copy, Clone)]
+    enum Device {}
+
+    #[test]
+    fn test_rect_corners() {
+        let rect = Rect::new(Point2D::new(1, 2), Size2D::new(10, 20));
+
+        // do test;
+        assert_eq!(rect.min(), Point2D::new(1, 2));
+        assert_eq!(rect.max(), Point2D::new(11, 22));
+        assert_eq!(rect.max_x(), 11);
+        assert_eq!(rect.max_y(), 22);
+        assert_eq!(Rect::from_corners(rect.min(), rect.max()), rect);
+    }
+
+    #[test]
+    fn test_rect_rounding() {
+        let rect = Rect::new(Point2D::new(0.4, 0.6), Size2D::new(2.0, 2.0));
+
+        // do test;
+        assert_eq!(rect.round(), Rect::new(Point2D::new(0.0, 1.0), Size2D::new(2.0, 2.0)));
+        assert_eq!(rect.round_in(), Rect::new(Point2D::new(1.0, 1.0), Size2D::new(1.0, 1.0)));
+        assert_eq!(rect.round_out(), Rect::new(Point2D::new(0.0, 0.0), Size2D::new(3.0, 3.0)));
+        assert_eq!(rect.round_with(Rounding::Trunc),
+                   Rect::new(Point2D::new(0.0, 0.0), Size2D::new(2.0, 2.0)));
+
+        // No whole pixel fits in a sub-pixel rect.
+        let thin = Rect::new(Point2D::new(0.2, 1.5), Size2D::new(0.6, 3.0));
+        assert_eq!(thin.round_in(), Rect::new(Point2D::new(1.0, 2.0), Size2D::new(0.0, 2.0)));
+        let typed: TypedRect<Css, f32> = TypedRect::typed(-0.8, 0.1, 0.6, 0.2);
+        assert_eq!(typed.cast_round_in::<i32>(), Some(TypedRect::typed(0, 1, 0, 0)));
+    }
+
+    #[test]
+    fn test_typed_rect_cast_round() {
+        let rect: TypedRect<Css, f32> = TypedRect::typed(0.5, 0.25, 10.0, 10.5);
+
+        // do test;
+        let truncated: TypedRect<Css, i32> = rect.cast().unwrap();
+        assert_eq!(truncated, TypedRect::typed(0, 0, 10, 10));
+
+        let nearest: TypedRect<Css, i32> = rect.cast_round(Rounding::Nearest).unwrap();
+        assert_eq!(nearest, TypedRect::typed(1, 0, 10, 11));
+
+        let inner: TypedRect<Css, i32> = rect.cast_round_in().unwrap();
+        assert_eq!(inner, TypedRect::typed(1, 1, 9, 9));
+
+        let outer: TypedRect<Css, i32> = rect.cast_round_out().unwrap();
+        assert_eq!(outer, TypedRect::typed(0, 0, 11, 11));
+    }
+
+    #[test]
+    fn test_snap_to_device_has_no_seams() {
+        let scale: ScaleFactor<Css, Device, f32> = ScaleFactor::new(1.5);
+        let left: TypedRect<Css, f32> = TypedRect::typed(0.0, 0.0, 1.0, 1.0);
+        let right: TypedRect<Css, f32> = TypedRect::typed(1.0, 0.0, 1.0, 1.0);
+
+        // do test;
+        let left = left.snap_to_device(scale).unwrap();
+        let right = right.snap_to_device(scale).unwrap();
+
+        assert_eq!(left.max_x(), right.min_x());
+        assert_eq!(left, TypedRect::typed(0, 0, 2, 2));
+        assert_eq!(right, TypedRect::typed(2, 0, 1, 2));
+    }
+
+    #[test]
+    fn test_snap_to_device_rejects_overflow() {
+        let scale: ScaleFactor<Css, Device, f64> = ScaleFactor::new(1.0e10);
+        let rect: TypedRect<Css, f64> = TypedRect::typed(0.0, 0.0, 1.0, 1.0);
+
+        // do test;
+        assert!(rect.snap_to_device(scale).is_none());
+    }
+
+    #[test]
+    fn test_scale() {
+        let rect = Rect::new(Point2D::new(1, 2), Size2D::new(3, 4));
+
+        // do test;
+        assert_eq!(rect * 2, Rect::new(Point2D::new(2, 4), Size2D::new(6, 8)));
+        assert_eq!(rect * 2 / 2, rect);
+    }
+
+    #[test]
+    fn test_overflow_aware_rect_ops() {
+        let rect = Rect::new(Point2D::new(1, 2), Size2D::new(3, 4));
+        let edge = Rect::new(Point2D::new(i32::MAX - 4, 0), Size2D::new(4, 4));
+
+        // do test;
+        assert_eq!(rect.checked_max(), Some(Point2D::new(4, 6)));
+        assert_eq!(edge.checked_max(), Some(Point2D::new(i32::MAX, 4)));
+        assert_eq!(rect.checked_translate(&Point2D::new(1, -2)),
+                   Some(Rect::new(Point2D::new(2, 0), Size2D::new(3, 4))));
+        assert_eq!(edge.checked_translate(&Point2D::new(1, 0)), None);
+        assert_eq!(rect.checked_mul(2), Some(Rect::new(Point2D::new(2, 4), Size2D::new(6, 8))));
+        assert_eq!(edge.checked_mul(2), None);
+
+        assert_eq!(edge.saturating_translate(&Point2D::new(10, 0)).origin, Point2D::new(i32::MAX, 0));
+        assert_eq!(edge.saturating_mul(2), Rect::new(Point2D::new(i32::MAX, 0), Size2D::new(8, 8)));
+        assert_eq!(edge.wrapping_translate(&Point2D::new(5, 0)).origin, Point2D::new(i32::MIN, 0));
+        assert_eq!(edge.wrapping_mul(2).origin, Point2D::new(-10, 0));
+
+        let size = Rect::new(Point2D::new(0u32, 0), Size2D::new(u32::MAX, 1));
+        assert_eq!(size.checked_max(), Some(Point2D::new(u32::MAX, 1)));
+        assert_eq!(size.checked_translate(&Point2D::new(1, 0)), None);
+    }
+
+    #[test]
+    fn test_scale_factor() {
+        let scale: ScaleFactor<Css, Device, f32> = ScaleFactor::new(2.0);
+        let rect: TypedRect<Css, f32> = TypedRect::typed(1.0, 2.0, 3.0, 4.0);
+
+        // do test;
+        let device: TypedRect<Device, f32> = rect * scale;
+        assert_eq!(device, TypedRect::typed(2.0, 4.0, 6.0, 8.0));
+        assert_eq!(device / scale, rect);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::clone_on_copy)]
+
 extern crate matrix;
 
 #[cfg(test)]
@@ -48,4 +50,22 @@ mod tests {
         assert_eq!(a.clone() + b.clone(), ScaleFactor::new(5));
         assert_eq!(a - b, ScaleFactor::new(-1));
     }
+
+    #[test]
+    fn test_scale_factor_compound_assignment() {
+        let mut factor: ScaleFactor<Inch, Mm, f32> = ScaleFactor::new(25.0);
+
+        // do test;
+        factor += ScaleFactor::new(0.5);
+        assert_eq!(factor.get(), 25.5);
+
+        factor -= ScaleFactor::new(0.1);
+        assert_eq!(factor.get(), 25.4);
+
+        factor *= 2.0;
+        assert_eq!(factor.get(), 50.8);
+
+        factor /= 4.0;
+        assert_eq!(factor.get(), 12.7);
+    }
 }
@@ -0,0 +1,375 @@
+#![cfg(feature = "serde")]
+
+extern crate matrix;
+#[macro_use]
+extern crate serde;
+
+// .
+// . Minimal in-memory serializer used to round-trip the geometry types.
+// .
+#[cfg(test)]
+mod value {
+
+    use serde::de::{self, DeserializeOwned, IntoDeserializer, MapAccess, Visitor};
+    use serde::ser::{self, Impossible, Serialize};
+    use std::fmt;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value {
+        I(i64),
+        U(u64),
+        F(f64),
+        Struct(Vec<(&'static str, Value)>),
+    }
+
+    #[derive(Debug)]
+    pub struct Error(String);
+
+    impl fmt::Display for Error {
+        fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(&self.0)
+        }
+    }
+
+    impl ::std::error::Error for Error {}
+
+    impl ser::Error for Error {
+        fn custom<T: fmt::Display>(msg: T) -> Error {
+            Error(msg.to_string())
+        }
+    }
+
+    impl de::Error for Error {
+        fn custom<T: fmt::Display>(msg: T) -> Error {
+            Error(msg.to_string())
+        }
+    }
+
+    pub fn to_value<T: Serialize>(value: &T) -> Result<Value, Error> {
+        value.serialize(Serializer)
+    }
+
+    pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+        T::deserialize(value)
+    }
+
+    pub struct Serializer;
+
+    pub struct StructSerializer(Vec<(&'static str, Value)>);
+
+    fn unsupported<T>(what: &str) -> Result<T, Error> {
+        Err(Error(format!("unsupported type: {}", what)))
+    }
+
+    impl ser::Serializer for Serializer {
+        type Ok = Value;
+        type Error = Error;
+        type SerializeSeq = Impossible<Value, Error>;
+        type SerializeTuple = Impossible<Value, Error>;
+        type SerializeTupleStruct = Impossible<Value, Error>;
+        type SerializeTupleVariant = Impossible<Value, Error>;
+        type SerializeMap = Impossible<Value, Error>;
+        type SerializeStruct = StructSerializer;
+        type SerializeStructVariant = Impossible<Value, Error>;
+
+        fn serialize_bool(self, _: bool) -> Result<Value, Error> {
+            unsupported("bool")
+        }
+
+        fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+            Ok(Value::I(v as i64))
+        }
+
+        fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+            Ok(Value::I(v as i64))
+        }
+
+        fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+            Ok(Value::I(v as i64))
+        }
+
+        fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+            Ok(Value::I(v))
+        }
+
+        fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+            Ok(Value::U(v as u64))
+        }
+
+        fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+            Ok(Value::U(v as u64))
+        }
+
+        fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+            Ok(Value::U(v as u64))
+        }
+
+        fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+            Ok(Value::U(v))
+        }
+
+        fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+            Ok(Value::F(v as f64))
+        }
+
+        fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+            Ok(Value::F(v))
+        }
+
+        fn serialize_char(self, _: char) -> Result<Value, Error> {
+            unsupported("char")
+        }
+
+        fn serialize_str(self, _: &str) -> Result<Value, Error> {
+            unsupported("str")
+        }
+
+        // Spelled out since serde only provides it when built with its std feature.
+        fn collect_str<T: ?Sized + fmt::Display>(self, _: &T) -> Result<Value, Error> {
+            unsupported("str")
+        }
+
+        fn serialize_bytes(self, _: &[u8]) -> Result<Value, Error> {
+            unsupported("bytes")
+        }
+
+        fn serialize_none(self) -> Result<Value, Error> {
+            unsupported("option")
+        }
+
+        fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<Value, Error> {
+            unsupported("option")
+        }
+
+        fn serialize_unit(self) -> Result<Value, Error> {
+            unsupported("unit")
+        }
+
+        fn serialize_unit_struct(self, _: &'static str) -> Result<Value, Error> {
+            unsupported("unit struct")
+        }
+
+        fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Result<Value, Error> {
+            unsupported("unit variant")
+        }
+
+        fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _: &'static str, value: &T) -> Result<Value, Error> {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T: ?Sized + Serialize>(self,
+                                                            _: &'static str,
+                                                            _: u32,
+                                                            _: &'static str,
+                                                            _: &T)
+                                                            -> Result<Value, Error> {
+            unsupported("newtype variant")
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+            unsupported("seq")
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+            unsupported("tuple")
+        }
+
+        fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Error> {
+            unsupported("tuple struct")
+        }
+
+        fn serialize_tuple_variant(self,
+                                   _: &'static str,
+                                   _: u32,
+                                   _: &'static str,
+                                   _: usize)
+                                   -> Result<Self::SerializeTupleVariant, Error> {
+            unsupported("tuple variant")
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+            unsupported("map")
+        }
+
+        fn serialize_struct(self, _: &'static str, len: usize) -> Result<StructSerializer, Error> {
+            Ok(StructSerializer(Vec::with_capacity(len)))
+        }
+
+        fn serialize_struct_variant(self,
+                                    _: &'static str,
+                                    _: u32,
+                                    _: &'static str,
+                                    _: usize)
+                                    -> Result<Self::SerializeStructVariant, Error> {
+            unsupported("struct variant")
+        }
+    }
+
+    impl ser::SerializeStruct for StructSerializer {
+        type Ok = Value;
+        type Error = Error;
+
+        fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+            self.0.push((key, value.serialize(Serializer)?));
+            Ok(())
+        }
+
+        fn end(self) -> Result<Value, Error> {
+            Ok(Value::Struct(self.0))
+        }
+    }
+
+    struct FieldAccess {
+        fields: ::std::vec::IntoIter<(&'static str, Value)>,
+        value: Option<Value>,
+    }
+
+    impl<'de> MapAccess<'de> for FieldAccess {
+        type Error = Error;
+
+        fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+            match self.fields.next() {
+                Some((key, value)) => {
+                    self.value = Some(value);
+                    seed.deserialize(key.into_deserializer()).map(Some)
+                }
+                None => Ok(None),
+            }
+        }
+
+        fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+            match self.value.take() {
+                Some(value) => seed.deserialize(value),
+                None => Err(de::Error::custom("value is missing")),
+            }
+        }
+    }
+
+    impl<'de> de::Deserializer<'de> for Value {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self {
+                Value::I(v) => visitor.visit_i64(v),
+                Value::U(v) => visitor.visit_u64(v),
+                Value::F(v) => visitor.visit_f64(v),
+                Value::Struct(fields) => {
+                    visitor.visit_map(FieldAccess {
+                        fields: fields.into_iter(),
+                        value: None,
+                    })
+                }
+            }
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::length::Length;
+    use matrix::matrix2d::Matrix2D;
+    use matrix::matrix4d::Matrix4D;
+    use matrix::point::{Point2D, Point3D, Point4D, TypedPoint2D};
+    use matrix::scale_factor::ScaleFactor;
+    use matrix::size::{Size2D, TypedSize2D};
+
+    use value::{from_value, to_value, Value};
+
+    #[derive(Debug, Copy, Clone)]
+    enum Mm {}
+
+    #[derive(Debug, Copy, Clone)]
+    enum Cm {}
+
+    #[test]
+    fn test_length_is_transparent() {
+        let length: Length<Mm, f32> = Length::new(12.5);
+
+        // do test;
+        let value = to_value(&length).unwrap();
+        assert_eq!(value, Value::F(12.5));
+
+        let result: Length<Mm, f32> = from_value(value).unwrap();
+        assert_eq!(result, length);
+    }
+
+    #[test]
+    fn test_scale_factor_is_transparent() {
+        let cm_per_mm: ScaleFactor<Mm, Cm, i32> = ScaleFactor::new(10);
+
+        // do test;
+        let value = to_value(&cm_per_mm).unwrap();
+        assert_eq!(value, Value::I(10));
+
+        let result: ScaleFactor<Mm, Cm, i32> = from_value(value).unwrap();
+        assert_eq!(result, cm_per_mm);
+    }
+
+    #[test]
+    fn test_points_round_trip() {
+        let p2 = Point2D::new(1.0, -2.0);
+        let p3 = Point3D::new(1u32, 2, 3);
+        let p4 = Point4D::new(1.0, 2.0, 3.0, 4.0);
+
+        // do test;
+        assert_eq!(to_value(&p2).unwrap(),
+                   Value::Struct(vec![("x", Value::F(1.0)), ("y", Value::F(-2.0))]));
+
+        assert_eq!(from_value::<Point2D<f64>>(to_value(&p2).unwrap()).unwrap(), p2);
+        assert_eq!(from_value::<Point3D<u32>>(to_value(&p3).unwrap()).unwrap(), p3);
+        assert_eq!(from_value::<Point4D<f32>>(to_value(&p4).unwrap()).unwrap(), p4);
+    }
+
+    #[test]
+    fn test_typed_point_serializes_as_untyped() {
+        let typed: TypedPoint2D<Mm, f32> = TypedPoint2D::typed(3.0, 4.0);
+
+        // do test;
+        let value = to_value(&typed).unwrap();
+        assert_eq!(value, to_value(&Point2D::new(3.0f32, 4.0)).unwrap());
+        assert_eq!(from_value::<TypedPoint2D<Mm, f32>>(value).unwrap(), typed);
+    }
+
+    #[test]
+    fn test_sizes_round_trip() {
+        let size = Size2D::new(640u32, 480);
+        let typed: TypedSize2D<Mm, f32> = TypedSize2D::typed(3.0, 5.0);
+
+        // do test;
+        assert_eq!(from_value::<Size2D<u32>>(to_value(&size).unwrap()).unwrap(), size);
+        assert_eq!(from_value::<TypedSize2D<Mm, f32>>(to_value(&typed).unwrap()).unwrap(),
+                   typed);
+    }
+
+    #[test]
+    fn test_matrices_round_trip() {
+        let m2 = Matrix2D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+        let m4 = Matrix4D::indentity().translate(1.0, 2.0, 3.0);
+
+        // do test;
+        let result2: Matrix2D<f64> = from_value(to_value(&m2).unwrap()).unwrap();
+        assert_eq!(result2.to_array(), m2.to_array());
+
+        let result4: Matrix4D = from_value(to_value(&m4).unwrap()).unwrap();
+        assert_eq!(result4, m4);
+    }
+
+    #[test]
+    fn test_malformed_input_is_rejected() {
+        let value = Value::Struct(vec![("x", Value::F(1.0))]);
+
+        // do test;
+        assert!(from_value::<Point2D<f32>>(value).is_err());
+    }
+}
@@ -80,3 +80,134 @@ mod tests {
         // assert!(size == typed_size);
     }
 }
+
+#[cfg(test)]
+mod overflow_tests {
+
+    use matrix::size::Size2D;
+
+    #[test]
+    fn test_checked_area() {
+        let size = Size2D::new(70_000u32, 70_000);
+
+        // do test;
+        assert_eq!(size.checked_area(), None);
+        assert_eq!(Size2D::new(640u32, 480).checked_area(), Some(307_200));
+    }
+
+    #[test]
+    fn test_checked_size_ops() {
+        let size = Size2D::new(u32::MAX, 1);
+
+        // do test;
+        assert_eq!(size.checked_add(&Size2D::new(1, 1)), None);
+        assert_eq!(size.checked_sub(&Size2D::new(1, 1)), Some(Size2D::new(u32::MAX - 1, 0)));
+        assert_eq!(size.checked_mul(2), None);
+        assert_eq!(size.saturating_add(&Size2D::new(1, 1)), Size2D::new(u32::MAX, 2));
+        assert_eq!(size.saturating_sub(&Size2D::new(2, 2)), Size2D::new(u32::MAX - 2, 0));
+        assert_eq!(size.saturating_mul(3), Size2D::new(u32::MAX, 3));
+        assert_eq!(size.wrapping_add(&Size2D::new(1, 1)), Size2D::new(0, 2));
+        assert_eq!(size.wrapping_mul(2), Size2D::new(u32::MAX - 1, 2));
+    }
+}
+
+#[cfg(test)]
+mod rounding_tests {
+
+    use matrix::num::Rounding;
+    use matrix::scale_factor::ScaleFactor;
+    use matrix::size::{Size2D, TypedSize2D};
+
+    #[derive(Debug, Copy, Clone)]
+    enum Css {}
+
+    #[derive(Debug, Copy, Clone)]
+    enum Device {}
+
+    #[test]
+    fn test_size_rounding() {
+        let size = Size2D::new(2.5, 3.2);
+
+        // do test;
+        assert_eq!(size.round(), Size2D::new(3.0, 3.0));
+        assert_eq!(size.floor(), Size2D::new(2.0, 3.0));
+        assert_eq!(size.ceil(), Size2D::new(3.0, 4.0));
+        assert_eq!(size.trunc(), Size2D::new(2.0, 3.0));
+    }
+
+    #[test]
+    fn test_size_cast_round() {
+        let size: TypedSize2D<Css, f32> = TypedSize2D::typed(2.9, 3.1);
+
+        // do test;
+        assert_eq!(size.as_usize(), TypedSize2D::typed(2, 3));
+        assert_eq!(size.cast_round::<usize>(Rounding::Nearest).unwrap(), TypedSize2D::typed(3, 3));
+        assert_eq!(size.cast_round::<usize>(Rounding::Ceil).unwrap(), TypedSize2D::typed(3, 4));
+    }
+
+    #[test]
+    fn test_size_snap_to_device() {
+        let size: TypedSize2D<Css, f32> = TypedSize2D::typed(100.0, 33.3);
+        let scale: ScaleFactor<Css, Device, f32> = ScaleFactor::new(1.5);
+
+        // do test;
+        assert_eq!(size.snap_to_device(scale).unwrap(), TypedSize2D::typed(150, 50));
+    }
+}
+
+#[cfg(test)]
+mod assignment_tests {
+
+    use matrix::scale_factor::ScaleFactor;
+    use matrix::size::{Size2D, TypedSize2D};
+
+    #[test]
+    fn test_size_compound_assignment() {
+        let mut size = Size2D::new(3, 5);
+
+        // do test;
+        size *= 10;
+        assert_eq!(size, Size2D::new(30, 50));
+
+        size /= 5;
+        assert_eq!(size, Size2D::new(6, 10));
+
+        size += Size2D::new(4, 0);
+        assert_eq!(size, Size2D::new(10, 10));
+
+        size -= Size2D::new(1, 2);
+        assert_eq!(size, Size2D::new(9, 8));
+    }
+
+    #[test]
+    fn test_scale_factor() {
+        enum Pt {}
+        enum Px {}
+
+        let size: TypedSize2D<Pt, f32> = TypedSize2D::typed(30.0, 12.0);
+        let px_per_pt: ScaleFactor<Pt, Px, f32> = ScaleFactor::new(2.0);
+
+        // do test;
+        let scaled: TypedSize2D<Px, f32> = size.clone() * px_per_pt.clone();
+        assert_eq!(scaled.untyped(), Size2D::new(60.0, 24.0));
+        assert_eq!((scaled / px_per_pt).untyped(), size.untyped());
+    }
+}
+
+#[cfg(test)]
+mod size3d_tests {
+
+    use matrix::num::Zero;
+    use matrix::size::Size3D;
+
+    #[test]
+    fn test_size3d() {
+        let size = Size3D::new(2.0, 3.0, 4.0);
+
+        // do test;
+        assert_eq!(size.volume(), 24.0);
+        assert_eq!(format!("{:?}", size), "2.0x3.0x4.0");
+        assert_eq!(format!("{}", Size3D::new(1, 2, 3)), "(1x2x3)");
+        assert_eq!(Size3D::<i32>::zero(), Size3D::new(0, 0, 0));
+    }
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::matrix4d::{self, Matrix4D};
+    use matrix::point::Point3D;
+    use matrix::transform::{Operation, ParseError, Transform};
+    use std::f64::consts::PI;
+
+    fn apply(source: &str, x: f32, y: f32) -> (f32, f32) {
+        let point = Transform::parse(source).unwrap().to_matrix().transform_point3d(&Point3D::new(x, y, 0.0)).unwrap();
+        (point.x, point.y)
+    }
+
+    fn assert_close(actual: (f32, f32), expected: (f32, f32)) {
+        assert!((actual.0 - expected.0).abs() < 1.0e-4 && (actual.1 - expected.1).abs() < 1.0e-4,
+                "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn test_parse() {
+        let transform: Transform = "translate(10px, 20) rotate(0.5turn) scale(2 3)".parse().unwrap();
+
+        // do test;
+        assert_eq!(transform.operations, vec![
+            Operation::Translate(10.0, 20.0, 0.0),
+            Operation::Rotate(0.0, 0.0, 1.0, PI),
+            Operation::Scale(2.0, 3.0, 1.0),
+        ]);
+        assert_eq!(Transform::parse("  ").unwrap(), Transform::default());
+        assert_eq!(Transform::parse("translateX(5)").unwrap().operations, vec![Operation::Translate(5.0, 0.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_angle_units() {
+        let angles: Vec<Operation> = ["rotate(180deg)", "rotate(200grad)", "rotate(3.1415927rad)", "rotate(0.5turn)"]
+            .iter()
+            .map(|source| Transform::parse(source).unwrap().operations[0])
+            .collect();
+
+        // do test;
+        for operation in &angles {
+            match *operation {
+                Operation::Rotate(_, _, _, angle) => assert!((angle - PI).abs() < 1.0e-6),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(Transform::parse("rotate(0)").unwrap().operations, vec![Operation::Rotate(0.0, 0.0, 1.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_right_to_left_order() {
+        assert_close(apply("translate(10, 20) rotate(90deg) scale(2)", 1.0, 0.0), (10.0, 22.0));
+        assert_close(apply("scale(2) translate(10, 20)", 1.0, 0.0), (22.0, 40.0));
+        assert_close(apply("skewX(45deg)", 0.0, 1.0), (1.0, 1.0));
+        assert_close(apply("matrix(1, 2, 3, 4, 5, 6)", 1.0, 1.0), (9.0, 12.0));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        assert!(Transform::parse("translate(1, 2) rotate(30deg) skew(10deg) matrix(1, 0, 0, 1, 5, 5)").unwrap().is_2d());
+        assert!(Transform::parse("rotate3d(0, 0, 2, 30deg) translateZ(0)").unwrap().is_2d());
+        assert!(!Transform::parse("rotateY(30deg)").unwrap().is_2d());
+        assert!(!Transform::parse("perspective(100px)").unwrap().is_2d());
+        assert!(!Transform::parse("translate3d(0, 0, 1)").unwrap().is_2d());
+    }
+
+    #[test]
+    fn test_double_precision_rows() {
+        let transform = Transform::parse("translate(500000.123, 4500000.456) rotate(90deg) scale(2)").unwrap();
+
+        // do test;
+        let rows = transform.to_rows();
+        assert_eq!(rows[3][0], 500000.123);
+        assert_eq!(rows[3][1], 4500000.456);
+        assert!((rows[0][1] - 2.0).abs() < 1.0e-12 && (rows[1][0] + 2.0).abs() < 1.0e-12);
+        let small = Transform::parse("translate(5, 6) rotate(90deg) scale(2)").unwrap().to_matrix();
+        let expected = Matrix4D::indentity().translate(5.0, 6.0, 0.0).rotate(0.0, 0.0, 1.0, PI as f32 / 2.0);
+        assert!(small.approx_eq(&expected.scale(2.0, 2.0, 1.0)));
+
+        let inverse = matrix4d::invert_rows(rows).unwrap();
+        let origin: f64 = (0..4).map(|k| rows[3][k] * inverse[k][0]).sum();
+        assert!(origin.abs() < 1.0e-9);
+        assert_eq!(matrix4d::invert_rows(Transform::parse("scale(0)").unwrap().to_rows()), None);
+    }
+
+    #[test]
+    fn test_perspective() {
+        let matrix = Transform::parse("perspective(100) translateZ(50)").unwrap().to_matrix();
+
+        // do test;
+        let point = matrix.transform_point3d(&Point3D::new(10.0, 10.0, 0.0)).unwrap();
+        assert_close((point.x, point.y), (20.0, 20.0));
+        assert_eq!(Transform::parse("matrix3d(1,0,0,0, 0,1,0,0, 0,0,1,0, 0,0,0,1)").unwrap().to_matrix(),
+                   Matrix4D::indentity());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Transform::parse("translate(1, 2"), Err(ParseError::Syntax("translate(1, 2".to_owned())));
+        assert_eq!(Transform::parse("(1)"), Err(ParseError::Syntax("(1)".to_owned())));
+        assert_eq!(Transform::parse("twist(1deg)"), Err(ParseError::UnknownFunction("twist".to_owned())));
+        assert_eq!(Transform::parse("scale(1, 2, 3)"), Err(ParseError::ArgumentCount("scale".to_owned(), 3)));
+        assert_eq!(Transform::parse("translate(1em)"), Err(ParseError::InvalidNumber("1em".to_owned())));
+        assert_eq!(Transform::parse("scale(inf)"), Err(ParseError::InvalidNumber("inf".to_owned())));
+        assert_eq!(Transform::parse("rotate(30)"), Err(ParseError::InvalidAngle("30".to_owned())));
+        assert_eq!(Transform::parse("perspective(0)"), Err(ParseError::InvalidDistance("0".to_owned())));
+        assert_eq!(Transform::parse("perspective(-10px)"), Err(ParseError::InvalidDistance("-10px".to_owned())));
+        assert_eq!(Transform::parse("perspective(nan)"), Err(ParseError::InvalidNumber("nan".to_owned())));
+
+        let error = Transform::parse("rotate(30)").unwrap_err();
+        assert_eq!(error.to_string(), "invalid angle '30', expected a unit of deg, rad, grad or turn");
+    }
+}
@@ -0,0 +1,208 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::geometry::{signed_area, triangulate, triangulate_with_holes};
+    use matrix::point::Point2D;
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    use std::f64::consts::PI;
+
+    fn polygon(coordinates: &[(f64, f64)]) -> Vec<Point2D<f64>> {
+        coordinates.iter().map(|&(x, y)| Point2D::new(x, y)).collect()
+    }
+
+    fn square(x: f64, y: f64, side: f64) -> Vec<Point2D<f64>> {
+        polygon(&[(x, y), (x + side, y), (x + side, y + side), (x, y + side)])
+    }
+
+    fn reversed(mut points: Vec<Point2D<f64>>) -> Vec<Point2D<f64>> {
+        points.reverse();
+        points
+    }
+
+    /// Sums the triangle areas, checking every index and every triangle's orientation.
+    fn triangle_area(vertices: &[Point2D<f64>], indices: &[u32]) -> f64 {
+        assert_eq!(indices.len() % 3, 0);
+
+        indices
+            .chunks(3)
+            .map(|triangle| {
+                let triangle: Vec<Point2D<f64>> = triangle.iter().map(|&index| vertices[index as usize]).collect();
+                let area = signed_area(&triangle);
+                assert!(area >= -1e-9, "clockwise triangle {:?}", triangle);
+                area
+            })
+            .sum()
+    }
+
+    /// Checks the triangles cover the outline less the holes.
+    fn check(outline: &[Point2D<f64>], holes: &[Vec<Point2D<f64>>]) -> Vec<u32> {
+        let indices = triangulate_with_holes(outline, holes);
+        let vertices: Vec<Point2D<f64>> = holes.iter().fold(outline.to_vec(), |mut vertices, hole| {
+            vertices.extend_from_slice(hole);
+            vertices
+        });
+        let expected = holes
+            .iter()
+            .fold(signed_area(outline).abs(), |area, hole| area - signed_area(hole).abs());
+
+        let area = triangle_area(&vertices, &indices);
+        assert!(
+            (area - expected).abs() <= 1e-9 * expected.max(1.0),
+            "{} != {}",
+            area,
+            expected
+        );
+        indices
+    }
+
+    fn circle(rng: &mut StdRng, center: Point2D<f64>, vertices: usize, min: f64, max: f64) -> Vec<Point2D<f64>> {
+        (0..vertices)
+            .map(|i| {
+                let angle = i as f64 * 2.0 * PI / vertices as f64;
+                center + Point2D::new(angle.cos(), angle.sin()) * rng.gen_range(min, max)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_signed_area() {
+        let outline = square(0.0, 0.0, 2.0);
+
+        // do test;
+        assert_eq!(signed_area(&outline), 4.0);
+        assert_eq!(signed_area(&reversed(outline)), -4.0);
+        assert_eq!(signed_area::<f64>(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_convex_and_concave() {
+        let comb = polygon(&[
+            (0.0, 0.0),
+            (5.0, 0.0),
+            (5.0, 3.0),
+            (4.0, 3.0),
+            (4.0, 1.0),
+            (3.0, 1.0),
+            (3.0, 3.0),
+            (0.0, 3.0),
+        ]);
+
+        // do test;
+        let indices = check(&square(0.0, 0.0, 1.0), &[]);
+        assert_eq!(indices.len(), 6);
+
+        assert_eq!(check(&comb, &[]).len(), 18);
+        assert_eq!(check(&reversed(comb), &[]).len(), 18);
+    }
+
+    #[test]
+    fn test_collinear_and_duplicate_vertices() {
+        let outline = polygon(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (2.0, 2.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+            (0.0, 2.0),
+            (0.0, 1.0),
+            (0.0, 0.0),
+        ]);
+
+        // do test;
+        assert_eq!(check(&outline, &[]).len(), 6);
+        assert!(triangulate(&outline[..2]).is_empty());
+        assert!(triangulate(&polygon(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)])).is_empty());
+        assert!(triangulate::<f64>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_holes() {
+        let outline = square(0.0, 0.0, 10.0);
+
+        // do test;
+        check(&outline, &[reversed(square(2.0, 2.0, 2.0))]);
+
+        // Holes are holes whatever their orientation.
+        check(&outline, &[square(2.0, 2.0, 2.0), reversed(square(5.0, 5.0, 3.0))]);
+
+        // A hole touching the outline at a vertex, and holes touching each other.
+        check(&outline, &[square(0.0, 0.0, 1.0)]);
+        check(&outline, &[square(2.0, 2.0, 2.0), square(4.0, 2.0, 2.0)]);
+        check(
+            &outline,
+            &[square(2.0, 2.0, 2.0), square(4.0, 4.0, 1.0), square(0.0, 0.0, 2.0)],
+        );
+    }
+
+    #[test]
+    fn test_f32() {
+        let outline = [
+            Point2D::new(0.0f32, 0.0),
+            Point2D::new(4.0, 0.0),
+            Point2D::new(4.0, 4.0),
+            Point2D::new(0.0, 4.0),
+        ];
+        let hole = [
+            Point2D::new(1.0f32, 1.0),
+            Point2D::new(1.0, 3.0),
+            Point2D::new(3.0, 3.0),
+            Point2D::new(3.0, 1.0),
+        ];
+
+        // do test;
+        let indices = triangulate_with_holes(&outline, &[&hole[..]]);
+        assert_eq!(indices.len(), 8 * 3);
+        assert!(indices.iter().all(|&index| index < 8));
+    }
+
+    #[test]
+    fn test_random_polygons_with_holes() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[48][..]);
+
+        // do test;
+        for round in 0..20 {
+            // Star shaped outlines reach at least 50 out, the holes stay within 35.
+            let outline = circle(&mut rng, Point2D::new(0.0, 0.0), 16 + 8 * round, 50.0, 100.0);
+            let mut holes = Vec::new();
+
+            for x in -3..4 {
+                for y in -3..4 {
+                    if rng.gen_weighted_bool(2) {
+                        let center = Point2D::new(x as f64 * 10.0, y as f64 * 10.0);
+                        let vertices = rng.gen_range(3, 12);
+                        let hole = circle(&mut rng, center, vertices, 1.0, 4.5);
+                        holes.push(if rng.gen_weighted_bool(2) { reversed(hole) } else { hole });
+                    }
+                }
+            }
+
+            if round % 2 == 0 {
+                let indices = check(&outline, &holes);
+                let vertices = outline.len() + holes.iter().map(|hole| hole.len()).sum::<usize>();
+                assert_eq!(indices.len(), 3 * (vertices + 2 * holes.len() - 2));
+            } else {
+                // Repeats some vertices and splits some edges in the middle.
+                let mut noisy = Vec::new();
+                for (i, &point) in outline.iter().enumerate() {
+                    noisy.push(point);
+                    if rng.gen_weighted_bool(3) {
+                        noisy.push(point);
+                    } else if rng.gen_weighted_bool(2) {
+                        noisy.push(point.lerp(&outline[(i + 1) % outline.len()], 0.5));
+                    }
+                }
+                check(&noisy, &holes);
+            }
+        }
+    }
+}
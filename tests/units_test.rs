@@ -0,0 +1,86 @@
+extern crate matrix;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::approxeq::ApproxEq;
+    use matrix::length::Length;
+    use matrix::scale_factor::ScaleFactor;
+    use matrix::size::TypedSize2D;
+    use matrix::units::{self, Cm, CssLength, CssPx, DeviceLength, DevicePx, Inch, Mm, Pica, Pt};
+    use matrix::units::{CM_PER_INCH, MM_PER_INCH, PT_PER_INCH, PX_PER_INCH, PX_PER_PT};
+
+    const HALF_INCH: Length<Inch, f32> = Length::new(0.5);
+
+    #[test]
+    fn test_standard_factors() {
+        assert_eq!((HALF_INCH * PX_PER_INCH).get(), 48.0);
+        assert_eq!((HALF_INCH * PT_PER_INCH).get(), 36.0);
+        assert_eq!((HALF_INCH * MM_PER_INCH).get(), 12.7);
+        assert_eq!((HALF_INCH * CM_PER_INCH).get(), 1.27);
+
+        let pt: Length<Pt, f32> = Length::new(12.0);
+        assert!((pt * PX_PER_PT).get().approx_eq(&16.0));
+    }
+
+    #[test]
+    fn test_generic_factor() {
+        let pt_per_pica: ScaleFactor<Pica, Pt, f64> = units::factor();
+        let px_per_mm: ScaleFactor<Mm, CssPx, f64> = units::factor();
+        let mm_per_cm: ScaleFactor<Cm, Mm, f32> = units::factor();
+
+        // do test;
+        assert_eq!(pt_per_pica.get(), 12.0);
+        assert!(px_per_mm.get().approx_eq(&(96.0 / 25.4)));
+        assert_eq!(mm_per_cm.get(), 10.0);
+        assert!((px_per_mm * px_per_mm.inv()).get().approx_eq(&1.0));
+    }
+
+    #[test]
+    fn test_a4_width_in_css_px() {
+        let a4_width: Length<Mm, f64> = Length::new(210.0);
+
+        // do test;
+        let px: CssLength<f64> = a4_width * units::factor();
+        assert!(px.get().approx_eq_eps(&793.7, &0.1));
+    }
+
+    #[test]
+    fn test_device_factors() {
+        let twelve_pt: Length<Pt, f32> = Length::new(12.0);
+
+        // do test;
+        let at_192dpi: DeviceLength<f32> = twelve_pt * units::device_factor(192.0);
+        assert_eq!(at_192dpi.get(), 32.0);
+
+        let px: CssLength<f32> = twelve_pt * PX_PER_PT;
+        let retina: ScaleFactor<CssPx, DevicePx, f32> = units::device_pixel_ratio(2.0);
+        assert!((px * retina).get().approx_eq(&32.0));
+    }
+
+    #[test]
+    fn test_typed_sizes_share_units() {
+        let size: TypedSize2D<CssPx, f32> = TypedSize2D::typed(96.0, 48.0);
+
+        // do test;
+        let inches = size * PX_PER_INCH.inv();
+        assert_eq!(inches, TypedSize2D::<Inch, f32>::typed(1.0, 0.5));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_unit_graph() {
+        let graph = units::unit_graph(192.0).unwrap();
+
+        // do test;
+        assert!(graph.convert(12.0, "pt", "dpx").unwrap().approx_eq(&32.0));
+        assert!(graph.convert(210.0, "mm", "px").unwrap().approx_eq_eps(&793.700787, &1.0e-6));
+        assert!(graph.convert(1.0, "pc", "pt").unwrap().approx_eq(&12.0));
+        assert!(graph.convert(2.0, "cm", "mm").unwrap().approx_eq(&20.0));
+        assert!(graph.convert(1.5, "px", "au").unwrap().approx_eq(&90.0));
+        assert!(graph.convert(96.0, "dpx", "in").unwrap().approx_eq(&0.5));
+        assert!(!graph.contains("em"));
+
+        assert!(units::unit_graph(0.0).is_err());
+    }
+}
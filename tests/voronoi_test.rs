@@ -0,0 +1,70 @@
+#![cfg(feature = "std")]
+
+extern crate matrix;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+
+    use matrix::geometry::{signed_area, Voronoi};
+    use matrix::point::Point2D;
+    use matrix::rect::Rect;
+
+    use rand::{Rng, SeedableRng, StdRng};
+
+    fn bounds() -> Rect<f64> {
+        Rect::from_corners(Point2D::new(0.0, 0.0), Point2D::new(100.0, 50.0))
+    }
+
+    /// Whether the point lies inside the convex counter-clockwise polygon, up to a tolerance.
+    fn contains(polygon: &[Point2D<f64>], point: Point2D<f64>) -> bool {
+        (0..polygon.len()).all(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            (b - a).cross(point - a) >= -1e-9 * (b - a).dot(b - a).sqrt()
+        })
+    }
+
+    #[test]
+    fn test_cells() {
+        let sites = vec![Point2D::new(25.0, 25.0), Point2D::new(75.0, 25.0), Point2D::new(75.0, 25.0)];
+
+        // do test;
+        let voronoi = Voronoi::new(&sites, &bounds());
+        assert_eq!(voronoi.len(), 3);
+        assert_eq!(signed_area(voronoi.cell(0)), 2500.0);
+        assert_eq!(signed_area(voronoi.cell(1)), 2500.0);
+        assert_eq!(voronoi.cell(1), voronoi.cell(2));
+
+        let single = Voronoi::new(&sites[..1], &bounds());
+        assert_eq!(signed_area(single.cell(0)), 5000.0);
+        assert!(Voronoi::new(&[], &bounds()).is_empty());
+    }
+
+    #[test]
+    fn test_random_sites() {
+        let mut rng: StdRng = SeedableRng::from_seed(&[53][..]);
+
+        // do test;
+        for round in 0..10 {
+            let sites: Vec<Point2D<f64>> = (0..10 + 20 * round)
+                .map(|_| Point2D::new(rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 50.0)))
+                .collect();
+            let voronoi = Voronoi::new(&sites, &bounds());
+
+            let area: f64 = voronoi.cells().iter().map(|cell| signed_area(cell)).sum();
+            assert!((area - 5000.0).abs() < 1e-6, "{}", area);
+
+            for (site, &point) in sites.iter().enumerate() {
+                assert!(contains(voronoi.cell(site), point));
+            }
+
+            for _ in 0..100 {
+                let query = Point2D::new(rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 50.0));
+                let nearest = (0..sites.len())
+                    .min_by(|&a, &b| query.distance_to(sites[a]).partial_cmp(&query.distance_to(sites[b])).unwrap())
+                    .unwrap();
+                assert!(contains(voronoi.cell(nearest), query));
+            }
+        }
+    }
+}